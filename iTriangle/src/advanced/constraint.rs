@@ -0,0 +1,750 @@
+use crate::geom::triangle::IntTriangle;
+use crate::int::triangulation::RawIntTriangulation;
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_float::triangle::Triangle;
+
+/// A set of mesh edges that must be preserved by Delaunay edge flips.
+///
+/// Each edge is stored as an unordered pair of vertex indices into the mesh `points`.
+#[derive(Debug, Clone, Default)]
+pub struct ConstraintEdges {
+    edges: BTreeSet<[usize; 2]>,
+}
+
+impl ConstraintEdges {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            edges: BTreeSet::new(),
+        }
+    }
+
+    /// Returns true if there are no constrained edges.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Returns the number of constrained edges.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns true if the edge between vertices `a` and `b` is constrained.
+    #[inline]
+    pub fn contains(&self, a: usize, b: usize) -> bool {
+        self.edges.contains(&Self::key(a, b))
+    }
+
+    /// Marks the edge between vertices `a` and `b` as constrained.
+    ///
+    /// Returns false if the edge was already constrained.
+    #[inline]
+    pub fn insert(&mut self, a: usize, b: usize) -> bool {
+        self.edges.insert(Self::key(a, b))
+    }
+
+    /// Removes the constraint from the edge between vertices `a` and `b`.
+    #[inline]
+    pub fn remove(&mut self, a: usize, b: usize) -> bool {
+        self.edges.remove(&Self::key(a, b))
+    }
+
+    /// Iterates over constrained edges as `[min, max]` vertex index pairs.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &[usize; 2]> {
+        self.edges.iter()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.edges.clear();
+    }
+
+    /// Replaces a constrained edge `ab` with `am` and `mb` when `m` is inserted on it.
+    #[inline]
+    pub(crate) fn split(&mut self, a: usize, b: usize, m: usize) -> bool {
+        if self.edges.remove(&Self::key(a, b)) {
+            self.edges.insert(Self::key(a, m));
+            self.edges.insert(Self::key(m, b));
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    pub(crate) fn shift(&mut self, points_offset: usize) {
+        if points_offset == 0 || self.edges.is_empty() {
+            return;
+        }
+        self.edges = self
+            .edges
+            .iter()
+            .map(|e| [e[0] + points_offset, e[1] + points_offset])
+            .collect();
+    }
+
+    #[inline]
+    fn key(a: usize, b: usize) -> [usize; 2] {
+        if a < b {
+            [a, b]
+        } else {
+            [b, a]
+        }
+    }
+}
+
+impl RawIntTriangulation {
+    /// Forces every segment to be an edge of the mesh and marks it as constrained.
+    ///
+    /// Segment ends must already be mesh vertices. A vertex lying exactly on a segment
+    /// splits it in two.
+    ///
+    /// Returns the segments (or their parts between split vertices) that could not be
+    /// inserted, the mesh around them is left unchanged.
+    pub(crate) fn insert_constraints(&mut self, segments: &[[IntPoint; 2]]) -> Vec<[IntPoint; 2]> {
+        let mut missed = Vec::new();
+        if segments.is_empty() {
+            return missed;
+        }
+        if self.triangles.is_empty() {
+            missed.extend(segments.iter().filter(|s| s[0] != s[1]));
+            return missed;
+        }
+
+        let mut sorted_points: Vec<_> = self
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (p, i))
+            .collect();
+        sorted_points.sort_unstable();
+
+        let mut recovery = EdgeRecovery::new(&mut self.triangles, &self.points);
+        let mut segment_missed = Vec::new();
+
+        for s in segments.iter() {
+            if s[0] == s[1] {
+                continue;
+            }
+            segment_missed.clear();
+            segment_missed.push(*s);
+
+            let a_indices = sorted_points.indices_of(s[0]);
+            let b_indices = sorted_points.indices_of(s[1]);
+            'candidates: for &(_, a) in a_indices.iter() {
+                for &(_, b) in b_indices.iter() {
+                    segment_missed.clear();
+                    if recovery.recover(a, b, &mut self.constraints, &mut segment_missed) {
+                        break 'candidates;
+                    }
+                }
+            }
+
+            missed.extend_from_slice(&segment_missed);
+        }
+
+        missed
+    }
+}
+
+trait PointIndexSearch {
+    fn indices_of(&self, p: IntPoint) -> &[(IntPoint, usize)];
+}
+
+impl PointIndexSearch for [(IntPoint, usize)] {
+    #[inline]
+    fn indices_of(&self, p: IntPoint) -> &[(IntPoint, usize)] {
+        let start = self.partition_point(|e| e.0 < p);
+        let end = start + self[start..].partition_point(|e| e.0 == p);
+        &self[start..end]
+    }
+}
+
+enum Walk {
+    Crossing(Vec<[usize; 2]>),
+    Split(usize),
+    Blocked,
+}
+
+struct EdgeRecovery<'a> {
+    triangles: &'a mut [IntTriangle],
+    points: &'a [IntPoint],
+    vertex_triangle: Vec<usize>,
+    star: Vec<usize>,
+    // triangles saved before the flips of the current segment, to roll them back
+    journal: Vec<(usize, IntTriangle)>,
+}
+
+impl<'a> EdgeRecovery<'a> {
+    fn new(triangles: &'a mut [IntTriangle], points: &'a [IntPoint]) -> Self {
+        let mut vertex_triangle = vec![usize::MAX; points.len()];
+        for (i, t) in triangles.iter().enumerate() {
+            for v in t.vertices.iter() {
+                vertex_triangle[v.index] = i;
+            }
+        }
+        Self {
+            triangles,
+            points,
+            vertex_triangle,
+            star: Vec::with_capacity(16),
+            journal: Vec::new(),
+        }
+    }
+
+    // on failure pushes the part of ab that was not inserted into `missed`
+    fn recover(
+        &mut self,
+        a: usize,
+        b: usize,
+        constraints: &mut ConstraintEdges,
+        missed: &mut Vec<[IntPoint; 2]>,
+    ) -> bool {
+        if a == b {
+            return true;
+        }
+        let is_recovered = self.vertex_triangle[a] != usize::MAX
+            && self.vertex_triangle[b] != usize::MAX
+            && match self.walk(a, b, constraints) {
+                Walk::Blocked => false,
+                Walk::Split(m) => {
+                    let is_am = self.recover(a, m, constraints, missed);
+                    let is_mb = self.recover(m, b, constraints, missed);
+                    // the parts are already reported
+                    return is_am && is_mb;
+                }
+                Walk::Crossing(edges) => {
+                    let is_flipped = edges.is_empty() || self.flip_crossing(a, b, edges);
+                    if is_flipped {
+                        constraints.insert(a, b);
+                    }
+                    is_flipped
+                }
+            };
+
+        if !is_recovered {
+            missed.push([self.points[a], self.points[b]]);
+        }
+
+        is_recovered
+    }
+
+    fn collect_star(&mut self, v: usize) {
+        self.star.clear();
+        let start = self.vertex_triangle[v];
+        if start == usize::MAX {
+            return;
+        }
+        self.star.push(start);
+        let mut i = 0;
+        while i < self.star.len() {
+            let t = &self.triangles[self.star[i]];
+            i += 1;
+            for (j, &n) in t.neighbors.iter().enumerate() {
+                // the edge opposite to `v` is not shared with another star triangle
+                if n == usize::MAX || t.vertices[j].index == v || self.star.contains(&n) {
+                    continue;
+                }
+                self.star.push(n);
+            }
+        }
+    }
+
+    fn walk(&mut self, a: usize, b: usize, constraints: &ConstraintEdges) -> Walk {
+        let pa = self.points[a];
+        let pb = self.points[b];
+
+        self.collect_star(a);
+
+        let mut start = None;
+        for &t_index in self.star.iter() {
+            let t = &self.triangles[t_index];
+            let k = t.position_of(a);
+            let u = t.vertices[(k + 1) % 3];
+            let w = t.vertices[(k + 2) % 3];
+
+            if u.index == b || w.index == b {
+                return Walk::Crossing(Vec::new());
+            }
+
+            let ou = orient(pa, pb, u.point);
+            let ow = orient(pa, pb, w.point);
+
+            if ou == 0 && u.point.is_between(pa, pb) {
+                return Walk::Split(u.index);
+            }
+            if ow == 0 && w.point.is_between(pa, pb) {
+                return Walk::Split(w.index);
+            }
+
+            if ou < 0 && ow > 0 {
+                start = Some((t_index, u.index, w.index));
+            }
+        }
+
+        let (mut t_index, mut u, mut w) = if let Some(start) = start {
+            start
+        } else {
+            return Walk::Blocked;
+        };
+
+        let mut crossing = Vec::new();
+        loop {
+            // constrained edges can not be crossed
+            if constraints.contains(u, w) {
+                return Walk::Blocked;
+            }
+            crossing.push([u, w]);
+            let t = &self.triangles[t_index];
+            let n_index = t.neighbors[t.other_vertex(u, w)];
+            if n_index == usize::MAX {
+                return Walk::Blocked;
+            }
+
+            let n = &self.triangles[n_index];
+            let v = n.vertices[n.other_vertex(u, w)];
+            if v.index == b {
+                return Walk::Crossing(crossing);
+            }
+
+            let ov = orient(pa, pb, v.point);
+            if ov == 0 {
+                return if v.point.is_between(pa, pb) {
+                    Walk::Split(v.index)
+                } else {
+                    Walk::Blocked
+                };
+            }
+
+            if ov < 0 {
+                u = v.index;
+            } else {
+                w = v.index;
+            }
+            t_index = n_index;
+        }
+    }
+
+    fn flip_crossing(&mut self, a: usize, b: usize, edges: Vec<[usize; 2]>) -> bool {
+        let pa = self.points[a];
+        let pb = self.points[b];
+
+        let mut queue = alloc::collections::VecDeque::from(edges);
+        self.journal.clear();
+
+        // Sloan's flip algorithm always terminates, the guard only protects
+        // against broken topology
+        let mut guard = 16 + queue.len() * queue.len() * 4;
+
+        while let Some([u, w]) = queue.pop_front() {
+            if guard == 0 {
+                self.rollback();
+                return false;
+            }
+            guard -= 1;
+
+            let (t_index, n_index) = if let Some(pair) = self.edge_triangles(u, w) {
+                pair
+            } else {
+                self.rollback();
+                return false;
+            };
+
+            let t = &self.triangles[t_index];
+            let n = &self.triangles[n_index];
+            let x = t.vertices[t.other_vertex(u, w)];
+            let y = n.vertices[n.other_vertex(u, w)];
+
+            let pu = self.points[u];
+            let pw = self.points[w];
+
+            let ou = orient(x.point, y.point, pu);
+            let ow = orient(x.point, y.point, pw);
+            let is_convex = ou < 0 && ow > 0 || ou > 0 && ow < 0;
+            if !is_convex {
+                queue.push_back([u, w]);
+                continue;
+            }
+
+            self.flip(t_index, n_index);
+
+            let is_touch = x.index == a || x.index == b || y.index == a || y.index == b;
+            if !is_touch {
+                let ox = orient(pa, pb, x.point);
+                let oy = orient(pa, pb, y.point);
+                if ox < 0 && oy > 0 || ox > 0 && oy < 0 {
+                    queue.push_back([x.index, y.index]);
+                }
+            }
+        }
+
+        true
+    }
+
+    // restores the triangles changed by the flips of the current segment
+    fn rollback(&mut self) {
+        while let Some((t_index, triangle)) = self.journal.pop() {
+            for v in triangle.vertices.iter() {
+                self.vertex_triangle[v.index] = t_index;
+            }
+            self.triangles[t_index] = triangle;
+        }
+    }
+
+    fn edge_triangles(&mut self, u: usize, w: usize) -> Option<(usize, usize)> {
+        self.collect_star(u);
+        for &t_index in self.star.iter() {
+            let t = &self.triangles[t_index];
+            if t.vertices.iter().any(|v| v.index == w) {
+                let n_index = t.neighbors[t.other_vertex(u, w)];
+                return if n_index == usize::MAX {
+                    None
+                } else {
+                    Some((t_index, n_index))
+                };
+            }
+        }
+        None
+    }
+
+    fn flip(&mut self, abc_index: usize, pcb_index: usize) {
+        use crate::advanced::delaunay::DelaunayFlip;
+        for t_index in [abc_index, pcb_index] {
+            let t = &self.triangles[t_index];
+            self.journal.push((t_index, t.clone()));
+            for &n_index in t.neighbors.iter() {
+                if n_index != usize::MAX {
+                    self.journal
+                        .push((n_index, self.triangles[n_index].clone()));
+                }
+            }
+        }
+        self.triangles.flip_triangles(abc_index, pcb_index);
+        for t_index in [abc_index, pcb_index] {
+            for v in self.triangles[t_index].vertices.iter() {
+                self.vertex_triangle[v.index] = t_index;
+            }
+        }
+    }
+}
+
+trait Between {
+    fn is_between(&self, a: IntPoint, b: IntPoint) -> bool;
+}
+
+impl Between for IntPoint {
+    // self, a and b are already on one line
+    #[inline]
+    fn is_between(&self, a: IntPoint, b: IntPoint) -> bool {
+        let pa = a.subtract(*self);
+        let pb = b.subtract(*self);
+        pa.dot_product(pb) < 0
+    }
+}
+
+// > 0 if c is on the left side of ab
+#[inline]
+fn orient(a: IntPoint, b: IntPoint, c: IntPoint) -> i64 {
+    -Triangle::area_two_point(a, b, c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::orient;
+    use crate::int::triangulatable::IntTriangulatable;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::area::Area;
+    use i_overlay::i_shape::int::path::IntPath;
+    use rand::RngExt;
+
+    #[test]
+    fn test_0() {
+        // without the constraint the Delaunay condition picks the short diagonal
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, -1),
+            IntPoint::new(20, 0),
+            IntPoint::new(10, 1),
+        ];
+
+        let (raw, missed) =
+            contour.triangulate_with_constraints(&[[IntPoint::new(0, 0), IntPoint::new(20, 0)]]);
+        assert!(missed.is_empty());
+
+        let delaunay = raw.into_delaunay();
+
+        delaunay.validate();
+        assert_eq!(delaunay.constraints.len(), 1);
+
+        let a = delaunay.index_of(IntPoint::new(0, 0));
+        let b = delaunay.index_of(IntPoint::new(20, 0));
+        assert!(delaunay.has_edge(a, b));
+    }
+
+    #[test]
+    fn test_1() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+
+        let (raw, missed) = contour.triangulate_with_constraints(&[
+            [IntPoint::new(2, 2), IntPoint::new(8, 8)],
+            [IntPoint::new(2, 8), IntPoint::new(8, 2)],
+        ]);
+        assert!(missed.is_empty());
+
+        // crossing point is inserted as a new vertex
+        assert_eq!(raw.points.len(), 9);
+        assert_eq!(raw.constraints.len(), 4);
+
+        let delaunay = raw.into_delaunay();
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+
+        for e in delaunay.constraints.iter() {
+            assert!(delaunay.has_edge(e[0], e[1]));
+        }
+    }
+
+    #[test]
+    fn test_2() {
+        // constraint crosses the boundary and splits the boundary edge
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+
+        let (raw, missed) =
+            contour.triangulate_with_constraints(&[[IntPoint::new(-5, 3), IntPoint::new(5, 7)]]);
+        assert!(missed.is_empty());
+
+        let delaunay = raw.into_delaunay();
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert_eq!(delaunay.points.len(), 6);
+        assert_eq!(delaunay.constraints.len(), 1);
+
+        let e = delaunay.constraints.iter().next().unwrap();
+        assert!(delaunay.has_edge(e[0], e[1]));
+    }
+
+    #[test]
+    fn test_3() {
+        // a vertex on the constraint splits it
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+
+        let (raw, missed) = contour.triangulate_with_constraints(&[
+            [IntPoint::new(0, 0), IntPoint::new(10, 10)],
+            [IntPoint::new(5, 5), IntPoint::new(5, 5)],
+            [IntPoint::new(5, 5), IntPoint::new(8, 2)],
+        ]);
+        assert!(missed.is_empty());
+
+        let delaunay = raw.into_delaunay();
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert_eq!(delaunay.constraints.len(), 3);
+        for e in delaunay.constraints.iter() {
+            assert!(delaunay.has_edge(e[0], e[1]));
+        }
+    }
+
+    #[test]
+    fn test_4() {
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(20, 0),
+                IntPoint::new(20, 20),
+                IntPoint::new(0, 20),
+            ],
+            vec![
+                IntPoint::new(8, 8),
+                IntPoint::new(8, 12),
+                IntPoint::new(12, 12),
+                IntPoint::new(12, 8),
+            ],
+        ];
+
+        // the segment passes through the hole and is split into two parts
+        let (raw, missed) =
+            shape.triangulate_with_constraints(&[[IntPoint::new(2, 10), IntPoint::new(18, 10)]]);
+        assert!(missed.is_empty());
+        assert_eq!(raw.constraints.len(), 2);
+
+        let delaunay = raw.into_delaunay().refine_with_circumcenters(4);
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), shape.area_two());
+
+        // refinement splits the parts, every vertex on them starts or ends a constrained edge
+        let mut xs: Vec<_> = delaunay
+            .points
+            .iter()
+            .filter(|p| p.y == 10 && (p.x <= 8 || p.x >= 12) && p.x >= 2 && p.x <= 18)
+            .map(|p| p.x)
+            .collect();
+        xs.sort_unstable();
+        assert_eq!(delaunay.constraints.len(), xs.len() - 2);
+        for w in xs.windows(2) {
+            if w[0] == 8 {
+                continue;
+            }
+            let a = delaunay.index_of(IntPoint::new(w[0], 10));
+            let b = delaunay.index_of(IntPoint::new(w[1], 10));
+            assert!(delaunay.constraints.contains(a, b));
+            assert!(delaunay.has_edge(a, b));
+        }
+    }
+
+    #[test]
+    fn test_5() {
+        // inserted one by one, the second segment crosses the first one
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+        let mut raw = contour.triangulate_with_steiner_points(&[
+            IntPoint::new(2, 2),
+            IntPoint::new(8, 8),
+            IntPoint::new(2, 8),
+            IntPoint::new(8, 2),
+        ]);
+
+        let missed = raw.insert_constraints(&[[IntPoint::new(2, 2), IntPoint::new(8, 8)]]);
+        assert!(missed.is_empty());
+        let triangles: Vec<_> = raw.triangles.iter().map(|t| t.vertices).collect();
+
+        let segments = [
+            [IntPoint::new(2, 8), IntPoint::new(8, 2)],
+            [IntPoint::new(1, 1), IntPoint::new(8, 2)],
+        ];
+        let missed = raw.insert_constraints(&segments);
+        assert_eq!(missed, segments);
+        assert_eq!(raw.constraints.len(), 1);
+
+        // the mesh is left as it was
+        raw.validate();
+        for (t, vertices) in raw.triangles.iter().zip(triangles.iter()) {
+            for (v0, v1) in t.vertices.iter().zip(vertices.iter()) {
+                assert_eq!(v0.index, v1.index);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_0() {
+        let contour = vec![
+            IntPoint::new(-30, -30),
+            IntPoint::new(30, -30),
+            IntPoint::new(30, 30),
+            IntPoint::new(-30, 30),
+        ];
+        for _ in 0..2_000 {
+            let segments = random_segments(58, 12);
+
+            let (raw, missed) = contour.triangulate_with_constraints(&segments);
+            assert!(missed.is_empty());
+            raw.validate();
+
+            let delaunay = raw.into_delaunay();
+            delaunay.validate();
+            assert_eq!(delaunay.area(), contour.area_two());
+
+            // segments do not touch each other, every one is a single constrained edge
+            assert_eq!(delaunay.constraints.len(), segments.len());
+            for s in segments.iter() {
+                let a = delaunay.index_of(s[0]);
+                let b = delaunay.index_of(s[1]);
+                assert!(delaunay.constraints.contains(a, b));
+                assert!(delaunay.has_edge(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_1() {
+        for _ in 0..2_000 {
+            let shape = [random(50, 12)];
+            let segments: Vec<_> = (0..4)
+                .map(|_| {
+                    let s = random(60, 2);
+                    [s[0], s[1]]
+                })
+                .collect();
+
+            let shapes = shape.simplify(FillRule::NonZero, IntOverlayOptions::keep_all_points());
+            let Some(first) = shapes.first() else {
+                continue;
+            };
+
+            let (raw, missed) = first.triangulate_with_constraints(&segments);
+            raw.validate();
+
+            let delaunay = raw.into_delaunay();
+            delaunay.validate();
+            for s in missed.iter() {
+                assert_ne!(s[0], s[1]);
+            }
+        }
+    }
+
+    // segments that do not touch each other
+    fn random_segments(radius: i32, n: usize) -> Vec<[IntPoint; 2]> {
+        let mut segments: Vec<[IntPoint; 2]> = Vec::with_capacity(n);
+        while segments.len() < n {
+            let s = random(radius, 2);
+            let s = [s[0], s[1]];
+            if s[0] == s[1] || segments.iter().any(|other| is_touch(&s, other)) {
+                continue;
+            }
+            segments.push(s);
+        }
+        segments
+    }
+
+    // conservative, collinear segments are always treated as touching
+    fn is_touch(s0: &[IntPoint; 2], s1: &[IntPoint; 2]) -> bool {
+        let o0 = orient(s0[0], s0[1], s1[0]).signum() * orient(s0[0], s0[1], s1[1]).signum();
+        let o1 = orient(s1[0], s1[1], s0[0]).signum() * orient(s1[0], s1[1], s0[1]).signum();
+        o0 <= 0 && o1 <= 0
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
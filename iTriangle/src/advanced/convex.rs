@@ -111,7 +111,9 @@ impl ConvexPolygonBuilder {
     }
 
     fn add(&mut self, edge: Edge, triangle: &IntTriangle) -> bool {
-        let v_index = triangle.opposite(edge.triangle_index);
+        let Some(v_index) = triangle.try_opposite(edge.triangle_index) else {
+            return false;
+        };
         let v = triangle.vertices[v_index];
 
         // a0 -> a1 -> p
@@ -1,6 +1,8 @@
 use crate::advanced::bitset::IndexBitSet;
 use crate::advanced::buffer::DelaunayBuffer;
+use crate::advanced::constraint::ConstraintEdges;
 use crate::geom::triangle::IntTriangle;
+use crate::int::provenance::ProvenanceMap;
 use crate::int::triangulation::RawIntTriangulation;
 use alloc::vec::Vec;
 use i_overlay::i_float::int::point::IntPoint;
@@ -16,14 +18,43 @@ use i_overlay::i_float::u128::UInt128;
 pub struct IntDelaunay {
     pub triangles: Vec<IntTriangle>,
     pub points: Vec<IntPoint>,
+    pub(crate) constraints: ConstraintEdges,
+    pub(crate) hole_origins: ProvenanceMap,
 }
 
 impl IntDelaunay {
+    /// Returns the edges that are kept during edge flips (empty for an unconstrained mesh).
+    ///
+    /// Each edge is a pair of indices into the `points` buffer.
+    #[inline]
+    pub fn constraints(&self) -> &ConstraintEdges {
+        &self.constraints
+    }
+
     #[inline]
     pub(crate) fn into_raw(self) -> RawIntTriangulation {
         RawIntTriangulation {
             triangles: self.triangles,
             points: self.points,
+            constraints: self.constraints,
+            hole_origins: self.hole_origins,
+        }
+    }
+
+    /// Restores the Delaunay condition around the given triangles, respecting constraints.
+    #[inline]
+    // returns the number of flips
+    pub(crate) fn fix_triangles(
+        &mut self,
+        indices: &mut Vec<usize>,
+        bitset: &mut IndexBitSet,
+    ) -> usize {
+        if self.constraints.is_empty() {
+            self.triangles
+                .fix_triangles_locked(&FreeEdges, indices, bitset)
+        } else {
+            self.triangles
+                .fix_triangles_locked(&self.constraints, indices, bitset)
         }
     }
 }
@@ -46,6 +77,8 @@ impl RawIntTriangulation {
     ///
     /// Uses the provided scratch buffer to avoid repeated allocations across calls.
     ///
+    /// Constrained edges are never flipped.
+    ///
     /// # Returns
     /// A new [`IntDelaunay`] structure with updated triangle connectivity.
     #[inline]
@@ -53,9 +86,17 @@ impl RawIntTriangulation {
         let mut delaunay = IntDelaunay {
             triangles: self.triangles,
             points: self.points,
+            constraints: self.constraints,
+            hole_origins: self.hole_origins,
         };
 
-        delaunay.triangles.build_with_buffer(buffer);
+        if delaunay.constraints.is_empty() {
+            delaunay.triangles.build_with_buffer(buffer);
+        } else {
+            delaunay
+                .triangles
+                .build_with_constraints(&delaunay.constraints, buffer);
+        }
 
         delaunay
     }
@@ -68,6 +109,48 @@ pub trait DelaunayRefine {
     fn fix_triangle(&mut self, abc_index: usize, bitset: &mut IndexBitSet);
     fn update_neighbor(&mut self, neighbor_index: usize, old_index: usize, new_index: usize);
     fn swap_triangles(&mut self, abc_index: usize, pcb_index: usize) -> bool;
+    fn build_with_constraints(
+        &mut self,
+        constraints: &ConstraintEdges,
+        buffer: &mut DelaunayBuffer,
+    );
+    fn fix_triangles_with_constraints(
+        &mut self,
+        constraints: &ConstraintEdges,
+        indices: &mut Vec<usize>,
+        bitset: &mut IndexBitSet,
+    );
+    fn swap_triangles_with_constraints(
+        &mut self,
+        constraints: &ConstraintEdges,
+        abc_index: usize,
+        pcb_index: usize,
+    ) -> bool;
+}
+
+/// Tells the flip procedure which edges must stay untouched.
+pub(crate) trait EdgeLock {
+    fn is_locked(&self, a: usize, b: usize) -> bool;
+}
+
+struct FreeEdges;
+
+impl EdgeLock for FreeEdges {
+    #[inline(always)]
+    fn is_locked(&self, _a: usize, _b: usize) -> bool {
+        false
+    }
+}
+
+impl EdgeLock for ConstraintEdges {
+    #[inline]
+    fn is_locked(&self, a: usize, b: usize) -> bool {
+        self.contains(a, b)
+    }
+}
+
+pub(crate) trait DelaunayFlip {
+    fn flip_triangles(&mut self, abc_index: usize, pcb_index: usize);
 }
 
 impl DelaunayRefine for [IntTriangle] {
@@ -79,17 +162,98 @@ impl DelaunayRefine for [IntTriangle] {
 
     #[inline]
     fn build_with_buffer(&mut self, buffer: &mut DelaunayBuffer) {
+        self.build_locked(&FreeEdges, buffer);
+    }
+
+    #[inline]
+    fn fix_triangles(&mut self, indices: &mut Vec<usize>, bitset: &mut IndexBitSet) {
+        self.fix_triangles_locked(&FreeEdges, indices, bitset);
+    }
+
+    #[inline]
+    fn fix_triangle(&mut self, abc_index: usize, unchecked: &mut IndexBitSet) {
+        self.fix_triangle_locked(&FreeEdges, abc_index, unchecked);
+    }
+
+    #[inline]
+    fn update_neighbor(&mut self, neighbor_index: usize, old_index: usize, new_index: usize) {
+        if neighbor_index >= self.len() {
+            return;
+        }
+        self[neighbor_index].update_neighbor(old_index, new_index);
+    }
+
+    #[inline]
+    fn swap_triangles(&mut self, abc_index: usize, pcb_index: usize) -> bool {
+        self.swap_triangles_locked(&FreeEdges, abc_index, pcb_index)
+    }
+
+    #[inline]
+    fn build_with_constraints(
+        &mut self,
+        constraints: &ConstraintEdges,
+        buffer: &mut DelaunayBuffer,
+    ) {
+        self.build_locked(constraints, buffer);
+    }
+
+    #[inline]
+    fn fix_triangles_with_constraints(
+        &mut self,
+        constraints: &ConstraintEdges,
+        indices: &mut Vec<usize>,
+        bitset: &mut IndexBitSet,
+    ) {
+        self.fix_triangles_locked(constraints, indices, bitset);
+    }
+
+    #[inline]
+    fn swap_triangles_with_constraints(
+        &mut self,
+        constraints: &ConstraintEdges,
+        abc_index: usize,
+        pcb_index: usize,
+    ) -> bool {
+        self.swap_triangles_locked(constraints, abc_index, pcb_index)
+    }
+}
+
+trait LockedDelaunayRefine {
+    fn build_locked<L: EdgeLock>(&mut self, lock: &L, buffer: &mut DelaunayBuffer);
+    fn fix_triangles_locked<L: EdgeLock>(
+        &mut self,
+        lock: &L,
+        indices: &mut Vec<usize>,
+        bitset: &mut IndexBitSet,
+    ) -> usize;
+    fn fix_triangle_locked<L: EdgeLock>(
+        &mut self,
+        lock: &L,
+        abc_index: usize,
+        unchecked: &mut IndexBitSet,
+    ) -> usize;
+    fn swap_triangles_locked<L: EdgeLock>(
+        &mut self,
+        lock: &L,
+        abc_index: usize,
+        pcb_index: usize,
+    ) -> bool;
+}
+
+impl LockedDelaunayRefine for [IntTriangle] {
+    #[inline]
+    fn build_locked<L: EdgeLock>(&mut self, lock: &L, buffer: &mut DelaunayBuffer) {
         let mut bitset = buffer.bitset.take().unwrap_or_default();
         bitset.clear_and_resize(self.len());
         for abc_index in 0..self.len() {
-            self.fix_triangle(abc_index, &mut bitset);
+            self.fix_triangle_locked(lock, abc_index, &mut bitset);
         }
 
         let mut indices = buffer.indices.take().unwrap_or_default();
         bitset.read_and_clean(&mut indices);
 
         if !indices.is_empty() {
-            self.fix_triangles(&mut indices, &mut bitset);
+            self.fix_triangles_locked(lock, &mut indices, &mut bitset);
         }
 
         buffer.bitset = Some(bitset);
@@ -97,20 +261,33 @@ impl DelaunayRefine for [IntTriangle] {
     }
 
     #[inline]
-    fn fix_triangles(&mut self, indices: &mut Vec<usize>, bitset: &mut IndexBitSet) {
+    fn fix_triangles_locked<L: EdgeLock>(
+        &mut self,
+        lock: &L,
+        indices: &mut Vec<usize>,
+        bitset: &mut IndexBitSet,
+    ) -> usize {
         debug_assert!(!indices.is_empty());
         debug_assert!(bitset.is_empty());
+        let mut flips = 0;
         while !indices.is_empty() {
             for &abc_index in indices.iter() {
-                self.fix_triangle(abc_index, bitset);
+                flips += self.fix_triangle_locked(lock, abc_index, bitset);
             }
             bitset.read_and_clean(indices);
         }
+        flips
     }
 
     #[inline]
-    fn fix_triangle(&mut self, abc_index: usize, unchecked: &mut IndexBitSet) {
+    fn fix_triangle_locked<L: EdgeLock>(
+        &mut self,
+        lock: &L,
+        abc_index: usize,
+        unchecked: &mut IndexBitSet,
+    ) -> usize {
         // loop by same triangle increase cache locality
+        let mut flips = 0;
         let mut skip = usize::MAX;
         let mut perfect = false;
         while !perfect {
@@ -121,7 +298,8 @@ impl DelaunayRefine for [IntTriangle] {
                     continue;
                 }
 
-                if self.swap_triangles(abc_index, pbc_index) {
+                if self.swap_triangles_locked(lock, abc_index, pbc_index) {
+                    flips += 1;
                     skip = pbc_index;
                     unchecked.insert(pbc_index);
                     perfect = false;
@@ -130,23 +308,26 @@ impl DelaunayRefine for [IntTriangle] {
             }
         }
         unchecked.remove(abc_index);
+        flips
     }
 
     #[inline]
-    fn update_neighbor(&mut self, neighbor_index: usize, old_index: usize, new_index: usize) {
-        if neighbor_index >= self.len() {
-            return;
-        }
-        self[neighbor_index].update_neighbor(old_index, new_index);
-    }
-
-    #[inline]
-    fn swap_triangles(&mut self, abc_index: usize, pcb_index: usize) -> bool {
+    fn swap_triangles_locked<L: EdgeLock>(
+        &mut self,
+        lock: &L,
+        abc_index: usize,
+        pcb_index: usize,
+    ) -> bool {
         // abc_index & pcb_index can not be more self.triangles.len()
         let t_abc = unsafe { self.get_unchecked(abc_index) };
         let t_pcb = unsafe { self.get_unchecked(pcb_index) };
         let abc = t_abc.abc_by_neighbor(pcb_index);
         let pcb = t_pcb.abc_by_neighbor(abc_index);
+
+        if lock.is_locked(abc.v1.vertex.index, abc.v2.vertex.index) {
+            return false;
+        }
+
         if DelaunayCondition::is_flip_not_required(
             pcb.v0.vertex.point, // p
             abc.v0.vertex.point, // a
@@ -156,6 +337,18 @@ impl DelaunayRefine for [IntTriangle] {
             return false;
         }
 
+        self.flip_triangles(abc_index, pcb_index);
+
+        true
+    }
+}
+
+impl DelaunayFlip for [IntTriangle] {
+    #[inline]
+    fn flip_triangles(&mut self, abc_index: usize, pcb_index: usize) {
+        let abc = self[abc_index].abc_by_neighbor(pcb_index);
+        let pcb = self[pcb_index].abc_by_neighbor(abc_index);
+
         // abc and pcb are clock-wised ordered triangles
 
         // abc -> abp
@@ -175,8 +368,6 @@ impl DelaunayRefine for [IntTriangle] {
         pca.neighbors[pcb.v1.position] = abc_index;
         pca.neighbors[pcb.v2.position] = pcb.v2.neighbor;
         pca.vertices[pcb.v2.position] = abc.v0.vertex;
-
-        true
     }
 }
 
@@ -253,7 +444,7 @@ impl IntTriangle {
 
 #[cfg(test)]
 impl IntDelaunay {
-    fn validate(&self) {
+    pub(crate) fn validate(&self) {
         use i_overlay::i_float::triangle::Triangle;
 
         for (i, t) in self.triangles.iter().enumerate() {
@@ -279,7 +470,7 @@ impl IntDelaunay {
         }
     }
 
-    fn area(&self) -> i64 {
+    pub(crate) fn area(&self) -> i64 {
         use i_overlay::i_float::triangle::Triangle;
         let mut s = 0;
         for t in self.triangles.iter() {
@@ -292,6 +483,46 @@ impl IntDelaunay {
 
         s
     }
+
+    pub(crate) fn index_of(&self, p: IntPoint) -> usize {
+        self.points.iter().position(|&v| v == p).unwrap()
+    }
+
+    pub(crate) fn has_edge(&self, a: usize, b: usize) -> bool {
+        self.triangles.iter().any(|t| {
+            t.vertices.iter().any(|v| v.index == a) && t.vertices.iter().any(|v| v.index == b)
+        })
+    }
+
+    // exact check: no neighbor vertex lies strictly inside a circumcircle
+    pub(crate) fn is_delaunay(&self) -> bool {
+        for t in self.triangles.iter() {
+            let [a, b, c] = t.vertices.map(|v| v.point);
+            for &n in t.neighbors.iter() {
+                if n >= self.triangles.len() {
+                    continue;
+                }
+                for v in self.triangles[n].vertices.iter() {
+                    if is_in_circle(a, b, c, v.point) {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+// `true` if p is strictly inside the circumcircle of the mesh triangle abc
+#[inline]
+pub(crate) fn is_in_circle(a: IntPoint, b: IntPoint, c: IntPoint, p: IntPoint) -> bool {
+    let (ax, ay) = ((a.x - p.x) as i128, (a.y - p.y) as i128);
+    let (bx, by) = ((b.x - p.x) as i128, (b.y - p.y) as i128);
+    let (cx, cy) = ((c.x - p.x) as i128, (c.y - p.y) as i128);
+    let det = (ax * ax + ay * ay) * (bx * cy - cx * by) - (bx * bx + by * by) * (ax * cy - cx * ay)
+        + (cx * cx + cy * cy) * (ax * by - bx * ay);
+    // mesh triangles are counter-clockwise, so inside is positive
+    det > 0
 }
 
 #[cfg(test)]
@@ -426,6 +657,8 @@ mod tests {
                 },
             ],
             points,
+            constraints: Default::default(),
+            hole_origins: Default::default(),
         };
 
         let is_swapped = delaunay.triangles.swap_triangles(0, 1);
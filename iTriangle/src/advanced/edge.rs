@@ -0,0 +1,453 @@
+use crate::advanced::constraint::ConstraintEdges;
+use crate::advanced::delaunay::IntDelaunay;
+use crate::geom::point::IndexPoint;
+use crate::geom::triangle::IntTriangle;
+use crate::int::provenance::ProvenanceMap;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The role of a triangle edge in the mesh.
+///
+/// Edge `i` of a triangle is the edge opposite `vertices[i]`, the same convention
+/// as `triangle_neighbors`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// The edge lies on an outer outline of the mesh.
+    OuterBoundary,
+    /// The edge lies on a hole outline.
+    ///
+    /// `contour` is the index of the input hole contour within its shape, the one
+    /// most vertices of the loop come from. The input vertices stay in the mesh, so
+    /// the index does not change when points are inserted or the mesh is refined.
+    /// It is `None` for a loop without input hole vertices, e.g. a hole cut by a
+    /// self-crossing outer contour. Holes merged by validation report one of their contours.
+    /// Outlines that touch at a vertex are split into separate loops there, a loop
+    /// that runs clockwise encloses an uncovered area and counts as a hole.
+    HoleBoundary { contour: Option<usize> },
+    /// The edge is shared by two triangles.
+    Interior,
+    /// The edge is shared by two triangles and was forced into the mesh by a constraint.
+    Constraint,
+}
+
+pub(crate) trait EdgeClassification {
+    fn edge_kinds(
+        &self,
+        constraints: &ConstraintEdges,
+        holes: &ProvenanceMap,
+    ) -> Vec<[EdgeKind; 3]>;
+}
+
+impl EdgeClassification for [IntTriangle] {
+    fn edge_kinds(
+        &self,
+        constraints: &ConstraintEdges,
+        holes: &ProvenanceMap,
+    ) -> Vec<[EdgeKind; 3]> {
+        let mut kinds: Vec<[EdgeKind; 3]> = self
+            .iter()
+            .map(|t| {
+                core::array::from_fn(|i| {
+                    let a = t.vertices[(i + 1) % 3].index;
+                    let b = t.vertices[(i + 2) % 3].index;
+                    if !constraints.is_empty() && constraints.contains(a, b) {
+                        EdgeKind::Constraint
+                    } else {
+                        EdgeKind::Interior
+                    }
+                })
+            })
+            .collect();
+
+        let mut visited = vec![false; 3 * self.len()];
+        let mut outline = Vec::new();
+        let mut stack = Vec::new();
+        let mut positions = BTreeMap::new();
+        let mut votes = BTreeMap::new();
+
+        for start in 0..self.len() {
+            for i in 0..3 {
+                if self[start].neighbors[i] < self.len() || visited[3 * start + i] {
+                    continue;
+                }
+
+                self.walk_outline(start, i, &mut visited, &mut outline);
+
+                // a hole touching another outline at a vertex is walked in one pass,
+                // split the walk into simple loops at repeated vertices
+                stack.clear();
+                positions.clear();
+                for &(t, j) in outline.iter() {
+                    let [a, b] = self[t].edge(j);
+                    positions.insert(a.index, stack.len());
+                    stack.push((t, j));
+
+                    let Some(&p) = positions.get(&b.index) else {
+                        continue;
+                    };
+
+                    let mut area = 0;
+                    for &(t, j) in stack[p..].iter() {
+                        let [a, b] = self[t].edge(j);
+                        positions.remove(&a.index);
+                        area += a.point.x as i64 * b.point.y as i64
+                            - b.point.x as i64 * a.point.y as i64;
+                    }
+
+                    // loops follow the triangle direction: outer ones are counter-clockwise
+                    let kind = if area > 0 {
+                        EdgeKind::OuterBoundary
+                    } else {
+                        EdgeKind::HoleBoundary {
+                            contour: self.hole_contour(&stack[p..], holes, &mut votes),
+                        }
+                    };
+
+                    for (t, j) in stack.drain(p..) {
+                        kinds[t][j] = kind;
+                    }
+                }
+            }
+        }
+
+        kinds
+    }
+}
+
+trait HoleNaming {
+    fn hole_contour(
+        &self,
+        hole: &[(usize, usize)],
+        holes: &ProvenanceMap,
+        votes: &mut BTreeMap<usize, usize>,
+    ) -> Option<usize>;
+}
+
+impl HoleNaming for [IntTriangle] {
+    // the input contour most vertices of the loop belong to, the smaller index wins a tie
+    fn hole_contour(
+        &self,
+        hole: &[(usize, usize)],
+        holes: &ProvenanceMap,
+        votes: &mut BTreeMap<usize, usize>,
+    ) -> Option<usize> {
+        if holes.is_empty() {
+            return None;
+        }
+
+        votes.clear();
+        for &(t, j) in hole.iter() {
+            let [a, _] = self[t].edge(j);
+            for contour in holes.contours_at(a.point) {
+                *votes.entry(contour).or_insert(0) += 1;
+            }
+        }
+
+        let mut best: Option<(usize, usize)> = None;
+        for (&contour, &count) in votes.iter() {
+            if best.is_none_or(|(_, c)| count > c) {
+                best = Some((contour, count));
+            }
+        }
+
+        best.map(|(contour, _)| contour)
+    }
+}
+
+trait OutlineWalk {
+    fn walk_outline(
+        &self,
+        start: usize,
+        edge: usize,
+        visited: &mut [bool],
+        outline: &mut Vec<(usize, usize)>,
+    );
+}
+
+impl OutlineWalk for [IntTriangle] {
+    // collects the boundary edges of one closed outline in walk order
+    fn walk_outline(
+        &self,
+        start: usize,
+        edge: usize,
+        visited: &mut [bool],
+        outline: &mut Vec<(usize, usize)>,
+    ) {
+        outline.clear();
+        let (mut t, mut i) = (start, edge);
+
+        loop {
+            visited[3 * t + i] = true;
+            outline.push((t, i));
+
+            // rotate around b to the next boundary edge that starts at b
+            let b = self[t].vertices[(i + 2) % 3].index;
+            let mut pb = (i + 2) % 3;
+            let mut guard = self.len();
+            loop {
+                let j = (pb + 2) % 3;
+                let neighbor = self[t].neighbors[j];
+                if neighbor >= self.len() {
+                    i = j;
+                    break;
+                }
+                t = neighbor;
+                pb = self[t].position_of(b);
+                guard -= 1;
+                if guard == 0 {
+                    return;
+                }
+            }
+
+            if visited[3 * t + i] {
+                return;
+            }
+        }
+    }
+}
+
+impl IntTriangle {
+    // the edge opposite vertex i, in the triangle direction
+    #[inline]
+    fn edge(&self, i: usize) -> [IndexPoint; 2] {
+        [self.vertices[(i + 1) % 3], self.vertices[(i + 2) % 3]]
+    }
+}
+
+impl IntDelaunay {
+    /// Returns the [`EdgeKind`] of every triangle edge.
+    ///
+    /// `edge_kinds()[t][i]` describes the edge of triangle `t` opposite its vertex `i`.
+    #[inline]
+    pub fn edge_kinds(&self) -> Vec<[EdgeKind; 3]> {
+        self.triangles
+            .edge_kinds(&self.constraints, &self.hole_origins)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::advanced::delaunay::IntDelaunay;
+    use crate::advanced::edge::EdgeKind;
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::int::unchecked::IntUncheckedTriangulatable;
+    use alloc::collections::BTreeMap;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::path::IntPath;
+    use rand::RngExt;
+
+    fn count(kinds: &[[EdgeKind; 3]], kind: EdgeKind) -> usize {
+        kinds.iter().flatten().filter(|&&k| k == kind).count()
+    }
+
+    fn hole(contour: usize) -> EdgeKind {
+        EdgeKind::HoleBoundary {
+            contour: Some(contour),
+        }
+    }
+
+    #[test]
+    fn test_0() {
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(30, 0),
+                IntPoint::new(30, 10),
+                IntPoint::new(0, 10),
+            ],
+            vec![
+                IntPoint::new(2, 2),
+                IntPoint::new(2, 8),
+                IntPoint::new(8, 8),
+                IntPoint::new(8, 2),
+            ],
+            vec![
+                IntPoint::new(12, 2),
+                IntPoint::new(12, 8),
+                IntPoint::new(18, 8),
+                IntPoint::new(18, 2),
+            ],
+        ];
+
+        let raw = shape.triangulate();
+        let kinds = raw.edge_kinds();
+        assert_eq!(kinds.len(), raw.triangles.len());
+
+        assert_eq!(count(&kinds, EdgeKind::OuterBoundary), 4);
+        assert_eq!(count(&kinds, hole(1)), 4);
+        assert_eq!(count(&kinds, hole(2)), 4);
+
+        for (triangle, kind) in raw.triangles.iter().zip(kinds.iter()) {
+            for (&neighbor, &k) in triangle.neighbors.iter().zip(kind.iter()) {
+                assert_eq!(k == EdgeKind::Interior, neighbor != usize::MAX);
+            }
+        }
+    }
+
+    #[test]
+    fn test_1() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(20, 0),
+            IntPoint::new(20, 20),
+            IntPoint::new(0, 20),
+        ];
+        let segments = [[IntPoint::new(5, 5), IntPoint::new(15, 15)]];
+        let delaunay = contour
+            .triangulate_with_constraints(&segments)
+            .0
+            .into_delaunay();
+
+        let kinds = delaunay.edge_kinds();
+        assert_eq!(count(&kinds, EdgeKind::OuterBoundary), 4);
+        // a constrained edge is seen from both sides
+        assert_eq!(count(&kinds, EdgeKind::Constraint), 2);
+    }
+
+    #[test]
+    fn test_2() {
+        let mut points = Vec::new();
+        for x in 0..4 {
+            for y in 0..4 {
+                points.push(IntPoint::new(10 * x, 10 * y));
+            }
+        }
+        let delaunay = IntDelaunay::from_points(&points);
+        let kinds = delaunay.edge_kinds();
+        assert_eq!(count(&kinds, EdgeKind::OuterBoundary), 12);
+        assert!(!kinds
+            .iter()
+            .flatten()
+            .any(|k| matches!(k, EdgeKind::HoleBoundary { .. })));
+    }
+
+    #[test]
+    fn test_3() {
+        // the hole touches the outer contour at a vertex
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(10, 0),
+                IntPoint::new(10, 10),
+                IntPoint::new(0, 10),
+            ],
+            vec![
+                IntPoint::new(0, 5),
+                IntPoint::new(5, 7),
+                IntPoint::new(5, 3),
+            ],
+        ];
+
+        let kinds = shape.triangulate().edge_kinds();
+        assert_eq!(count(&kinds, EdgeKind::OuterBoundary), 5);
+        assert_eq!(count(&kinds, hole(1)), 3);
+    }
+
+    #[test]
+    fn test_4() {
+        // the holes come in reverse order and keep their input index through refinement
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(300, 0),
+                IntPoint::new(300, 100),
+                IntPoint::new(0, 100),
+            ],
+            vec![
+                IntPoint::new(220, 20),
+                IntPoint::new(220, 80),
+                IntPoint::new(280, 80),
+                IntPoint::new(280, 20),
+            ],
+            vec![
+                IntPoint::new(20, 20),
+                IntPoint::new(20, 80),
+                IntPoint::new(80, 80),
+                IntPoint::new(80, 20),
+            ],
+        ];
+
+        let delaunay = shape.triangulate().into_delaunay();
+        let count_before = delaunay.points.len();
+        let (delaunay, _) = delaunay.refine_to_min_angle(25.0, 200, None);
+        assert!(delaunay.points.len() > count_before);
+
+        let kinds = delaunay.edge_kinds();
+        let mut hole_x = BTreeMap::new();
+        for (triangle, kind) in delaunay.triangles.iter().zip(kinds.iter()) {
+            for (i, &k) in kind.iter().enumerate() {
+                if let EdgeKind::HoleBoundary { contour } = k {
+                    let [a, b] = triangle.edge(i).map(|v| v.point.x);
+                    let range = hole_x.entry(contour).or_insert((a, a));
+                    range.0 = range.0.min(a).min(b);
+                    range.1 = range.1.max(a).max(b);
+                }
+            }
+        }
+
+        // the holes were split by refinement but every part is still named after its contour
+        assert!(count(&kinds, hole(1)) > 4);
+        assert!(count(&kinds, hole(2)) > 4);
+        assert_eq!(hole_x.len(), 2);
+        assert_eq!(hole_x[&Some(1)], (220, 280));
+        assert_eq!(hole_x[&Some(2)], (20, 80));
+    }
+
+    #[test]
+    fn test_random_0() {
+        for _ in 0..5_000 {
+            let shapes = [vec![random(30, 10), random(20, 8)], vec![random(20, 6)]]
+                .simplify(FillRule::NonZero, IntOverlayOptions::keep_all_points());
+            let edge_count: usize = shapes.iter().flatten().map(|c| c.len()).sum();
+
+            let raw = shapes.uncheck_triangulate();
+            let kinds = raw.edge_kinds();
+
+            // every hole loop runs clockwise, all outer loops together run counter-clockwise
+            let mut outer_area = 0;
+            let mut hole_areas = BTreeMap::new();
+            let mut boundary_count = 0;
+            for (triangle, kind) in raw.triangles.iter().zip(kinds.iter()) {
+                for (i, &k) in kind.iter().enumerate() {
+                    let [a, b] = triangle.edge(i).map(|v| v.point);
+                    let cross = a.x as i64 * b.y as i64 - b.x as i64 * a.y as i64;
+                    match k {
+                        EdgeKind::OuterBoundary => outer_area += cross,
+                        EdgeKind::HoleBoundary { contour } => {
+                            *hole_areas.entry(contour).or_insert(0) += cross;
+                        }
+                        _ => {
+                            assert!(triangle.neighbors[i] < raw.triangles.len());
+                            continue;
+                        }
+                    }
+                    assert_eq!(triangle.neighbors[i], usize::MAX);
+                    boundary_count += 1;
+                }
+            }
+
+            assert_eq!(boundary_count, edge_count);
+            assert!(raw.triangles.is_empty() || outer_area > 0);
+            assert!(hole_areas.values().all(|&a| a < 0));
+        }
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
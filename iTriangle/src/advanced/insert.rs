@@ -0,0 +1,706 @@
+use crate::advanced::bitset::IndexBitSet;
+use crate::advanced::delaunay::{DelaunayRefine, IntDelaunay};
+use crate::geom::point::IndexPoint;
+use crate::geom::triangle::{Abc, IntTriangle};
+use alloc::vec;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_float::triangle::Triangle;
+
+/// The outcome of [`IntDelaunay::insert_point`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InsertResult {
+    /// The point became a new mesh vertex.
+    ///
+    /// - `vertex_index`: index of the new vertex in `points`
+    /// - `triangles`: sorted indices of the triangles around the new vertex,
+    ///   these are all triangles created or changed by the insertion
+    Inserted {
+        vertex_index: usize,
+        triangles: Vec<usize>,
+    },
+    /// The point coincides with an existing vertex, the mesh is unchanged.
+    OnExistingVertex { vertex_index: usize },
+    /// The point is outside the mesh, the mesh is unchanged.
+    Outside,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MeshLocation {
+    Inside(usize),
+    // triangle index and position of the vertex opposite the edge
+    OnEdge(usize, usize),
+    OnVertex(usize),
+    Outside,
+}
+
+impl IntDelaunay {
+    /// Inserts a single point into the mesh and restores the Delaunay condition.
+    ///
+    /// The containing triangle is found by walking the triangle neighbors, a point
+    /// the walk can not reach, e.g. in another part of the mesh, by a scan of all
+    /// triangles. A point inside a triangle splits it into three, a point on an edge splits
+    /// the edge and both adjacent triangles. A split constrained edge stays constrained.
+    ///
+    /// # Parameters
+    /// - `p`: the point to insert
+    ///
+    /// # Returns
+    /// An [`InsertResult`] with the new vertex index and the affected triangles.
+    pub fn insert_point(&mut self, p: IntPoint) -> InsertResult {
+        let location = self.locate(self.triangles.len().wrapping_sub(1), p);
+
+        let vertex_index = self.points.len();
+        let vp = IndexPoint::new(vertex_index, p);
+
+        let mut buffer = Vec::with_capacity(4);
+        match location {
+            MeshLocation::Outside => return InsertResult::Outside,
+            MeshLocation::OnVertex(vertex_index) => {
+                return InsertResult::OnExistingVertex { vertex_index };
+            }
+            MeshLocation::Inside(abc_index) => {
+                self.points.push(p);
+                buffer.extend_from_slice(&self.split_inner(abc_index, vp));
+            }
+            MeshLocation::OnEdge(abc_index, position) => {
+                self.points.push(p);
+                let abc = match position {
+                    0 => self.triangles[abc_index].abc_by_a(),
+                    1 => self.triangles[abc_index].abc_by_b(),
+                    _ => self.triangles[abc_index].abc_by_c(),
+                };
+                self.split_edge(abc_index, abc, vp, &mut buffer);
+            }
+        }
+
+        let start = buffer[0];
+        let mut bitset = IndexBitSet::with_size(self.triangles.len());
+        self.fix_triangles(&mut buffer, &mut bitset);
+
+        InsertResult::Inserted {
+            vertex_index,
+            triangles: self.vertex_star(start, vertex_index),
+        }
+    }
+
+    /// Splits the edge bc of abc (and its neighbor pcb, if any) at the vertex `vm`.
+    ///
+    /// `vm` must lie strictly inside the edge, `vm.point` must already be in `points`.
+    /// A constrained edge is split into two constrained edges.
+    /// Indices of the new and changed triangles are appended to `buffer`.
+    pub(crate) fn split_edge(
+        &mut self,
+        abc_index: usize,
+        abc: Abc,
+        vm: IndexPoint,
+        buffer: &mut Vec<usize>,
+    ) {
+        let b_index = abc.v1.vertex.index;
+        let c_index = abc.v2.vertex.index;
+        if self.constraints.contains(b_index, c_index) {
+            self.constraints.split(b_index, c_index, vm.index);
+        }
+
+        let pcb_index = abc.v0.neighbor;
+        if pcb_index < self.triangles.len() {
+            let pcb = self.triangles[pcb_index].abc_by_neighbor(abc_index);
+            buffer.extend_from_slice(
+                &self.split_edge_with_neighbor(abc_index, abc, pcb_index, pcb, vm),
+            );
+        } else {
+            buffer.extend_from_slice(&self.split_alone_edge(abc_index, abc, vm));
+        }
+    }
+
+    fn split_edge_with_neighbor(
+        &mut self,
+        abc_index: usize,
+        abc: Abc,
+        pcb_index: usize,
+        pcb: Abc,
+        vm: IndexPoint,
+    ) -> [usize; 4] {
+        // abc -> abm
+        // pcb -> pcm
+        let abm_index = abc_index;
+        let pcm_index = pcb_index;
+
+        let amc_index = self.triangles.len();
+        let pmb_index = amc_index + 1;
+
+        let abm = IntTriangle {
+            vertices: [abc.v0.vertex, abc.v1.vertex, vm],
+            neighbors: [pmb_index, amc_index, abc.v2.neighbor],
+        };
+
+        let amc = IntTriangle {
+            vertices: [abc.v0.vertex, vm, abc.v2.vertex],
+            neighbors: [pcm_index, abc.v1.neighbor, abm_index],
+        };
+
+        let pmb = IntTriangle {
+            vertices: [pcb.v0.vertex, vm, pcb.v2.vertex],
+            neighbors: [abm_index, pcb.v1.neighbor, pcm_index],
+        };
+
+        let pcm = IntTriangle {
+            vertices: [pcb.v0.vertex, pcb.v1.vertex, vm],
+            neighbors: [amc_index, pmb_index, pcb.v2.neighbor],
+        };
+
+        self.triangles
+            .update_neighbor(abc.v1.neighbor, abc_index, amc_index);
+        self.triangles
+            .update_neighbor(abc.v2.neighbor, abc_index, abm_index);
+
+        self.triangles
+            .update_neighbor(pcb.v1.neighbor, pcb_index, pmb_index);
+        self.triangles
+            .update_neighbor(pcb.v2.neighbor, pcb_index, pcm_index);
+
+        self.triangles[abm_index] = abm;
+        self.triangles[pcm_index] = pcm;
+        self.triangles.push(amc);
+        self.triangles.push(pmb);
+
+        [abm_index, pcm_index, amc_index, pmb_index]
+    }
+
+    fn split_alone_edge(&mut self, abc_index: usize, abc: Abc, vm: IndexPoint) -> [usize; 2] {
+        let abm_index = abc_index;
+        let amc_index = self.triangles.len();
+
+        let abm = IntTriangle {
+            vertices: [abc.v0.vertex, abc.v1.vertex, vm],
+            neighbors: [usize::MAX, amc_index, abc.v2.neighbor],
+        };
+
+        let amc = IntTriangle {
+            vertices: [abc.v0.vertex, vm, abc.v2.vertex],
+            neighbors: [usize::MAX, abc.v1.neighbor, abm_index],
+        };
+
+        self.triangles
+            .update_neighbor(abc.v1.neighbor, abc_index, amc_index);
+        self.triangles
+            .update_neighbor(abc.v2.neighbor, abc_index, abm_index);
+
+        self.triangles[abm_index] = abm;
+        self.triangles.push(amc);
+
+        [abm_index, amc_index]
+    }
+
+    // abc -> pbc, apc, abp
+    pub(crate) fn split_inner(&mut self, abc_index: usize, vp: IndexPoint) -> [usize; 3] {
+        let abc = self.triangles[abc_index].clone();
+        let [a, b, c] = abc.vertices;
+        let [na, nb, nc] = abc.neighbors;
+
+        let pbc_index = abc_index;
+        let apc_index = self.triangles.len();
+        let abp_index = apc_index + 1;
+
+        let pbc = IntTriangle {
+            vertices: [vp, b, c],
+            neighbors: [na, apc_index, abp_index],
+        };
+
+        let apc = IntTriangle {
+            vertices: [a, vp, c],
+            neighbors: [pbc_index, nb, abp_index],
+        };
+
+        let abp = IntTriangle {
+            vertices: [a, b, vp],
+            neighbors: [pbc_index, apc_index, nc],
+        };
+
+        self.triangles.update_neighbor(nb, abc_index, apc_index);
+        self.triangles.update_neighbor(nc, abc_index, abp_index);
+
+        self.triangles[pbc_index] = pbc;
+        self.triangles.push(apc);
+        self.triangles.push(abp);
+
+        [pbc_index, apc_index, abp_index]
+    }
+
+    #[inline]
+    fn locate(&self, hint: usize, p: IntPoint) -> MeshLocation {
+        walk(&self.triangles, hint, p).1
+    }
+
+    #[inline]
+    fn vertex_star(&self, start: usize, vertex_index: usize) -> Vec<usize> {
+        vertex_star(&self.triangles, start, vertex_index)
+    }
+}
+
+// walks from the hint triangle toward p and returns the index of the last visited
+// triangle, a walk that leaves the mesh follows the boundary loop it left through
+// to find where it comes back in, so concave parts and holes are crossed too.
+// A point in another part of the mesh, e.g. an island inside a hole, is not
+// reachable that way, all triangles are scanned before p is reported outside
+pub(crate) fn walk(triangles: &[IntTriangle], hint: usize, p: IntPoint) -> (usize, MeshLocation) {
+    let count = triangles.len();
+    if count == 0 {
+        return (usize::MAX, MeshLocation::Outside);
+    }
+
+    let mut index = if hint < count { hint } else { 0 };
+    // a visibility walk can cycle in a non-Delaunay mesh, a changing edge order breaks it
+    for step in 0..3 * count {
+        let triangle = &triangles[index];
+        match triangle.locate(index, p, step % 3) {
+            Step::Found(location) => return (index, location),
+            Step::Next(position) => {
+                let next = triangle.neighbors[position];
+                if next < count {
+                    index = next;
+                } else if let Some(next) = reenter(triangles, index, position, p) {
+                    index = next;
+                } else {
+                    return scan(triangles, p).unwrap_or((index, MeshLocation::Outside));
+                }
+            }
+        }
+    }
+
+    scan(triangles, p).unwrap_or((index, MeshLocation::Outside))
+}
+
+// the first triangle that holds p, checked one by one
+fn scan(triangles: &[IntTriangle], p: IntPoint) -> Option<(usize, MeshLocation)> {
+    triangles
+        .iter()
+        .enumerate()
+        .find_map(|(index, triangle)| match triangle.locate(index, p, 0) {
+            Step::Found(location) => Some((index, location)),
+            Step::Next(_) => None,
+        })
+}
+
+// the walk left the mesh through the boundary edge of `start` at `position`,
+// the segment from the middle of that edge to p is followed to the nearest
+// boundary edge of the same loop it crosses, None if p is outside that loop
+fn reenter(triangles: &[IntTriangle], start: usize, position: usize, p: IntPoint) -> Option<usize> {
+    let triangle = &triangles[start];
+    let a = triangle.vertices[(position + 1) % 3];
+    let b = triangle.vertices[(position + 2) % 3];
+    let m = [
+        a.point.x as i128 + b.point.x as i128,
+        a.point.y as i128 + b.point.y as i128,
+    ];
+    let p2 = [2 * p.x as i128, 2 * p.y as i128];
+
+    let mut nearest: Option<(f64, usize)> = None;
+
+    // the boundary edge (from, pivot) is in triangle `index`
+    let mut index = start;
+    let mut from = a.index;
+    let mut pivot = b;
+    for _ in 0..3 * triangles.len() {
+        // rotate around the pivot through the mesh to the next boundary edge
+        let mut t = &triangles[index];
+        loop {
+            let next = t.neighbors[t.position_of(from)];
+            let other = t.other_vertex(pivot.index, from);
+            if next == usize::MAX {
+                from = pivot.index;
+                pivot = t.vertices[other];
+                break;
+            }
+            from = t.vertices[other].index;
+            index = next;
+            t = &triangles[index];
+        }
+
+        if index == start && from == a.index && pivot.index == b.index {
+            break;
+        }
+
+        let u = triangles[index].vertices[triangles[index].position_of(from)].point;
+        let v = pivot.point;
+        if is_on_segment(u, v, p) {
+            return Some(index);
+        }
+
+        if let Some(t) = crossing(m, p2, u, v) {
+            if nearest.is_none_or(|(best, _)| t < best) {
+                nearest = Some((t, index));
+            }
+        }
+    }
+
+    nearest.map(|(_, index)| index)
+}
+
+#[inline]
+fn is_on_segment(a: IntPoint, b: IntPoint, p: IntPoint) -> bool {
+    Triangle::area_two_point(a, b, p) == 0
+        && a.x.min(b.x) <= p.x
+        && p.x <= a.x.max(b.x)
+        && a.y.min(b.y) <= p.y
+        && p.y <= a.y.max(b.y)
+}
+
+// the position in (0, 1] where the segment m -> p crosses the edge uv,
+// m and p are doubled, the ends of uv count on one side only
+#[inline]
+fn crossing(m: [i128; 2], p: [i128; 2], u: IntPoint, v: IntPoint) -> Option<f64> {
+    let u = [2 * u.x as i128, 2 * u.y as i128];
+    let v = [2 * v.x as i128, 2 * v.y as i128];
+
+    if (cross(m, p, u) > 0) == (cross(m, p, v) > 0) {
+        return None;
+    }
+
+    let s0 = cross(u, v, m);
+    let s1 = cross(u, v, p);
+    if s0 == 0 || s1 != 0 && (s0 > 0) == (s1 > 0) {
+        return None;
+    }
+
+    Some(s0 as f64 / (s0 - s1) as f64)
+}
+
+#[inline]
+fn cross(o: [i128; 2], a: [i128; 2], b: [i128; 2]) -> i128 {
+    (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+}
+
+// all triangles sharing the vertex, collected through neighbors
+pub(crate) fn vertex_star(
+    triangles: &[IntTriangle],
+    start: usize,
+    vertex_index: usize,
+) -> Vec<usize> {
+    let mut star = Vec::with_capacity(8);
+    let mut queue = vec![start];
+    while let Some(index) = queue.pop() {
+        if index >= triangles.len() || star.contains(&index) {
+            continue;
+        }
+        let triangle = &triangles[index];
+        if triangle.vertices.iter().all(|v| v.index != vertex_index) {
+            continue;
+        }
+        star.push(index);
+        queue.extend_from_slice(&triangle.neighbors);
+    }
+
+    star.sort_unstable();
+    star
+}
+
+pub(crate) enum Step {
+    Found(MeshLocation),
+    // position of the vertex opposite the edge to cross
+    Next(usize),
+}
+
+impl IntTriangle {
+    // `shift` picks the edge checked first, it decides between two possible exits
+    #[inline]
+    pub(crate) fn locate(&self, index: usize, p: IntPoint, shift: usize) -> Step {
+        let mut on_edge = usize::MAX;
+        let mut exit = usize::MAX;
+        for k in 0..3 {
+            let i = (k + shift) % 3;
+            let v = self.vertices[i];
+            if v.point == p {
+                return Step::Found(MeshLocation::OnVertex(v.index));
+            }
+
+            let b = self.vertices[(i + 1) % 3].point;
+            let c = self.vertices[(i + 2) % 3].point;
+
+            // inner points are on the same side as the triangle (negative area)
+            let area = Triangle::area_two_point(b, c, p);
+            if area > 0 {
+                // prefer an inner edge, a boundary edge leads out of the mesh
+                if exit == usize::MAX || self.neighbors[exit] == usize::MAX {
+                    exit = i;
+                }
+            } else if area == 0 {
+                on_edge = i;
+            }
+        }
+
+        if exit != usize::MAX {
+            Step::Next(exit)
+        } else if on_edge != usize::MAX {
+            Step::Found(MeshLocation::OnEdge(index, on_edge))
+        } else {
+            Step::Found(MeshLocation::Inside(index))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::advanced::delaunay::IntDelaunay;
+    use crate::advanced::insert::InsertResult;
+    use crate::int::triangulatable::IntTriangulatable;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::i_float::int::point::IntPoint;
+    use rand::RngExt;
+
+    fn square() -> IntDelaunay {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+        contour.triangulate().into_delaunay()
+    }
+
+    #[test]
+    fn test_0() {
+        let mut delaunay = square();
+        let area = delaunay.area();
+
+        let result = delaunay.insert_point(IntPoint::new(3, 4));
+        delaunay.validate();
+
+        let InsertResult::Inserted {
+            vertex_index,
+            triangles,
+        } = result
+        else {
+            panic!("point is not inserted");
+        };
+
+        assert_eq!(vertex_index, 4);
+        assert_eq!(delaunay.points[vertex_index], IntPoint::new(3, 4));
+        assert_eq!(delaunay.triangles.len(), 4);
+        assert_eq!(triangles.len(), 4);
+        assert_eq!(delaunay.area(), area);
+    }
+
+    #[test]
+    fn test_1() {
+        let mut delaunay = square();
+        let area = delaunay.area();
+
+        // inner diagonal
+        let result = delaunay.insert_point(IntPoint::new(5, 5));
+        delaunay.validate();
+
+        let InsertResult::Inserted { triangles, .. } = result else {
+            panic!("point is not inserted");
+        };
+        assert_eq!(triangles.len(), 4);
+        assert_eq!(delaunay.triangles.len(), 4);
+        assert_eq!(delaunay.area(), area);
+
+        // boundary edge
+        let result = delaunay.insert_point(IntPoint::new(5, 0));
+        delaunay.validate();
+
+        let InsertResult::Inserted { triangles, .. } = result else {
+            panic!("point is not inserted");
+        };
+        assert_eq!(triangles.len(), 2);
+        assert_eq!(delaunay.triangles.len(), 5);
+        assert_eq!(delaunay.area(), area);
+    }
+
+    #[test]
+    fn test_2() {
+        let mut delaunay = square();
+        let count = delaunay.triangles.len();
+
+        assert_eq!(
+            delaunay.insert_point(IntPoint::new(10, 10)),
+            InsertResult::OnExistingVertex {
+                vertex_index: delaunay.index_of(IntPoint::new(10, 10))
+            }
+        );
+        assert_eq!(
+            delaunay.insert_point(IntPoint::new(20, 5)),
+            InsertResult::Outside
+        );
+        assert_eq!(
+            delaunay.insert_point(IntPoint::new(5, -1)),
+            InsertResult::Outside
+        );
+
+        assert_eq!(delaunay.points.len(), 4);
+        assert_eq!(delaunay.triangles.len(), count);
+    }
+
+    #[test]
+    fn test_3() {
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(20, 0),
+                IntPoint::new(20, 20),
+                IntPoint::new(0, 20),
+            ],
+            vec![
+                IntPoint::new(5, 5),
+                IntPoint::new(5, 15),
+                IntPoint::new(15, 15),
+                IntPoint::new(15, 5),
+            ],
+        ];
+        let segments = [[IntPoint::new(2, 10), IntPoint::new(2, 18)]];
+        let mut delaunay = shape
+            .triangulate_with_constraints(&segments)
+            .0
+            .into_delaunay();
+        let area = delaunay.area();
+
+        // inside the hole
+        assert_eq!(
+            delaunay.insert_point(IntPoint::new(10, 10)),
+            InsertResult::Outside
+        );
+
+        // on the constraint
+        let result = delaunay.insert_point(IntPoint::new(2, 14));
+        delaunay.validate();
+        assert!(matches!(result, InsertResult::Inserted { .. }));
+
+        let a = delaunay.index_of(IntPoint::new(2, 10));
+        let b = delaunay.index_of(IntPoint::new(2, 18));
+        let m = delaunay.index_of(IntPoint::new(2, 14));
+
+        assert!(!delaunay.constraints.contains(a, b));
+        assert!(delaunay.constraints.contains(a, m));
+        assert!(delaunay.constraints.contains(m, b));
+        assert!(delaunay.has_edge(a, m));
+        assert!(delaunay.has_edge(m, b));
+        assert_eq!(delaunay.area(), area);
+    }
+
+    #[test]
+    fn test_4() {
+        // two separate squares, the walk from one can not reach the other
+        let shapes = vec![vec![rect(0, 0, 10, 10)], vec![rect(20, 0, 30, 10)]];
+        let mut delaunay = shapes.triangulate().into_delaunay();
+        let area = delaunay.area();
+
+        for p in [IntPoint::new(5, 5), IntPoint::new(25, 5)] {
+            let result = delaunay.insert_point(p);
+            delaunay.validate();
+            assert!(matches!(result, InsertResult::Inserted { .. }));
+        }
+        assert_eq!(
+            delaunay.insert_point(IntPoint::new(15, 5)),
+            InsertResult::Outside
+        );
+        assert_eq!(delaunay.points.len(), 10);
+        assert_eq!(delaunay.area(), area);
+    }
+
+    #[test]
+    fn test_5() {
+        // an island inside the hole of a ring
+        let mut hole = rect(10, 10, 20, 20);
+        hole.reverse();
+        let shapes = vec![vec![rect(0, 0, 30, 30), hole], vec![rect(13, 13, 17, 17)]];
+        let mut delaunay = shapes.triangulate().into_delaunay();
+        let area = delaunay.area();
+
+        for p in [
+            IntPoint::new(15, 15),
+            IntPoint::new(5, 5),
+            IntPoint::new(14, 15),
+        ] {
+            let result = delaunay.insert_point(p);
+            delaunay.validate();
+            assert!(matches!(result, InsertResult::Inserted { .. }));
+        }
+        assert_eq!(
+            delaunay.insert_point(IntPoint::new(11, 15)),
+            InsertResult::Outside
+        );
+        assert_eq!(delaunay.area(), area);
+    }
+
+    #[test]
+    fn test_random_0() {
+        let mut rng = rand::rng();
+        for _ in 0..200 {
+            let mut delaunay = square_of(1000);
+            let area = delaunay.area();
+            for _ in 0..100 {
+                let p = IntPoint::new(rng.random_range(-100..=1100), rng.random_range(-100..=1100));
+                let inside = (0..=1000).contains(&p.x) && (0..=1000).contains(&p.y);
+                let count = delaunay.points.len();
+                match delaunay.insert_point(p) {
+                    InsertResult::Inserted {
+                        vertex_index,
+                        triangles,
+                    } => {
+                        assert!(inside);
+                        assert_eq!(vertex_index, count);
+                        for &t in triangles.iter() {
+                            let triangle = &delaunay.triangles[t];
+                            assert!(triangle.vertices.iter().any(|v| v.index == vertex_index));
+                        }
+                    }
+                    InsertResult::OnExistingVertex { vertex_index } => {
+                        assert_eq!(delaunay.points[vertex_index], p);
+                    }
+                    InsertResult::Outside => assert!(!inside),
+                }
+            }
+            delaunay.validate();
+            assert_eq!(delaunay.area(), area);
+            assert!(delaunay.is_delaunay());
+        }
+    }
+
+    #[test]
+    fn test_random_1() {
+        // incremental insertion gives the same mesh area as the batch one
+        let mut rng = rand::rng();
+        for _ in 0..200 {
+            let mut points = Vec::with_capacity(50);
+            for _ in 0..50 {
+                points.push(IntPoint::new(
+                    rng.random_range(1..100),
+                    rng.random_range(1..100),
+                ));
+            }
+            let mut delaunay = square_of(100);
+            for &p in points.iter() {
+                delaunay.insert_point(p);
+            }
+            delaunay.validate();
+            assert!(delaunay.is_delaunay());
+
+            let mut unique = points.clone();
+            unique.sort_unstable();
+            unique.dedup();
+            assert_eq!(delaunay.points.len(), unique.len() + 4);
+        }
+    }
+
+    fn square_of(size: i32) -> IntDelaunay {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(size, 0),
+            IntPoint::new(size, size),
+            IntPoint::new(0, size),
+        ];
+        contour.triangulate().into_delaunay()
+    }
+
+    fn rect(x0: i32, y0: i32, x1: i32, y1: i32) -> Vec<IntPoint> {
+        vec![
+            IntPoint::new(x0, y0),
+            IntPoint::new(x1, y0),
+            IntPoint::new(x1, y1),
+            IntPoint::new(x0, y1),
+        ]
+    }
+}
@@ -1,6 +1,11 @@
 pub mod bitset;
 pub mod buffer;
 pub mod centroid;
+pub mod constraint;
 pub mod convex;
 pub mod delaunay;
+pub mod edge;
+pub mod insert;
+pub mod point_set;
+pub mod remove;
 pub mod triangulation;
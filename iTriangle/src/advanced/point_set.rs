@@ -0,0 +1,243 @@
+use crate::advanced::delaunay::IntDelaunay;
+use crate::int::monotone::triangulator::MonotoneTriangulator;
+use crate::int::triangulation::RawIntTriangulation;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_float::triangle::Triangle;
+use i_overlay::i_shape::int::shape::IntContour;
+
+impl IntDelaunay {
+    /// Builds a Delaunay triangulation of a bare point set.
+    ///
+    /// The mesh covers the convex hull of the points. Every input point becomes a
+    /// vertex; duplicates are merged. Points lying on the hull edges are kept as hull vertices.
+    ///
+    /// # Parameters
+    /// - `points`: points to triangulate, in any order
+    ///
+    /// # Returns
+    /// An [`IntDelaunay`] mesh. It is empty if there are fewer than 3 distinct points
+    /// or all points are collinear.
+    pub fn from_points(points: &[IntPoint]) -> Self {
+        let mut sorted = points.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        let hull = sorted.convex_hull();
+        if hull.len() < 3 {
+            return RawIntTriangulation::default().into_delaunay();
+        }
+
+        let mut inner = Vec::with_capacity(sorted.len() - hull.len());
+        let mut hull_sorted = hull.clone();
+        hull_sorted.sort_unstable();
+        for p in sorted.into_iter() {
+            if hull_sorted.binary_search(&p).is_err() {
+                inner.push(p);
+            }
+        }
+
+        let mut raw = RawIntTriangulation::default();
+        MonotoneTriangulator::default().contour_into_net_triangulation(
+            &hull,
+            Some(&inner),
+            &mut raw,
+        );
+
+        raw.into_delaunay()
+    }
+}
+
+trait ConvexHull {
+    fn convex_hull(&self) -> IntContour;
+}
+
+impl ConvexHull for [IntPoint] {
+    // Andrew's monotone chain over points sorted by x then y.
+    // Collinear points on the hull edges are kept.
+    // The hull is counter-clockwise, empty if all points are collinear.
+    fn convex_hull(&self) -> IntContour {
+        if self.len() < 3 {
+            return IntContour::new();
+        }
+
+        let first = self[0];
+        let last = self[self.len() - 1];
+        if self
+            .iter()
+            .all(|&p| Triangle::area_two_point(first, last, p) == 0)
+        {
+            return IntContour::new();
+        }
+
+        let mut hull = IntContour::with_capacity(16);
+
+        // bottom chain
+        for &p in self.iter() {
+            while hull.len() >= 2 && is_right_turn(hull[hull.len() - 2], hull[hull.len() - 1], p) {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+
+        // top chain
+        let bottom_len = hull.len() + 1;
+        for &p in self.iter().rev().skip(1) {
+            while hull.len() >= bottom_len
+                && is_right_turn(hull[hull.len() - 2], hull[hull.len() - 1], p)
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+
+        // the first point is repeated at the end
+        hull.pop();
+
+        hull
+    }
+}
+
+#[inline]
+fn is_right_turn(a: IntPoint, b: IntPoint, c: IntPoint) -> bool {
+    // area_two_point is negative for counter-clockwise triangles
+    Triangle::area_two_point(a, b, c) > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::advanced::delaunay::IntDelaunay;
+    use crate::advanced::point_set::ConvexHull;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::area::Area;
+    use rand::RngExt;
+
+    #[test]
+    fn test_0() {
+        let mut points = Vec::new();
+        for x in 0..3 {
+            for y in 0..3 {
+                points.push(IntPoint::new(10 * x, 10 * y));
+            }
+        }
+
+        let delaunay = IntDelaunay::from_points(&points);
+        delaunay.validate();
+
+        assert_eq!(delaunay.points.len(), 9);
+        assert_eq!(delaunay.triangles.len(), 8);
+        assert_eq!(delaunay.area(), -800);
+    }
+
+    #[test]
+    fn test_1() {
+        let points = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(5, 5),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 0),
+        ];
+
+        let delaunay = IntDelaunay::from_points(&points);
+        assert!(delaunay.triangles.is_empty());
+
+        let delaunay = IntDelaunay::from_points(&points[..2]);
+        assert!(delaunay.triangles.is_empty());
+    }
+
+    #[test]
+    fn test_2() {
+        let points = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(0, 10),
+            IntPoint::new(10, 0),
+            IntPoint::new(3, 3),
+        ];
+
+        let delaunay = IntDelaunay::from_points(&points);
+        delaunay.validate();
+
+        assert_eq!(delaunay.points.len(), 4);
+        assert_eq!(delaunay.triangles.len(), 3);
+        assert_eq!(delaunay.area(), -100);
+    }
+
+    #[test]
+    fn test_3() {
+        let mut points = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(0, 5),
+            IntPoint::new(0, 10),
+            IntPoint::new(5, 10),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 5),
+            IntPoint::new(10, 0),
+            IntPoint::new(5, 0),
+            IntPoint::new(5, 5),
+        ];
+        points.sort_unstable();
+
+        let hull = points.convex_hull();
+        assert_eq!(hull.len(), 8);
+        assert!(hull.area_two() < 0);
+
+        let delaunay = IntDelaunay::from_points(&points);
+        delaunay.validate();
+        assert_eq!(delaunay.area(), hull.area_two());
+        assert_eq!(delaunay.triangles.len(), 8);
+
+        let net = delaunay.centroid_net(0);
+        assert_eq!(net.len(), 9);
+
+        let polygons = delaunay.to_convex_polygons();
+        assert!(!polygons.is_empty());
+    }
+
+    #[test]
+    fn test_random_0() {
+        for _ in 0..10_000 {
+            let points = random(20, 12);
+            let delaunay = IntDelaunay::from_points(&points);
+            delaunay.validate();
+
+            let mut sorted = points.clone();
+            sorted.sort_unstable();
+            sorted.dedup();
+            let hull = sorted.convex_hull();
+            if hull.is_empty() {
+                assert!(delaunay.triangles.is_empty());
+                continue;
+            }
+
+            assert_eq!(delaunay.area(), hull.area_two());
+            assert_eq!(delaunay.points.len(), sorted.len());
+        }
+    }
+
+    #[test]
+    fn test_random_1() {
+        for _ in 0..20 {
+            let points = random(1000, 500);
+            let delaunay = IntDelaunay::from_points(&points);
+            delaunay.validate();
+            let delaunay = delaunay.refine_with_circumcenters(100);
+            delaunay.validate();
+        }
+    }
+
+    fn random(radius: i32, n: usize) -> Vec<IntPoint> {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
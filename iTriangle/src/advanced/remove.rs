@@ -0,0 +1,410 @@
+use crate::advanced::bitset::IndexBitSet;
+use crate::advanced::delaunay::IntDelaunay;
+use crate::geom::point::IndexPoint;
+use crate::geom::triangle::IntTriangle;
+use crate::int::earcut::earcut_wide::{AdaptiveEarcut, EARCUT_MAX_POINTS};
+use crate::int::monotone::triangulator::MonotoneTriangulator;
+use crate::int::triangulation::RawIntTriangulation;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+
+/// The reason [`IntDelaunay::remove_vertex`] refused to remove a vertex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoveVertexError {
+    /// The index is out of range or no triangle uses the vertex.
+    NotFound,
+    /// The vertex lies on the mesh outline, removing it would change the outline.
+    BoundaryVertex,
+    /// The vertex is an endpoint of a constrained edge.
+    ConstrainedVertex,
+    /// The outline around the vertex can not be re-triangulated with all of its
+    /// points, for example when some of them are collinear.
+    DegenerateRing,
+}
+
+// a triangle around the removed vertex
+struct StarItem {
+    // triangle index
+    index: usize,
+    // the edge opposite the removed vertex, in counter-clockwise order
+    a: IndexPoint,
+    b: IndexPoint,
+    // the triangle across the edge ab
+    outer: usize,
+}
+
+impl IntDelaunay {
+    /// Removes an inner vertex and re-triangulates the hole around it.
+    ///
+    /// The triangles around the vertex are replaced by a triangulation of their
+    /// outline, after which the Delaunay condition is restored locally.
+    /// The point stays in `points` so other vertex indices do not change,
+    /// but no triangle references it anymore. Triangle indices may change.
+    ///
+    /// # Parameters
+    /// - `index`: index of the vertex in `points`
+    ///
+    /// # Errors
+    /// Returns a [`RemoveVertexError`] and leaves the mesh unchanged if the vertex
+    /// is unknown, lies on the outline, is an endpoint of a constrained edge,
+    /// or the outline around it can not be re-triangulated.
+    pub fn remove_vertex(&mut self, index: usize) -> Result<(), RemoveVertexError> {
+        let star = self.vertex_ring(index)?;
+
+        if !self.constraints.is_empty()
+            && star
+                .iter()
+                .any(|item| self.constraints.contains(index, item.a.index))
+        {
+            return Err(RemoveVertexError::ConstrainedVertex);
+        }
+
+        let contour: Vec<IntPoint> = star.iter().map(|item| item.a.point).collect();
+        let mut raw = RawIntTriangulation::default();
+        if contour.len() <= EARCUT_MAX_POINTS {
+            contour.adaptive_earcut_net_into(&mut raw);
+        } else {
+            MonotoneTriangulator::default()
+                .contour_into_net_triangulation(&contour, None, &mut raw);
+        }
+
+        // maps the hole triangulation back to the star,
+        // every ring point must be used or the old triangles would stay behind
+        if raw.triangles.len() + 2 != star.len() {
+            return Err(RemoveVertexError::DegenerateRing);
+        }
+        let mut ring_index = Vec::with_capacity(raw.points.len());
+        for p in raw.points.iter() {
+            let r = contour
+                .iter()
+                .position(|c| c == p)
+                .ok_or(RemoveVertexError::DegenerateRing)?;
+            ring_index.push(r);
+        }
+
+        let mut buffer = Vec::with_capacity(raw.triangles.len());
+        for (i, local) in raw.triangles.iter().enumerate() {
+            let slot = star[i].index;
+            let mut triangle = IntTriangle {
+                vertices: [IndexPoint::empty(); 3],
+                neighbors: [usize::MAX; 3],
+            };
+            for j in 0..3 {
+                let r = ring_index[local.vertices[j].index];
+                triangle.vertices[j] = star[r].a;
+
+                let neighbor = local.neighbors[j];
+                if neighbor < raw.triangles.len() {
+                    triangle.neighbors[j] = star[neighbor].index;
+                }
+            }
+
+            for j in 0..3 {
+                if local.neighbors[j] < raw.triangles.len() {
+                    continue;
+                }
+                // an outline edge, it goes along the ring from r0 to r1
+                let r0 = ring_index[local.vertices[(j + 1) % 3].index];
+                let item = &star[r0];
+                triangle.neighbors[j] = item.outer;
+                if item.outer < self.triangles.len() {
+                    let outer = &mut self.triangles[item.outer];
+                    let position = outer.other_vertex(item.a.index, item.b.index);
+                    outer.neighbors[position] = slot;
+                }
+            }
+
+            self.triangles[slot] = triangle;
+            buffer.push(slot);
+        }
+
+        let n = star.len();
+        self.remove_triangles(star[n - 1].index.max(star[n - 2].index), &mut buffer);
+        self.remove_triangles(star[n - 1].index.min(star[n - 2].index), &mut buffer);
+
+        let mut bitset = IndexBitSet::with_size(self.triangles.len());
+        self.fix_triangles(&mut buffer, &mut bitset);
+
+        Ok(())
+    }
+
+    // collects the triangles around the vertex in counter-clockwise order
+    fn vertex_ring(&self, index: usize) -> Result<Vec<StarItem>, RemoveVertexError> {
+        if index >= self.points.len() {
+            return Err(RemoveVertexError::NotFound);
+        }
+
+        let start = self
+            .triangles
+            .iter()
+            .position(|t| t.vertices.iter().any(|v| v.index == index))
+            .ok_or(RemoveVertexError::NotFound)?;
+
+        let mut star = Vec::with_capacity(8);
+        let mut t = start;
+        loop {
+            let triangle = &self.triangles[t];
+            let v = triangle.position_of(index);
+            let ia = (v + 1) % 3;
+            let ib = (v + 2) % 3;
+
+            star.push(StarItem {
+                index: t,
+                a: triangle.vertices[ia],
+                b: triangle.vertices[ib],
+                outer: triangle.neighbors[v],
+            });
+
+            // the next triangle shares the edge from the vertex to b
+            let next = triangle.neighbors[ia];
+            if next >= self.triangles.len() {
+                return Err(RemoveVertexError::BoundaryVertex);
+            }
+            if next == start {
+                break;
+            }
+            if star.len() > self.triangles.len() {
+                return Err(RemoveVertexError::NotFound);
+            }
+            t = next;
+        }
+
+        Ok(star)
+    }
+
+    // moves the last triangle into the free slot
+    fn remove_triangles(&mut self, slot: usize, buffer: &mut [usize]) {
+        let last = self.triangles.len() - 1;
+        if slot != last {
+            self.triangles.swap(slot, last);
+            let neighbors = self.triangles[slot].neighbors;
+            for n in neighbors {
+                if n < last {
+                    let neighbor = &mut self.triangles[n];
+                    if let Some(position) = neighbor.try_opposite(last) {
+                        neighbor.neighbors[position] = slot;
+                    }
+                }
+            }
+            for i in buffer.iter_mut() {
+                if *i == last {
+                    *i = slot;
+                }
+            }
+        }
+        self.triangles.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::advanced::delaunay::IntDelaunay;
+    use crate::advanced::insert::InsertResult;
+    use crate::advanced::remove::RemoveVertexError;
+    use crate::int::triangulatable::IntTriangulatable;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_float::triangle::Triangle;
+    use rand::RngExt;
+
+    fn square(size: i32) -> IntDelaunay {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(size, 0),
+            IntPoint::new(size, size),
+            IntPoint::new(0, size),
+        ];
+        contour.triangulate().into_delaunay()
+    }
+
+    fn assert_valid(delaunay: &IntDelaunay, area: i64) {
+        delaunay.validate();
+        for t in delaunay.triangles.iter() {
+            let [a, b, c] = t.vertices.map(|v| v.point);
+            assert!(Triangle::area_two_point(a, b, c) < 0);
+        }
+        assert_eq!(delaunay.area(), area);
+    }
+
+    #[test]
+    fn test_0() {
+        let mut delaunay = square(10);
+        let area = delaunay.area();
+        let count = delaunay.triangles.len();
+
+        let InsertResult::Inserted { vertex_index, .. } =
+            delaunay.insert_point(IntPoint::new(3, 4))
+        else {
+            panic!("point is not inserted");
+        };
+
+        assert_eq!(delaunay.remove_vertex(vertex_index), Ok(()));
+        assert_valid(&delaunay, area);
+        assert_eq!(delaunay.triangles.len(), count);
+        assert!(delaunay
+            .triangles
+            .iter()
+            .all(|t| t.vertices.iter().all(|v| v.index != vertex_index)));
+
+        assert_eq!(
+            delaunay.remove_vertex(vertex_index),
+            Err(RemoveVertexError::NotFound)
+        );
+        assert_eq!(
+            delaunay.remove_vertex(100),
+            Err(RemoveVertexError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_1() {
+        let mut delaunay = square(10);
+        let count = delaunay.triangles.len();
+        assert_eq!(
+            delaunay.remove_vertex(0),
+            Err(RemoveVertexError::BoundaryVertex)
+        );
+        assert_eq!(delaunay.triangles.len(), count);
+    }
+
+    #[test]
+    fn test_2() {
+        // a grid gives collinear points around the center
+        let mut points = Vec::new();
+        for x in 0..5 {
+            for y in 0..5 {
+                points.push(IntPoint::new(10 * x, 10 * y));
+            }
+        }
+        let mut delaunay = IntDelaunay::from_points(&points);
+        let area = delaunay.area();
+
+        for p in [
+            IntPoint::new(20, 20),
+            IntPoint::new(10, 10),
+            IntPoint::new(30, 20),
+        ] {
+            let index = delaunay.index_of(p);
+            assert_eq!(delaunay.remove_vertex(index), Ok(()));
+            assert_valid(&delaunay, area);
+        }
+        assert_eq!(delaunay.triangles.len(), 32 - 6);
+    }
+
+    #[test]
+    fn test_3() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(20, 0),
+            IntPoint::new(20, 20),
+            IntPoint::new(0, 20),
+        ];
+        let segments = [[IntPoint::new(5, 5), IntPoint::new(15, 15)]];
+        let mut delaunay = contour
+            .triangulate_with_constraints(&segments)
+            .0
+            .into_delaunay();
+
+        let a = delaunay.index_of(IntPoint::new(5, 5));
+        assert_eq!(
+            delaunay.remove_vertex(a),
+            Err(RemoveVertexError::ConstrainedVertex)
+        );
+    }
+
+    #[test]
+    fn test_random_0() {
+        let mut rng = rand::rng();
+        for _ in 0..500 {
+            let mut delaunay = square(1000);
+            let area = delaunay.area();
+
+            let mut inserted = Vec::new();
+            for _ in 0..30 {
+                let p = IntPoint::new(rng.random_range(0..=1000), rng.random_range(0..=1000));
+                if let InsertResult::Inserted { vertex_index, .. } = delaunay.insert_point(p) {
+                    inserted.push(vertex_index);
+                }
+            }
+
+            for &index in inserted.iter() {
+                let p = delaunay.points[index];
+                let on_boundary = p.x == 0 || p.y == 0 || p.x == 1000 || p.y == 1000;
+                match delaunay.remove_vertex(index) {
+                    Ok(()) => assert!(!on_boundary),
+                    Err(e) => {
+                        assert!(on_boundary);
+                        assert_eq!(e, RemoveVertexError::BoundaryVertex);
+                    }
+                }
+                assert_valid(&delaunay, area);
+                assert!(delaunay.is_delaunay());
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_1() {
+        // a vertex with a big star
+        let mut rng = rand::rng();
+        for _ in 0..20 {
+            let mut points = vec![IntPoint::new(0, 0)];
+            let n = rng.random_range(60..100);
+            for i in 0..n {
+                let angle = core::f64::consts::TAU * i as f64 / n as f64;
+                let r = rng.random_range(900.0..1000.0);
+                points.push(IntPoint::new(
+                    (r * angle.cos()) as i32,
+                    (r * angle.sin()) as i32,
+                ));
+            }
+            let mut delaunay = IntDelaunay::from_points(&points);
+            let area = delaunay.area();
+            let center = delaunay.index_of(IntPoint::new(0, 0));
+            assert_eq!(delaunay.remove_vertex(center), Ok(()));
+            assert_valid(&delaunay, area);
+            assert!(delaunay.is_delaunay());
+        }
+    }
+
+    #[test]
+    fn test_random_2() {
+        // points on a coarse grid give many collinear rings
+        let mut rng = rand::rng();
+        for _ in 0..500 {
+            let points: Vec<_> = (0..40)
+                .map(|_| IntPoint::new(rng.random_range(0..8) * 10, rng.random_range(0..8) * 10))
+                .collect();
+            let mut delaunay = IntDelaunay::from_points(&points);
+            let area = delaunay.area();
+
+            for index in 0..delaunay.points.len() {
+                let triangles: Vec<_> = delaunay
+                    .triangles
+                    .iter()
+                    .map(|t| (t.vertices.map(|v| v.index), t.neighbors))
+                    .collect();
+                match delaunay.remove_vertex(index) {
+                    Ok(()) => {}
+                    Err(RemoveVertexError::DegenerateRing) => {
+                        // the mesh is left as it was
+                        let after: Vec<_> = delaunay
+                            .triangles
+                            .iter()
+                            .map(|t| (t.vertices.map(|v| v.index), t.neighbors))
+                            .collect();
+                        assert_eq!(after, triangles);
+                    }
+                    Err(e) => {
+                        assert!(matches!(
+                            e,
+                            RemoveVertexError::BoundaryVertex | RemoveVertexError::NotFound
+                        ));
+                    }
+                }
+                assert_valid(&delaunay, area);
+            }
+        }
+    }
+}
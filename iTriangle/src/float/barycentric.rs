@@ -0,0 +1,112 @@
+use crate::float::prepared::PreparedTriangulation;
+use crate::float::triangulation::Triangulation;
+use crate::int::attribute::Lerp;
+use crate::int::barycentric::{mix, IntBarycentric};
+use crate::int::triangulation::{IndexType, IntTriangulation};
+use crate::location::TriangleIndex;
+use alloc::vec::Vec;
+use i_overlay::i_float::adapter::FloatPointAdapter;
+use i_overlay::i_float::float::compatible::FloatPointCompatible;
+use i_overlay::i_float::float::number::FloatNumber;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_shape::float::adapter::PathToInt;
+
+/// Barycentric coordinates of a point in a mesh triangle, the weights sum to `1`.
+///
+/// The weights come from the exact [`IntBarycentric`] of the integer mesh, so a point
+/// on an edge or a vertex gets exact zero weights.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Barycentric<T: FloatNumber> {
+    pub triangle: TriangleIndex,
+    pub weights: [T; 3],
+}
+
+impl<T: FloatNumber> Barycentric<T> {
+    #[inline]
+    fn with_int(barycentric: IntBarycentric) -> Self {
+        Self {
+            triangle: barycentric.triangle,
+            weights: barycentric.to_f64().map(T::from_float),
+        }
+    }
+
+    /// Mixes the values at the triangle vertices, see [`IntBarycentric::interpolate`].
+    #[inline]
+    pub fn interpolate<A: Lerp>(&self, values: [&A; 3]) -> A {
+        mix(values, self.weights.map(|w| w.to_f64()))
+    }
+}
+
+impl<P: FloatPointCompatible, I: IndexType> Triangulation<P, I> {
+    /// Locates the points like [`locate_points`](Self::locate_points) and returns
+    /// the barycentric coordinates in the holding triangle, `None` for points
+    /// outside the mesh.
+    ///
+    /// The mesh is quantized on its own like in [`prepare`](Self::prepare), so the
+    /// result for a point does not depend on the other points.
+    pub fn locate_barycentric(&self, points: &[P]) -> Vec<Option<Barycentric<P::Scalar>>> {
+        let (triangulation, inside, int_points) = self.to_int_with(points);
+        let result = triangulation
+            .locate_barycentric(&int_points)
+            .into_iter()
+            .map(|barycentric| barycentric.map(Barycentric::with_int))
+            .collect();
+        scatter(points.len(), &inside, result)
+    }
+
+    /// Interpolates a per-vertex field at the points, `None` for points outside the mesh.
+    ///
+    /// `values[i]` is the value at `self.points[i]`, see [`IntTriangulation::interpolate`].
+    ///
+    /// # Panics
+    /// Panics if `values` and `points` of the triangulation have different lengths.
+    pub fn interpolate<A: Lerp>(&self, values: &[A], points: &[P]) -> Vec<Option<A>> {
+        let (triangulation, inside, int_points) = self.to_int_with(points);
+        let result = triangulation.interpolate(values, &int_points);
+        scatter(points.len(), &inside, result)
+    }
+
+    // the mesh in integer space and the points inside its bounds with their indices,
+    // a point beyond the bounds can not be in the mesh
+    fn to_int_with(&self, points: &[P]) -> (IntTriangulation<I>, Vec<usize>, Vec<IntPoint>) {
+        let adapter = FloatPointAdapter::with_iter(self.points.iter());
+        let triangulation = IntTriangulation {
+            points: self.points.to_int(&adapter),
+            indices: self.indices.clone(),
+        };
+
+        let mut inside = Vec::with_capacity(points.len());
+        let mut int_points = Vec::with_capacity(points.len());
+        for (i, p) in points.iter().enumerate() {
+            if adapter.rect.contains(p) {
+                inside.push(i);
+                int_points.push(adapter.float_to_int(p));
+            }
+        }
+
+        (triangulation, inside, int_points)
+    }
+}
+
+// puts the results of the points inside the bounds back to their indices
+fn scatter<T>(count: usize, inside: &[usize], result: Vec<Option<T>>) -> Vec<Option<T>> {
+    let mut all: Vec<Option<T>> = (0..count).map(|_| None).collect();
+    for (&i, value) in inside.iter().zip(result) {
+        all[i] = value;
+    }
+    all
+}
+
+impl<P: FloatPointCompatible> PreparedTriangulation<P> {
+    /// Locates the point like [`locate_point`](Self::locate_point) and returns
+    /// the barycentric coordinates in the holding triangle, `None` outside the mesh.
+    #[inline]
+    pub fn locate_barycentric(&self, p: &P) -> Option<Barycentric<P::Scalar>> {
+        if !self.adapter.rect.contains(p) {
+            return None;
+        }
+        self.prepared
+            .locate_barycentric(self.adapter.float_to_int(p))
+            .map(Barycentric::with_int)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::float::triangulation::Triangulation;
+use crate::int::error::{check_points_count, TriangulationError};
 use crate::int::triangulation::IndexType;
 use alloc::vec::Vec;
 
@@ -29,6 +30,17 @@ impl<P, I: IndexType> TriangulationBuilder<P, I> {
         self
     }
 
+    /// Appends another `Triangulation` like [`append`](Self::append), but returns
+    /// [`TriangulationError::IndexOverflow`] instead of panicking when `I` cannot
+    /// address all points. The builder is left unchanged on error.
+    pub fn try_append(
+        &mut self,
+        triangulation: Triangulation<P, I>,
+    ) -> Result<&mut Self, TriangulationError> {
+        check_points_count::<I>(self.points.len() + triangulation.points.len())?;
+        Ok(self.append(triangulation))
+    }
+
     /// Builds and returns the final `Triangulation`.
     pub fn build(self) -> Triangulation<P, I> {
         Triangulation {
@@ -1,4 +1,10 @@
+use crate::advanced::delaunay::IntDelaunay;
 use crate::float::delaunay::Delaunay;
+use crate::int::attribute::Lerp;
+use crate::int::error::TriangulationError;
+use crate::tessellation::circumcenter::{EdgeSelector, RefineOptions, RefineReport};
+use alloc::vec::Vec;
+use i_overlay::i_float::adapter::FloatPointAdapter;
 use i_overlay::i_float::float::compatible::FloatPointCompatible;
 
 impl<P: FloatPointCompatible> Delaunay<P> {
@@ -26,4 +32,182 @@ impl<P: FloatPointCompatible> Delaunay<P> {
         self.delaunay
             .refine_with_circumcenters_by_obtuse_angle_mut(int_area);
     }
+
+    /// Consuming variant of [`refine_with_circumcenters_and_attributes_mut`](Self::refine_with_circumcenters_and_attributes_mut).
+    #[inline]
+    pub fn refine_with_circumcenters_and_attributes<A: Lerp>(
+        mut self,
+        min_area: P::Scalar,
+        attributes: &mut Vec<A>,
+    ) -> Result<Self, TriangulationError> {
+        self.refine_with_circumcenters_and_attributes_mut(min_area, attributes)?;
+        Ok(self)
+    }
+
+    /// Refines the mesh and extends `attributes` for every inserted point,
+    /// see [`IntDelaunay::refine_with_circumcenters_and_attributes_mut`](crate::advanced::delaunay::IntDelaunay::refine_with_circumcenters_and_attributes_mut).
+    ///
+    /// # Errors
+    /// Returns [`TriangulationError::AttributeCountMismatch`] if `attributes` and
+    /// [`points`](Self::points) have different lengths.
+    #[inline]
+    pub fn refine_with_circumcenters_and_attributes_mut<A: Lerp>(
+        &mut self,
+        min_area: P::Scalar,
+        attributes: &mut Vec<A>,
+    ) -> Result<(), TriangulationError> {
+        let int_area = self.adapter.sqr_float_to_int(min_area);
+        self.delaunay
+            .refine_with_circumcenters_and_attributes_mut(int_area, attributes)
+    }
+
+    /// Consuming variant of [`refine_with_sizing_mut`](Self::refine_with_sizing_mut).
+    #[inline]
+    pub fn refine_with_sizing<F: Fn(P) -> P::Scalar>(
+        mut self,
+        sizing: F,
+        options: &RefineOptions,
+    ) -> (Self, RefineReport) {
+        let report = self.refine_with_sizing_mut(sizing, options);
+        (self, report)
+    }
+
+    /// Refines the mesh until every edge is shorter than the max edge length
+    /// returned by `sizing` at that place,
+    /// see [`IntDelaunay::refine_with_sizing_mut`](crate::advanced::delaunay::IntDelaunay::refine_with_sizing_mut).
+    #[inline]
+    pub fn refine_with_sizing_mut<F: Fn(P) -> P::Scalar>(
+        &mut self,
+        sizing: F,
+        options: &RefineOptions,
+    ) -> RefineReport {
+        let adapter = &self.adapter;
+        self.delaunay.refine_with_sizing_mut(
+            |p| {
+                let length = sizing(adapter.int_to_float(&p));
+                adapter.len_float_to_int(length).max(0) as u32
+            },
+            options,
+        )
+    }
+
+    /// Refines the mesh like [`refine_with_circumcenters_mut`](Self::refine_with_circumcenters_mut)
+    /// with custom stopping criteria,
+    /// see [`IntDelaunay::refine_with_options_mut`](crate::advanced::delaunay::IntDelaunay::refine_with_options_mut).
+    #[inline]
+    pub fn refine_with_options_mut(
+        &mut self,
+        min_area: P::Scalar,
+        options: &RefineOptions,
+    ) -> RefineReport {
+        let int_area = self.adapter.sqr_float_to_int(min_area);
+        self.delaunay.refine_with_options_mut(int_area, options)
+    }
+
+    /// Consuming variant of [`refine_to_max_area_mut`](Self::refine_to_max_area_mut).
+    #[inline]
+    pub fn refine_to_max_area(
+        mut self,
+        max_area: P::Scalar,
+        max_points: Option<usize>,
+    ) -> (Self, RefineReport) {
+        let report = self.refine_to_max_area_mut(max_area, max_points);
+        (self, report)
+    }
+
+    /// Refines the mesh until every triangle has an area of at most `max_area`,
+    /// see [`IntDelaunay::refine_to_max_area_mut`](crate::advanced::delaunay::IntDelaunay::refine_to_max_area_mut).
+    #[inline]
+    pub fn refine_to_max_area_mut(
+        &mut self,
+        max_area: P::Scalar,
+        max_points: Option<usize>,
+    ) -> RefineReport {
+        let int_area = self.adapter.sqr_float_to_int(max_area);
+        self.delaunay.refine_to_max_area_mut(int_area, max_points)
+    }
+
+    /// Consuming variant of [`refine_graded_mut`](Self::refine_graded_mut).
+    #[inline]
+    pub fn refine_graded(
+        mut self,
+        min_size: P::Scalar,
+        growth: f64,
+        options: &RefineOptions,
+    ) -> (Self, RefineReport) {
+        let report = self.refine_graded_mut(min_size, growth, options);
+        (self, report)
+    }
+
+    /// Refines the mesh with small triangles near the boundary and larger ones inside,
+    /// see [`IntDelaunay::refine_graded_mut`](crate::advanced::delaunay::IntDelaunay::refine_graded_mut).
+    #[inline]
+    pub fn refine_graded_mut(
+        &mut self,
+        min_size: P::Scalar,
+        growth: f64,
+        options: &RefineOptions,
+    ) -> RefineReport {
+        let int_size = self.adapter.len_float_to_int(min_size).max(0) as u32;
+        self.delaunay.refine_graded_mut(int_size, growth, options)
+    }
+
+    /// Consuming variant of [`refine_with_selector_mut`](Self::refine_with_selector_mut).
+    #[inline]
+    pub fn refine_with_selector<S: FloatEdgeSelector<P>>(mut self, selector: &S) -> Self {
+        self.refine_with_selector_mut(selector);
+        self
+    }
+
+    /// Refines the mesh with circumcenters, the edges to split are picked by `selector`,
+    /// see [`IntDelaunay::refine_with_selector_mut`](crate::advanced::delaunay::IntDelaunay::refine_with_selector_mut).
+    #[inline]
+    pub fn refine_with_selector_mut<S: FloatEdgeSelector<P>>(&mut self, selector: &S) {
+        let selector = FloatSelector {
+            adapter: &self.adapter,
+            selector,
+        };
+        self.delaunay.refine_with_selector_mut(&selector);
+    }
+
+    /// Refines the mesh like [`refine_with_selector_mut`](Self::refine_with_selector_mut)
+    /// with custom stopping criteria.
+    #[inline]
+    pub fn refine_with_selector_and_options_mut<S: FloatEdgeSelector<P>>(
+        &mut self,
+        selector: &S,
+        options: &RefineOptions,
+    ) -> RefineReport {
+        let selector = FloatSelector {
+            adapter: &self.adapter,
+            selector,
+        };
+        self.delaunay
+            .refine_with_selector_and_options_mut(&selector, options)
+    }
+}
+
+/// Float counterpart of [`EdgeSelector`], sees the triangle in float coordinates.
+pub trait FloatEdgeSelector<P: FloatPointCompatible> {
+    /// Returns the position of the vertex opposite the edge to split, `None` keeps the triangle.
+    ///
+    /// `is_boundary[i]` is `true` if the edge opposite `points[i]` is on the mesh boundary.
+    fn select(&self, points: [P; 3], is_boundary: [bool; 3]) -> Option<usize>;
+}
+
+struct FloatSelector<'a, P: FloatPointCompatible, S> {
+    adapter: &'a FloatPointAdapter<P>,
+    selector: &'a S,
+}
+
+impl<P: FloatPointCompatible, S: FloatEdgeSelector<P>> EdgeSelector for FloatSelector<'_, P, S> {
+    #[inline]
+    fn select(&self, delaunay: &IntDelaunay, index: usize) -> Option<usize> {
+        let triangle = &delaunay.triangles[index];
+        let points = triangle
+            .vertices
+            .map(|v| self.adapter.int_to_float(&v.point));
+        let is_boundary = [0, 1, 2].map(|i| triangle.is_boundary(i));
+        self.selector.select(points, is_boundary)
+    }
 }
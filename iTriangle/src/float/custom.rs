@@ -1,7 +1,9 @@
+use crate::float::triangulatable::{segments_to_float, ConstraintSegments, SteinerPoints};
 use crate::float::triangulation::RawTriangulation;
 use crate::int::custom::IntCustomTriangulatable;
 use crate::int::triangulation::RawIntTriangulation;
 use crate::int::validation::Validation;
+use alloc::vec::Vec;
 use i_overlay::i_float::adapter::FloatPointAdapter;
 use i_overlay::i_float::float::compatible::FloatPointCompatible;
 use i_overlay::i_float::float::rect::FloatRect;
@@ -22,6 +24,13 @@ pub trait CustomTriangulatable<P: FloatPointCompatible> {
         points: &[P],
         validation: Validation,
     ) -> RawTriangulation<P>;
+
+    /// Performs triangulation with constraint segments and a custom [`Validation`] config.
+    fn custom_triangulate_with_constraints(
+        &self,
+        segments: &[[P; 2]],
+        validation: Validation,
+    ) -> (RawTriangulation<P>, Vec<[P; 2]>);
 }
 
 impl<P: FloatPointCompatible> CustomTriangulatable<P> for Contour<P> {
@@ -43,7 +52,8 @@ impl<P: FloatPointCompatible> CustomTriangulatable<P> for Contour<P> {
         points: &[P],
         validation: Validation,
     ) -> RawTriangulation<P> {
-        if let Some(rect) = FloatRect::with_path(self) {
+        if let Some(mut rect) = FloatRect::with_path(self) {
+            points.add_to_rect(&mut rect);
             let adapter = FloatPointAdapter::<P>::new(rect);
             let float_points = points.to_int(&adapter);
             let raw = self
@@ -57,6 +67,29 @@ impl<P: FloatPointCompatible> CustomTriangulatable<P> for Contour<P> {
             }
         }
     }
+
+    fn custom_triangulate_with_constraints(
+        &self,
+        segments: &[[P; 2]],
+        validation: Validation,
+    ) -> (RawTriangulation<P>, Vec<[P; 2]>) {
+        if let Some(mut rect) = FloatRect::with_path(self) {
+            segments.add_to_rect(&mut rect);
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let int_segments = segments.segments_to_int(&adapter);
+            let (raw, missed) = self
+                .to_int(&adapter)
+                .custom_triangulate_with_constraints(&int_segments, validation);
+            let missed = segments_to_float(&missed, &adapter);
+            (RawTriangulation { raw, adapter }, missed)
+        } else {
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            (raw, Vec::new())
+        }
+    }
 }
 
 impl<P: FloatPointCompatible> CustomTriangulatable<P> for [Contour<P>] {
@@ -78,7 +111,8 @@ impl<P: FloatPointCompatible> CustomTriangulatable<P> for [Contour<P>] {
         points: &[P],
         validation: Validation,
     ) -> RawTriangulation<P> {
-        if let Some(rect) = FloatRect::with_paths(self) {
+        if let Some(mut rect) = FloatRect::with_paths(self) {
+            points.add_to_rect(&mut rect);
             let adapter = FloatPointAdapter::<P>::new(rect);
             let float_points = points.to_int(&adapter);
             let raw = self
@@ -92,6 +126,29 @@ impl<P: FloatPointCompatible> CustomTriangulatable<P> for [Contour<P>] {
             }
         }
     }
+
+    fn custom_triangulate_with_constraints(
+        &self,
+        segments: &[[P; 2]],
+        validation: Validation,
+    ) -> (RawTriangulation<P>, Vec<[P; 2]>) {
+        if let Some(mut rect) = FloatRect::with_paths(self) {
+            segments.add_to_rect(&mut rect);
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let int_segments = segments.segments_to_int(&adapter);
+            let (raw, missed) = self
+                .to_int(&adapter)
+                .custom_triangulate_with_constraints(&int_segments, validation);
+            let missed = segments_to_float(&missed, &adapter);
+            (RawTriangulation { raw, adapter }, missed)
+        } else {
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            (raw, Vec::new())
+        }
+    }
 }
 
 impl<P: FloatPointCompatible> CustomTriangulatable<P> for [Shape<P>] {
@@ -113,7 +170,8 @@ impl<P: FloatPointCompatible> CustomTriangulatable<P> for [Shape<P>] {
         points: &[P],
         validation: Validation,
     ) -> RawTriangulation<P> {
-        if let Some(rect) = FloatRect::with_list_of_paths(self) {
+        if let Some(mut rect) = FloatRect::with_list_of_paths(self) {
+            points.add_to_rect(&mut rect);
             let adapter = FloatPointAdapter::<P>::new(rect);
             let float_points = points.to_int(&adapter);
             let raw = self
@@ -127,4 +185,27 @@ impl<P: FloatPointCompatible> CustomTriangulatable<P> for [Shape<P>] {
             }
         }
     }
+
+    fn custom_triangulate_with_constraints(
+        &self,
+        segments: &[[P; 2]],
+        validation: Validation,
+    ) -> (RawTriangulation<P>, Vec<[P; 2]>) {
+        if let Some(mut rect) = FloatRect::with_list_of_paths(self) {
+            segments.add_to_rect(&mut rect);
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let int_segments = segments.segments_to_int(&adapter);
+            let (raw, missed) = self
+                .to_int(&adapter)
+                .custom_triangulate_with_constraints(&int_segments, validation);
+            let missed = segments_to_float(&missed, &adapter);
+            (RawTriangulation { raw, adapter }, missed)
+        } else {
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            (raw, Vec::new())
+        }
+    }
 }
@@ -1,15 +1,18 @@
 use crate::advanced::buffer::DelaunayBuffer;
+use crate::advanced::constraint::ConstraintEdges;
 use crate::advanced::delaunay::IntDelaunay;
+use crate::advanced::edge::EdgeKind;
 use crate::float::triangulation::{RawTriangulation, Triangulation};
 use crate::int::triangulation::IndexType;
 use alloc::vec::Vec;
 use i_overlay::i_float::adapter::FloatPointAdapter;
 use i_overlay::i_float::float::compatible::FloatPointCompatible;
-use i_overlay::i_shape::float::adapter::PathToFloat;
+use i_overlay::i_float::float::rect::FloatRect;
+use i_overlay::i_shape::float::adapter::{PathToFloat, PathToInt};
 
 /// A Delaunay-refined triangle mesh with float-mapped geometry.
 ///
-/// Produced from [`Triangulation::into_delaunay`] by applying edge flips
+/// Produced from [`RawTriangulation::into_delaunay`] by applying edge flips
 /// to satisfy the Delaunay condition.
 pub struct Delaunay<P: FloatPointCompatible> {
     pub(super) delaunay: IntDelaunay,
@@ -33,6 +36,22 @@ impl<P: FloatPointCompatible> RawTriangulation<P> {
 }
 
 impl<P: FloatPointCompatible> Delaunay<P> {
+    /// Builds a Delaunay triangulation of a bare point set.
+    ///
+    /// The mesh covers the convex hull of the points. It is empty if there are
+    /// fewer than 3 distinct points or all points are collinear.
+    /// See [`IntDelaunay::from_points`].
+    #[inline]
+    pub fn from_points(points: &[P]) -> Self {
+        let rect = FloatRect::with_points(points).unwrap_or(FloatRect::zero());
+        let adapter = FloatPointAdapter::<P>::new(rect);
+        let int_points = points.to_int(&adapter);
+        Self {
+            delaunay: IntDelaunay::from_points(&int_points),
+            adapter,
+        }
+    }
+
     /// Returns the float-mapped vertex positions in the triangulation.
     #[inline]
     pub fn points(&self) -> Vec<P> {
@@ -51,6 +70,18 @@ impl<P: FloatPointCompatible> Delaunay<P> {
         self.delaunay.triangle_neighbors()
     }
 
+    /// Returns the [`EdgeKind`] of every triangle edge, see [`IntDelaunay::edge_kinds`].
+    #[inline]
+    pub fn edge_kinds(&self) -> Vec<[EdgeKind; 3]> {
+        self.delaunay.edge_kinds()
+    }
+
+    /// Returns the constrained edges as pairs of indices into [`points`](Self::points).
+    #[inline]
+    pub fn constraints(&self) -> &ConstraintEdges {
+        &self.delaunay.constraints
+    }
+
     /// Converts this refined mesh into a flat float [`Triangulation`].
     #[inline]
     pub fn to_triangulation<I: IndexType>(&self) -> Triangulation<P, I> {
@@ -3,6 +3,8 @@ use i_overlay::i_float::float::compatible::FloatPointCompatible;
 use i_overlay::i_float::float::number::FloatNumber;
 use i_overlay::{i_float::adapter::FloatPointAdapter, i_shape::float::adapter::PathToInt};
 
+use crate::float::delaunay::Delaunay;
+use crate::float::triangulation::RawTriangulation;
 use crate::int::locator::IntPointInTriangulationLocator;
 use crate::{
     float::triangulation::Triangulation, int::triangulation::IndexType,
@@ -47,10 +49,39 @@ impl<P, I: IndexType> PointInTriangulationLocator<P> for Triangulation<P, I> {
     }
 }
 
+impl<P: FloatPointCompatible> RawTriangulation<P> {
+    /// Locates a single point by walking the triangle neighbors, starting at the
+    /// triangle `hint`, see [`RawIntTriangulation::locate_point_from`](crate::int::triangulation::RawIntTriangulation::locate_point_from).
+    ///
+    /// Unlike [`Triangulation::locate_points`] the mesh is not converted on every call.
+    #[inline]
+    pub fn locate_point_from(&self, hint: &mut usize, p: &P) -> PointLocationInTriangulation {
+        if !self.adapter.rect.contains(p) {
+            return PointLocationInTriangulation::Outside;
+        }
+        self.raw
+            .locate_point_from(hint, self.adapter.float_to_int(p))
+    }
+}
+
+impl<P: FloatPointCompatible> Delaunay<P> {
+    /// Locates a single point by walking the triangle neighbors, starting at the
+    /// triangle `hint`, see [`RawIntTriangulation::locate_point_from`](crate::int::triangulation::RawIntTriangulation::locate_point_from).
+    #[inline]
+    pub fn locate_point_from(&self, hint: &mut usize, p: &P) -> PointLocationInTriangulation {
+        if !self.adapter.rect.contains(p) {
+            return PointLocationInTriangulation::Outside;
+        }
+        self.delaunay
+            .locate_point_from(hint, self.adapter.float_to_int(p))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::vec;
 
+    use crate::float::triangulatable::Triangulatable;
     use crate::{
         float::triangulation::Triangulation,
         location::{PointLocationInTriangulation, TriangleIndex},
@@ -105,4 +136,59 @@ mod tests {
             PointLocationInTriangulation::Outside
         ));
     }
+
+    #[test]
+    fn test_locate_point_from() {
+        let contour = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+        let raw = contour.triangulate();
+        let delaunay = contour.triangulate().into_delaunay();
+        let triangulation = raw.to_triangulation::<u16>();
+
+        let points = [
+            [3.0, 1.0],
+            [1.0, 3.0],
+            [2.0, 2.0],
+            [2.0, 0.0],
+            [0.0, 0.0],
+            [5.0, 1.0],
+        ];
+        let expected = triangulation.locate_points::<f64>(&points);
+
+        let mut hint = 0;
+        for (p, location) in points.iter().zip(expected.iter()) {
+            assert_eq!(&raw.locate_point_from(&mut hint, p), location);
+        }
+
+        let mut hint = 0;
+        for (p, location) in points.iter().zip(expected.iter()) {
+            assert_eq!(&delaunay.locate_point_from(&mut hint, p), location);
+        }
+    }
+
+    #[test]
+    fn test_locate_point_from_parts() {
+        // two separate squares, the walk from one can not reach the other
+        let shapes = [
+            vec![vec![[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]]],
+            vec![vec![[10.0, 0.0], [14.0, 0.0], [14.0, 4.0], [10.0, 4.0]]],
+        ];
+        let raw = shapes.triangulate();
+        let delaunay = shapes.triangulate().into_delaunay();
+        let triangulation = raw.to_triangulation::<u16>();
+
+        let points = [[1.0, 3.0], [13.0, 1.0], [7.0, 2.0], [2.0, 1.0]];
+        let expected = triangulation.locate_points::<f64>(&points);
+        assert!(expected[1].triangle().is_some());
+
+        let mut hint = 0;
+        for (p, location) in points.iter().zip(expected.iter()) {
+            assert_eq!(&raw.locate_point_from(&mut hint, p), location);
+        }
+
+        let mut hint = 0;
+        for (p, location) in points.iter().zip(expected.iter()) {
+            let result = delaunay.locate_point_from(&mut hint, p);
+            assert_eq!(result.triangle().is_some(), location.triangle().is_some());
+        }
+    }
 }
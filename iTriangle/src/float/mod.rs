@@ -1,3 +1,4 @@
+pub mod barycentric;
 pub mod builder;
 pub mod centroid_net;
 pub mod circumcenter;
@@ -5,6 +6,9 @@ pub mod convex;
 pub mod custom;
 pub mod delaunay;
 pub mod locator;
+pub mod prepared;
+pub mod quality;
+pub mod region;
 pub mod triangulatable;
 pub mod triangulation;
 pub mod triangulator;
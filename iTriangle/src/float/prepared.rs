@@ -0,0 +1,235 @@
+use crate::float::delaunay::Delaunay;
+use crate::float::triangulation::{RawTriangulation, Triangulation};
+use crate::int::prepared::IntPreparedTriangulation;
+use crate::int::triangulation::{IndexType, IntTriangulation};
+use crate::location::{PointLocationInTriangulation, TriangleIndex};
+use alloc::vec::Vec;
+use i_overlay::i_float::adapter::FloatPointAdapter;
+use i_overlay::i_float::float::compatible::FloatPointCompatible;
+use i_overlay::i_float::float::number::FloatNumber;
+use i_overlay::i_float::float::rect::FloatRect;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_float::int::rect::IntRect;
+use i_overlay::i_shape::float::adapter::PathToInt;
+
+/// The point of a mesh that is the closest to a query point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestPoint<P: FloatPointCompatible> {
+    /// The triangle that holds `point`.
+    pub triangle: TriangleIndex,
+    /// The closest point, the query point itself if it is inside the mesh.
+    pub point: P,
+    /// Squared distance between the query point and `point`.
+    pub sqr_distance: P::Scalar,
+}
+
+/// A float triangulation with a grid index, built once for many queries.
+///
+/// The mesh is converted to integer coordinates a single time and the
+/// [`FloatPointAdapter`] is kept, see [`IntPreparedTriangulation`].
+///
+/// Triangle indices are the same as in the source [`Triangulation`].
+pub struct PreparedTriangulation<P: FloatPointCompatible> {
+    pub(super) prepared: IntPreparedTriangulation,
+    pub(super) adapter: FloatPointAdapter<P>,
+}
+
+impl<P: FloatPointCompatible, I: IndexType> Triangulation<P, I> {
+    /// Builds a [`PreparedTriangulation`] for repeated queries against this mesh.
+    pub fn prepare(&self) -> PreparedTriangulation<P> {
+        let adapter = FloatPointAdapter::with_iter(self.points.iter());
+        let triangulation = IntTriangulation {
+            points: self.points.to_int(&adapter),
+            indices: self.indices.clone(),
+        };
+
+        PreparedTriangulation {
+            prepared: triangulation.prepare(),
+            adapter,
+        }
+    }
+}
+
+impl<P: FloatPointCompatible> RawTriangulation<P> {
+    /// Builds a [`PreparedTriangulation`] for repeated queries against this mesh.
+    #[inline]
+    pub fn prepare(&self) -> PreparedTriangulation<P> {
+        PreparedTriangulation {
+            prepared: self.raw.prepare(),
+            adapter: self.adapter.clone(),
+        }
+    }
+}
+
+impl<P: FloatPointCompatible> Delaunay<P> {
+    /// Builds a [`PreparedTriangulation`] for repeated queries against this mesh.
+    #[inline]
+    pub fn prepare(&self) -> PreparedTriangulation<P> {
+        PreparedTriangulation {
+            prepared: self.delaunay.prepare(),
+            adapter: self.adapter.clone(),
+        }
+    }
+}
+
+impl<P: FloatPointCompatible> PreparedTriangulation<P> {
+    /// Returns the number of triangles.
+    #[inline]
+    pub fn triangles_count(&self) -> usize {
+        self.prepared.triangles_count()
+    }
+
+    /// Locates a point, the result is the same as in [`Triangulation::locate_points`].
+    #[inline]
+    pub fn locate_point(&self, p: &P) -> PointLocationInTriangulation {
+        if !self.adapter.rect.contains(p) {
+            return PointLocationInTriangulation::Outside;
+        }
+        self.prepared.locate_point(self.adapter.float_to_int(p))
+    }
+
+    /// Returns the sorted indices of the triangles that intersect or touch `rect`.
+    ///
+    /// The test runs on the integer mesh, so a triangle that touches the rect
+    /// within the rounding error may be reported or missed.
+    pub fn triangles_in_rect(&self, rect: &FloatRect<P::Scalar>) -> Vec<TriangleIndex> {
+        match self.int_rect(rect) {
+            Some(int_rect) => self.prepared.triangles_in_rect(&int_rect),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the sorted indices of the triangles that are not farther than
+    /// `radius` from `center`.
+    pub fn triangles_in_radius(&self, center: &P, radius: P::Scalar) -> Vec<TriangleIndex> {
+        let rect = FloatRect::new(
+            center.x() - radius,
+            center.x() + radius,
+            center.y() - radius,
+            center.y() + radius,
+        );
+        let Some(mut int_rect) = self.int_rect(&rect) else {
+            return Vec::new();
+        };
+
+        // a unit margin against the rounding, the exact test runs in floats
+        int_rect.min_x = int_rect.min_x.saturating_sub(1);
+        int_rect.max_x = int_rect.max_x.saturating_add(1);
+        int_rect.min_y = int_rect.min_y.saturating_sub(1);
+        int_rect.max_y = int_rect.max_y.saturating_add(1);
+
+        let sqr_radius = radius * radius;
+        self.prepared
+            .triangles_in_rect(&int_rect)
+            .into_iter()
+            .filter(|t| {
+                let triangle = self.prepared.triangles[t.index()].map(|p| self.to_float(p));
+                sqr_distance_to_triangle(center, &triangle) <= sqr_radius
+            })
+            .collect()
+    }
+
+    /// Returns the point of the mesh that is the closest to `p`, `None` for an empty mesh
+    /// or a non-finite `p`, see [`IntPreparedTriangulation::closest_point`].
+    ///
+    /// `p` may be far outside the mesh, the nearest boundary edge is found on the
+    /// integer mesh and the projection on it is made in floats.
+    pub fn closest_point(&self, p: &P) -> Option<ClosestPoint<P>> {
+        if !p.x().to_f64().is_finite() || !p.y().to_f64().is_finite() {
+            return None;
+        }
+
+        if self.adapter.rect.contains(p) {
+            if let Some(triangle) = self.locate_point(p).triangle() {
+                return Some(ClosestPoint {
+                    triangle,
+                    point: *p,
+                    sqr_distance: P::Scalar::from_float(0.0),
+                });
+            }
+        }
+
+        // the integer space goes on beyond the adapter rect, a far point
+        // saturates to the i64 range
+        let x = ((p.x() - self.adapter.offset.x()) * self.adapter.dir_scale).to_f64() as i64;
+        let y = ((p.y() - self.adapter.offset.y()) * self.adapter.dir_scale).to_f64() as i64;
+        let edge = &self.prepared.edges[self.prepared.nearest_edge(x, y)?];
+
+        let a = self.to_float(edge.a);
+        let b = self.to_float(edge.b);
+        let point = closest_on_segment(p, &a, &b);
+        let (dx, dy) = (point.x() - p.x(), point.y() - p.y());
+
+        Some(ClosestPoint {
+            triangle: TriangleIndex::new(edge.triangle),
+            point,
+            sqr_distance: dx * dx + dy * dy,
+        })
+    }
+
+    // the part of the rect inside the mesh bounds in integer coordinates
+    fn int_rect(&self, rect: &FloatRect<P::Scalar>) -> Option<IntRect> {
+        let bounds = &self.adapter.rect;
+        let min_x = rect.min_x.max(bounds.min_x);
+        let max_x = rect.max_x.min(bounds.max_x);
+        let min_y = rect.min_y.max(bounds.min_y);
+        let max_y = rect.max_y.min(bounds.max_y);
+        if min_x > max_x || min_y > max_y {
+            return None;
+        }
+
+        let min = self.adapter.float_to_int(&P::from_xy(min_x, min_y));
+        let max = self.adapter.float_to_int(&P::from_xy(max_x, max_y));
+        Some(IntRect::with_min_max(min, max))
+    }
+
+    #[inline]
+    fn to_float(&self, p: IntPoint) -> P {
+        self.adapter.int_to_float(&p)
+    }
+}
+
+fn sqr_distance_to_triangle<P: FloatPointCompatible>(p: &P, triangle: &[P; 3]) -> P::Scalar {
+    let zero = P::Scalar::from_float(0.0);
+    let [a, b, c] = triangle;
+
+    let q0 = cross(a, b, p);
+    let q1 = cross(b, c, p);
+    let q2 = cross(c, a, p);
+    let has_neg = q0 < zero || q1 < zero || q2 < zero;
+    let has_pos = q0 > zero || q1 > zero || q2 > zero;
+    if !(has_neg && has_pos) {
+        return zero;
+    }
+
+    sqr_distance_to_segment(p, a, b)
+        .min(sqr_distance_to_segment(p, b, c))
+        .min(sqr_distance_to_segment(p, c, a))
+}
+
+#[inline]
+fn cross<P: FloatPointCompatible>(a: &P, b: &P, p: &P) -> P::Scalar {
+    (b.x() - a.x()) * (p.y() - a.y()) - (b.y() - a.y()) * (p.x() - a.x())
+}
+
+fn sqr_distance_to_segment<P: FloatPointCompatible>(p: &P, a: &P, b: &P) -> P::Scalar {
+    let q = closest_on_segment(p, a, b);
+    let (dx, dy) = (q.x() - p.x(), q.y() - p.y());
+    dx * dx + dy * dy
+}
+
+fn closest_on_segment<P: FloatPointCompatible>(p: &P, a: &P, b: &P) -> P {
+    let zero = P::Scalar::from_float(0.0);
+    let one = P::Scalar::from_float(1.0);
+
+    let (abx, aby) = (b.x() - a.x(), b.y() - a.y());
+    let (apx, apy) = (p.x() - a.x(), p.y() - a.y());
+    let sqr_ab = abx * abx + aby * aby;
+    let t = if sqr_ab > zero {
+        ((abx * apx + aby * apy) / sqr_ab).max(zero).min(one)
+    } else {
+        zero
+    };
+
+    P::from_xy(a.x() + t * abx, a.y() + t * aby)
+}
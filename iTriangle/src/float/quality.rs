@@ -0,0 +1,33 @@
+use crate::float::delaunay::Delaunay;
+use crate::tessellation::circumcenter::RefineReport;
+use i_overlay::i_float::float::compatible::FloatPointCompatible;
+
+impl<P: FloatPointCompatible> Delaunay<P> {
+    /// Consuming variant of [`refine_to_min_angle_mut`](Self::refine_to_min_angle_mut).
+    #[inline]
+    pub fn refine_to_min_angle(
+        mut self,
+        degrees: f64,
+        max_area: P::Scalar,
+        max_points: Option<usize>,
+    ) -> (Self, RefineReport) {
+        let report = self.refine_to_min_angle_mut(degrees, max_area, max_points);
+        (self, report)
+    }
+
+    /// Refines the mesh until every triangle has all angles of at least `degrees`
+    /// and an area of at most `max_area`, pass infinity for no area limit.
+    ///
+    /// See [`IntDelaunay::refine_to_min_angle_mut`](crate::advanced::delaunay::IntDelaunay::refine_to_min_angle_mut).
+    #[inline]
+    pub fn refine_to_min_angle_mut(
+        &mut self,
+        degrees: f64,
+        max_area: P::Scalar,
+        max_points: Option<usize>,
+    ) -> RefineReport {
+        let int_area = self.adapter.sqr_float_to_int(max_area);
+        self.delaunay
+            .refine_to_min_angle_mut(degrees, int_area, max_points)
+    }
+}
@@ -0,0 +1,37 @@
+use crate::float::delaunay::Delaunay;
+use i_overlay::core::fill_rule::FillRule;
+use i_overlay::core::overlay_rule::OverlayRule;
+use i_overlay::float::single::SingleFloatOverlay;
+use i_overlay::i_float::float::compatible::FloatPointCompatible;
+use i_overlay::i_shape::float::adapter::ShapesToInt;
+use i_overlay::i_shape::source::resource::ShapeResource;
+
+impl<P: FloatPointCompatible> Delaunay<P> {
+    /// Consuming variant of [`refine_within_mut`](Self::refine_within_mut).
+    #[inline]
+    pub fn refine_within<R: ShapeResource<P>>(mut self, region: &R, min_area: P::Scalar) -> Self {
+        self.refine_within_mut(region, min_area);
+        self
+    }
+
+    /// Refines only the triangles whose centroid lies inside `region`,
+    /// see [`IntDelaunay::refine_within_mut`](crate::advanced::delaunay::IntDelaunay::refine_within_mut).
+    ///
+    /// `region` is a contour, contours or shapes, it may extend beyond the mesh.
+    #[inline]
+    pub fn refine_within_mut<R: ShapeResource<P>>(&mut self, region: &R, min_area: P::Scalar) {
+        // clipped to the mesh bounds, so the region fits into the int space of the mesh
+        let rect = &self.adapter.rect;
+        let bounds = [
+            P::from_xy(rect.min_x, rect.min_y),
+            P::from_xy(rect.max_x, rect.min_y),
+            P::from_xy(rect.max_x, rect.max_y),
+            P::from_xy(rect.min_x, rect.max_y),
+        ];
+        let region = region
+            .overlay(&bounds, OverlayRule::Intersect, FillRule::NonZero)
+            .to_int(&self.adapter);
+        let int_area = self.adapter.sqr_float_to_int(min_area);
+        self.delaunay.refine_within_mut(&region, int_area);
+    }
+}
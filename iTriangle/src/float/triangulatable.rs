@@ -1,9 +1,17 @@
 use crate::float::triangulation::RawTriangulation;
+use crate::int::attribute::Lerp;
+use crate::int::error::{
+    check_attributes_count, check_float_paths, check_float_steiner_points, TriangulationError,
+};
+use crate::int::steiner::SteinerOutcome;
 use crate::int::triangulatable::IntTriangulatable;
 use crate::int::triangulation::RawIntTriangulation;
+use alloc::vec;
+use alloc::vec::Vec;
 use i_overlay::i_float::adapter::FloatPointAdapter;
 use i_overlay::i_float::float::compatible::FloatPointCompatible;
 use i_overlay::i_float::float::rect::FloatRect;
+use i_overlay::i_float::int::point::IntPoint;
 use i_overlay::i_shape::base::data::{Contour, Shape};
 use i_overlay::i_shape::float::adapter::{PathToInt, ShapeToInt, ShapesToInt};
 use i_overlay::i_shape::float::rect::RectInit;
@@ -18,15 +26,104 @@ use i_overlay::i_shape::float::rect::RectInit;
 /// - `[Contour<P>]`
 /// - `[Shape<P>]`
 pub trait Triangulatable<P: FloatPointCompatible> {
-    /// Triangulates the shape(s) using the default [`Triangulator`] configuration.
+    /// Triangulates the shape(s) using the default [`Triangulator`](crate::float::triangulator::Triangulator) configuration.
     ///
     /// Validation includes contour simplification, direction correction, and area filtering.
     fn triangulate(&self) -> RawTriangulation<P>;
 
     /// Triangulates the shape(s) and inserts the given Steiner points.
     ///
-    /// Points must lie strictly within the interior of the geometry.
+    /// Points inside the geometry become vertices, points lying on a boundary edge
+    /// split the edge. Points outside the geometry are skipped.
     fn triangulate_with_steiner_points(&self, points: &[P]) -> RawTriangulation<P>;
+
+    /// Works like [`triangulate_with_steiner_points`](Self::triangulate_with_steiner_points)
+    /// but reports bad input instead of panicking.
+    ///
+    /// # Errors
+    /// Returns [`TriangulationError::EmptyInput`] or [`TriangulationError::CoordinateOutOfRange`]
+    /// for bad shapes, and [`TriangulationError::InvalidSteinerPoint`] for the first
+    /// non-finite Steiner point.
+    fn try_triangulate_with_steiner_points(
+        &self,
+        points: &[P],
+    ) -> Result<RawTriangulation<P>, TriangulationError>;
+
+    /// Triangulates the shape(s), inserts the given Steiner points and reports
+    /// what happened to each of them, in the order of `points`.
+    fn triangulate_with_steiner_outcomes(
+        &self,
+        points: &[P],
+    ) -> (RawTriangulation<P>, Vec<SteinerOutcome>);
+
+    /// Triangulates the shape(s) so that every constraint segment becomes a mesh edge.
+    ///
+    /// Segments are clipped by the geometry and split at intersections. Also returns
+    /// the segment parts inside the geometry that could not be forced into the mesh.
+    fn triangulate_with_constraints(
+        &self,
+        segments: &[[P; 2]],
+    ) -> (RawTriangulation<P>, Vec<[P; 2]>);
+
+    /// Triangulates the shape(s) and returns an attribute for every output vertex.
+    ///
+    /// `attributes` hold one value per input point, contour by contour in input order.
+    /// Points created while resolving self-intersections get a value interpolated along
+    /// the input edge they lie on. See [`IntTriangulatable::triangulate_with_attributes`].
+    ///
+    /// # Errors
+    /// Returns [`TriangulationError::AttributeCountMismatch`] if the number of attributes
+    /// differs from the number of input points.
+    fn triangulate_with_attributes<A: Lerp>(
+        &self,
+        attributes: &[A],
+    ) -> Result<(RawTriangulation<P>, Vec<A>), TriangulationError>;
+}
+
+pub(crate) trait SteinerPoints<P: FloatPointCompatible> {
+    fn add_to_rect(&self, rect: &mut FloatRect<P::Scalar>);
+}
+
+impl<P: FloatPointCompatible> SteinerPoints<P> for [P] {
+    #[inline]
+    fn add_to_rect(&self, rect: &mut FloatRect<P::Scalar>) {
+        for p in self.iter() {
+            rect.add_point(p);
+        }
+    }
+}
+
+pub(crate) trait ConstraintSegments<P: FloatPointCompatible> {
+    fn add_to_rect(&self, rect: &mut FloatRect<P::Scalar>);
+    fn segments_to_int(&self, adapter: &FloatPointAdapter<P>) -> Vec<[IntPoint; 2]>;
+}
+
+#[inline]
+pub(crate) fn segments_to_float<P: FloatPointCompatible>(
+    segments: &[[IntPoint; 2]],
+    adapter: &FloatPointAdapter<P>,
+) -> Vec<[P; 2]> {
+    segments
+        .iter()
+        .map(|s| [adapter.int_to_float(&s[0]), adapter.int_to_float(&s[1])])
+        .collect()
+}
+
+impl<P: FloatPointCompatible> ConstraintSegments<P> for [[P; 2]] {
+    #[inline]
+    fn add_to_rect(&self, rect: &mut FloatRect<P::Scalar>) {
+        for s in self.iter() {
+            rect.add_point(&s[0]);
+            rect.add_point(&s[1]);
+        }
+    }
+
+    #[inline]
+    fn segments_to_int(&self, adapter: &FloatPointAdapter<P>) -> Vec<[IntPoint; 2]> {
+        self.iter()
+            .map(|s| [adapter.float_to_int(&s[0]), adapter.float_to_int(&s[1])])
+            .collect()
+    }
 }
 
 impl<P: FloatPointCompatible> Triangulatable<P> for [P] {
@@ -44,7 +141,8 @@ impl<P: FloatPointCompatible> Triangulatable<P> for [P] {
     }
 
     fn triangulate_with_steiner_points(&self, points: &[P]) -> RawTriangulation<P> {
-        if let Some(rect) = FloatRect::with_path(self) {
+        if let Some(mut rect) = FloatRect::with_path(self) {
+            points.add_to_rect(&mut rect);
             let adapter = FloatPointAdapter::<P>::new(rect);
             let float_points = points.to_int(&adapter);
             let raw = self
@@ -58,6 +156,78 @@ impl<P: FloatPointCompatible> Triangulatable<P> for [P] {
             }
         }
     }
+
+    fn try_triangulate_with_steiner_points(
+        &self,
+        points: &[P],
+    ) -> Result<RawTriangulation<P>, TriangulationError> {
+        check_float_paths(core::iter::once(self))?;
+        check_float_steiner_points(points)?;
+        Ok(self.triangulate_with_steiner_points(points))
+    }
+
+    fn triangulate_with_steiner_outcomes(
+        &self,
+        points: &[P],
+    ) -> (RawTriangulation<P>, Vec<SteinerOutcome>) {
+        if let Some(mut rect) = FloatRect::with_path(self) {
+            points.add_to_rect(&mut rect);
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let int_points = points.to_int(&adapter);
+            let (raw, outcomes) = self
+                .to_int(&adapter)
+                .triangulate_with_steiner_outcomes(&int_points);
+            (RawTriangulation { raw, adapter }, outcomes)
+        } else {
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            (raw, vec![SteinerOutcome::Outside; points.len()])
+        }
+    }
+
+    fn triangulate_with_constraints(
+        &self,
+        segments: &[[P; 2]],
+    ) -> (RawTriangulation<P>, Vec<[P; 2]>) {
+        if let Some(mut rect) = FloatRect::with_path(self) {
+            segments.add_to_rect(&mut rect);
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let int_segments = segments.segments_to_int(&adapter);
+            let (raw, missed) = self
+                .to_int(&adapter)
+                .triangulate_with_constraints(&int_segments);
+            let missed = segments_to_float(&missed, &adapter);
+            (RawTriangulation { raw, adapter }, missed)
+        } else {
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            (raw, Vec::new())
+        }
+    }
+
+    fn triangulate_with_attributes<A: Lerp>(
+        &self,
+        attributes: &[A],
+    ) -> Result<(RawTriangulation<P>, Vec<A>), TriangulationError> {
+        if let Some(rect) = FloatRect::with_path(self) {
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let (raw, attributes) = self
+                .to_int(&adapter)
+                .triangulate_with_attributes(attributes)?;
+            Ok((RawTriangulation { raw, adapter }, attributes))
+        } else {
+            check_attributes_count(0, attributes.len())?;
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            Ok((raw, Vec::new()))
+        }
+    }
 }
 
 impl<P: FloatPointCompatible> Triangulatable<P> for [Contour<P>] {
@@ -75,7 +245,8 @@ impl<P: FloatPointCompatible> Triangulatable<P> for [Contour<P>] {
     }
 
     fn triangulate_with_steiner_points(&self, points: &[P]) -> RawTriangulation<P> {
-        if let Some(rect) = FloatRect::with_paths(self) {
+        if let Some(mut rect) = FloatRect::with_paths(self) {
+            points.add_to_rect(&mut rect);
             let adapter = FloatPointAdapter::<P>::new(rect);
             let float_points = points.to_int(&adapter);
             let raw = self
@@ -89,6 +260,78 @@ impl<P: FloatPointCompatible> Triangulatable<P> for [Contour<P>] {
             }
         }
     }
+
+    fn try_triangulate_with_steiner_points(
+        &self,
+        points: &[P],
+    ) -> Result<RawTriangulation<P>, TriangulationError> {
+        check_float_paths(self.iter().map(|c| c.as_slice()))?;
+        check_float_steiner_points(points)?;
+        Ok(self.triangulate_with_steiner_points(points))
+    }
+
+    fn triangulate_with_steiner_outcomes(
+        &self,
+        points: &[P],
+    ) -> (RawTriangulation<P>, Vec<SteinerOutcome>) {
+        if let Some(mut rect) = FloatRect::with_paths(self) {
+            points.add_to_rect(&mut rect);
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let int_points = points.to_int(&adapter);
+            let (raw, outcomes) = self
+                .to_int(&adapter)
+                .triangulate_with_steiner_outcomes(&int_points);
+            (RawTriangulation { raw, adapter }, outcomes)
+        } else {
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            (raw, vec![SteinerOutcome::Outside; points.len()])
+        }
+    }
+
+    fn triangulate_with_constraints(
+        &self,
+        segments: &[[P; 2]],
+    ) -> (RawTriangulation<P>, Vec<[P; 2]>) {
+        if let Some(mut rect) = FloatRect::with_paths(self) {
+            segments.add_to_rect(&mut rect);
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let int_segments = segments.segments_to_int(&adapter);
+            let (raw, missed) = self
+                .to_int(&adapter)
+                .triangulate_with_constraints(&int_segments);
+            let missed = segments_to_float(&missed, &adapter);
+            (RawTriangulation { raw, adapter }, missed)
+        } else {
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            (raw, Vec::new())
+        }
+    }
+
+    fn triangulate_with_attributes<A: Lerp>(
+        &self,
+        attributes: &[A],
+    ) -> Result<(RawTriangulation<P>, Vec<A>), TriangulationError> {
+        if let Some(rect) = FloatRect::with_paths(self) {
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let (raw, attributes) = self
+                .to_int(&adapter)
+                .triangulate_with_attributes(attributes)?;
+            Ok((RawTriangulation { raw, adapter }, attributes))
+        } else {
+            check_attributes_count(0, attributes.len())?;
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            Ok((raw, Vec::new()))
+        }
+    }
 }
 
 impl<P: FloatPointCompatible> Triangulatable<P> for [Shape<P>] {
@@ -106,7 +349,8 @@ impl<P: FloatPointCompatible> Triangulatable<P> for [Shape<P>] {
     }
 
     fn triangulate_with_steiner_points(&self, points: &[P]) -> RawTriangulation<P> {
-        if let Some(rect) = FloatRect::with_list_of_paths(self) {
+        if let Some(mut rect) = FloatRect::with_list_of_paths(self) {
+            points.add_to_rect(&mut rect);
             let adapter = FloatPointAdapter::<P>::new(rect);
             let float_points = points.to_int(&adapter);
             let raw = self
@@ -120,4 +364,76 @@ impl<P: FloatPointCompatible> Triangulatable<P> for [Shape<P>] {
             }
         }
     }
+
+    fn try_triangulate_with_steiner_points(
+        &self,
+        points: &[P],
+    ) -> Result<RawTriangulation<P>, TriangulationError> {
+        check_float_paths(self.iter().flatten().map(|c| c.as_slice()))?;
+        check_float_steiner_points(points)?;
+        Ok(self.triangulate_with_steiner_points(points))
+    }
+
+    fn triangulate_with_steiner_outcomes(
+        &self,
+        points: &[P],
+    ) -> (RawTriangulation<P>, Vec<SteinerOutcome>) {
+        if let Some(mut rect) = FloatRect::with_list_of_paths(self) {
+            points.add_to_rect(&mut rect);
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let int_points = points.to_int(&adapter);
+            let (raw, outcomes) = self
+                .to_int(&adapter)
+                .triangulate_with_steiner_outcomes(&int_points);
+            (RawTriangulation { raw, adapter }, outcomes)
+        } else {
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            (raw, vec![SteinerOutcome::Outside; points.len()])
+        }
+    }
+
+    fn triangulate_with_constraints(
+        &self,
+        segments: &[[P; 2]],
+    ) -> (RawTriangulation<P>, Vec<[P; 2]>) {
+        if let Some(mut rect) = FloatRect::with_list_of_paths(self) {
+            segments.add_to_rect(&mut rect);
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let int_segments = segments.segments_to_int(&adapter);
+            let (raw, missed) = self
+                .to_int(&adapter)
+                .triangulate_with_constraints(&int_segments);
+            let missed = segments_to_float(&missed, &adapter);
+            (RawTriangulation { raw, adapter }, missed)
+        } else {
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            (raw, Vec::new())
+        }
+    }
+
+    fn triangulate_with_attributes<A: Lerp>(
+        &self,
+        attributes: &[A],
+    ) -> Result<(RawTriangulation<P>, Vec<A>), TriangulationError> {
+        if let Some(rect) = FloatRect::with_list_of_paths(self) {
+            let adapter = FloatPointAdapter::<P>::new(rect);
+            let (raw, attributes) = self
+                .to_int(&adapter)
+                .triangulate_with_attributes(attributes)?;
+            Ok((RawTriangulation { raw, adapter }, attributes))
+        } else {
+            check_attributes_count(0, attributes.len())?;
+            let raw = RawTriangulation {
+                raw: RawIntTriangulation::default(),
+                adapter: FloatPointAdapter::<P>::new(FloatRect::zero()),
+            };
+            Ok((raw, Vec::new()))
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use crate::int::error::{check_points_count, TriangulationError};
 use crate::int::triangulation::{IndexType, IntTriangulation, RawIntTriangulation};
 use alloc::vec::Vec;
 use i_overlay::i_float::adapter::FloatPointAdapter;
@@ -51,6 +52,16 @@ impl<P: FloatPointCompatible> RawTriangulation<P> {
             points: self.points(),
         }
     }
+
+    /// Converts this flat triangulation into a flat [`Triangulation`], or returns
+    /// [`TriangulationError::IndexOverflow`] if `I` cannot address all points.
+    #[inline]
+    pub fn try_to_triangulation<I: IndexType>(
+        &self,
+    ) -> Result<Triangulation<P, I>, TriangulationError> {
+        check_points_count::<I>(self.raw.points.len())?;
+        Ok(self.to_triangulation())
+    }
 }
 
 impl<P, I: IndexType> Triangulation<P, I> {
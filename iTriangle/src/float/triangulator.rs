@@ -1,9 +1,16 @@
 use crate::float::triangulation::Triangulation;
+use crate::int::diagnostics::ValidationIssue;
+use crate::int::error::{check_float_paths, TriangulationError};
+use crate::int::provenance::VertexOrigin;
 use crate::int::triangulation::{IndexType, IntTriangulation};
 use crate::int::triangulator::IntTriangulator;
 use crate::int::validation::Validation;
+use alloc::vec::Vec;
 use i_overlay::core::solver::Solver;
+use i_overlay::i_float::adapter::FloatPointAdapter;
 use i_overlay::i_float::float::compatible::FloatPointCompatible;
+use i_overlay::i_float::float::number::FloatNumber;
+use i_overlay::i_float::float::rect::FloatRect;
 use i_overlay::i_shape::flat::buffer::FlatContoursBuffer;
 use i_overlay::i_shape::source::resource::ShapeResource;
 
@@ -12,6 +19,8 @@ pub struct Triangulator<I> {
     flat_buffer: Option<FlatContoursBuffer>,
     int_buffer: Option<IntTriangulation<I>>,
     int_triangulator: IntTriangulator<I>,
+    // the adapter of the last validated input, maps issue locations back to float space
+    issues_adapter: Option<FloatPointAdapter<[f64; 2]>>,
 }
 
 impl<I: IndexType> Triangulator<I> {
@@ -30,20 +39,110 @@ impl<I: IndexType> Triangulator<I> {
         self.int_triangulator.delaunay
     }
 
-    /// Enables or disables Earcut64 optimization for small contours.
+    /// Enables or disables Earcut optimization for small contours.
     ///
-    /// When enabled, the triangulator will automatically use the highly optimized Earcut64
-    /// algorithm for any contour with fewer or equal than 64 points. This reduces overhead
+    /// When enabled, the triangulator will automatically use the highly optimized Earcut
+    /// algorithm for any contour with fewer or equal than 256 points. The solver is picked
+    /// by size: Earcut64, Earcut128 or Earcut256. Shapes with holes take the same path
+    /// when the holes do not touch and the shape still fits after every hole is bridged
+    /// to the outer contour. This reduces overhead
     /// for small polygons while maintaining correctness.
     pub fn earcut(&mut self, enable: bool) {
         self.int_triangulator.earcut = enable;
     }
 
-    /// Returns whether Earcut64 optimization is currently enabled.
+    /// Returns whether Earcut optimization is currently enabled.
     pub fn is_earcut(&self) -> bool {
         self.int_triangulator.earcut
     }
 
+    /// Enables or disables vertex provenance tracking.
+    ///
+    /// When enabled, every triangulation records where each output vertex came from,
+    /// see [`Triangulator::vertex_origins`].
+    pub fn provenance(&mut self, enable: bool) {
+        self.int_triangulator.provenance = enable;
+    }
+
+    /// Returns whether vertex provenance tracking is currently enabled.
+    pub fn is_provenance(&self) -> bool {
+        self.int_triangulator.provenance
+    }
+
+    /// Returns the origin of every vertex of the last triangulation.
+    ///
+    /// `vertex_origins()[i]` describes `triangulation.points[i]`. Input points are reported
+    /// as `Input { shape: 0, contour, vertex }`, where `contour` counts the paths of the
+    /// resource in iteration order. The list is empty unless provenance is enabled.
+    pub fn vertex_origins(&self) -> &[VertexOrigin] {
+        self.int_triangulator.vertex_origins()
+    }
+
+    /// Enables or disables validation diagnostics.
+    ///
+    /// When enabled, every validated triangulation records what the cleanup pass changed,
+    /// see [`Triangulator::validation_issues`].
+    pub fn diagnostics(&mut self, enable: bool) {
+        self.int_triangulator.diagnostics = enable;
+    }
+
+    /// Returns whether validation diagnostics are currently enabled.
+    pub fn is_diagnostics(&self) -> bool {
+        self.int_triangulator.diagnostics
+    }
+
+    /// Returns what validation changed in the input of the last triangulation.
+    ///
+    /// Contours are reported as `shape: 0` and `contour` counts the paths of the resource
+    /// in iteration order, the same as [`vertex_origins`](Self::vertex_origins).
+    /// The list is empty unless diagnostics are enabled.
+    pub fn validation_issues<P: FloatPointCompatible>(&self) -> Vec<ValidationIssue<P>> {
+        let issues = self.int_triangulator.validation_issues();
+        let Some(adapter) = &self.issues_adapter else {
+            return Vec::new();
+        };
+
+        let adapter = FloatPointAdapter::<P> {
+            dir_scale: FloatNumber::from_float(adapter.dir_scale),
+            inv_scale: FloatNumber::from_float(adapter.inv_scale),
+            offset: P::from_xy(
+                FloatNumber::from_float(adapter.offset[0]),
+                FloatNumber::from_float(adapter.offset[1]),
+            ),
+            rect: FloatRect::new(
+                FloatNumber::from_float(adapter.rect.min_x),
+                FloatNumber::from_float(adapter.rect.max_x),
+                FloatNumber::from_float(adapter.rect.min_y),
+                FloatNumber::from_float(adapter.rect.max_y),
+            ),
+        };
+
+        issues
+            .iter()
+            .map(|issue| issue.to_float(&adapter))
+            .collect()
+    }
+
+    #[inline]
+    fn keep_adapter<P: FloatPointCompatible>(&mut self, adapter: &FloatPointAdapter<P>) {
+        if !self.int_triangulator.diagnostics {
+            self.issues_adapter = None;
+            return;
+        }
+
+        self.issues_adapter = Some(FloatPointAdapter {
+            dir_scale: adapter.dir_scale.to_f64(),
+            inv_scale: adapter.inv_scale.to_f64(),
+            offset: [adapter.offset.x().to_f64(), adapter.offset.y().to_f64()],
+            rect: FloatRect::new(
+                adapter.rect.min_x.to_f64(),
+                adapter.rect.max_x.to_f64(),
+                adapter.rect.min_y.to_f64(),
+                adapter.rect.max_y.to_f64(),
+            ),
+        });
+    }
+
     /// Performs triangulation on the given shape resource and returns a new `Triangulation`.
     ///
     /// - `resource`: A shape container implementing `ShapeResource` (e.g., contour, contours, or shapes).
@@ -57,6 +156,7 @@ impl<I: IndexType> Triangulator<I> {
             flat_buffer: Some(FlatContoursBuffer::with_capacity(max_points_count)),
             int_buffer: Some(IntTriangulation::with_capacity(max_points_count)),
             int_triangulator: IntTriangulator::new(max_points_count, validation, solver),
+            issues_adapter: None,
         }
     }
 }
@@ -91,6 +191,7 @@ impl<I: IndexType> Triangulator<I> {
 
         self.int_triangulator
             .triangulate_flat_into(&mut flat_buffer, &mut int_buffer);
+        self.keep_adapter(&adapter);
 
         let triangulation = int_buffer.to_float(&adapter);
 
@@ -124,6 +225,7 @@ impl<I: IndexType> Triangulator<I> {
 
         self.int_triangulator
             .triangulate_flat_into(&mut flat_buffer, &mut int_buffer);
+        self.keep_adapter(&adapter);
 
         triangulation.set_with_int(&int_buffer, &adapter);
 
@@ -131,6 +233,61 @@ impl<I: IndexType> Triangulator<I> {
         self.int_buffer = Some(int_buffer);
     }
 
+    /// Works like [`triangulate`](Self::triangulate) but reports bad input instead of panicking.
+    ///
+    /// # Errors
+    /// Returns a [`TriangulationError`] if the resource has no contour with at least three
+    /// points, has a non-finite coordinate, or the result has more points than `I` can address.
+    #[inline]
+    pub fn try_triangulate<R, P>(
+        &mut self,
+        resource: &R,
+    ) -> Result<Triangulation<P, I>, TriangulationError>
+    where
+        R: ShapeResource<P> + ?Sized,
+        P: FloatPointCompatible,
+    {
+        let mut triangulation = Triangulation::with_capacity(0);
+        self.try_triangulate_into(resource, &mut triangulation)?;
+        Ok(triangulation)
+    }
+
+    /// Works like [`triangulate_into`](Self::triangulate_into) but reports bad input instead
+    /// of panicking, see [`try_triangulate`](Self::try_triangulate).
+    ///
+    /// `triangulation` and the results of the last successful call are left unchanged on error.
+    #[inline]
+    pub fn try_triangulate_into<R, P>(
+        &mut self,
+        resource: &R,
+        triangulation: &mut Triangulation<P, I>,
+    ) -> Result<(), TriangulationError>
+    where
+        R: ShapeResource<P> + ?Sized,
+        P: FloatPointCompatible,
+    {
+        check_float_paths(resource.iter_paths())?;
+
+        let mut flat_buffer = self.flat_buffer.take().unwrap_or_default();
+        let mut int_buffer = self.int_buffer.take().unwrap_or_default();
+        let adapter = flat_buffer.set_with_resource(resource);
+
+        let result = self
+            .int_triangulator
+            .try_triangulate_flat_into(&mut flat_buffer, &mut int_buffer);
+
+        // the issues of the last successful call stay mapped by its adapter
+        if result.is_ok() {
+            self.keep_adapter(&adapter);
+            triangulation.set_with_int(&int_buffer, &adapter);
+        }
+
+        self.flat_buffer = Some(flat_buffer);
+        self.int_buffer = Some(int_buffer);
+
+        result
+    }
+
     /// Performs triangulation on the provided shape resource and returns a new `Triangulation`.
     ///
     /// Skips input validation (e.g., area checks or self-intersections), offering faster performance
@@ -1,4 +1,5 @@
 use crate::geom::point::IndexPoint;
+use i_overlay::i_float::triangle::Triangle;
 
 #[derive(Debug, Clone)]
 pub struct Abc {
@@ -56,27 +57,52 @@ impl IntTriangle {
         }
     }
 
-    pub fn opposite(&self, neighbor: usize) -> usize {
-        #[cfg(debug_assertions)]
-        {
-            for i in 0..3 {
-                if self.neighbors[i] == neighbor {
-                    return i;
-                }
-            }
-
-            panic!("Neighbor is not present");
+    #[inline]
+    pub(crate) fn position_of(&self, vertex_index: usize) -> usize {
+        if self.vertices[0].index == vertex_index {
+            0
+        } else if self.vertices[1].index == vertex_index {
+            1
+        } else {
+            2
         }
+    }
 
-        #[cfg(not(debug_assertions))]
-        {
-            for i in 0..2 {
-                if self.neighbors[i] == neighbor {
-                    return i;
-                }
-            }
+    /// Position of the vertex opposite the edge shared with `neighbor`.
+    ///
+    /// Falls back to `2` if `neighbor` is not adjacent, use
+    /// [`try_opposite`](Self::try_opposite) to detect that.
+    #[inline]
+    pub fn opposite(&self, neighbor: usize) -> usize {
+        self.try_opposite(neighbor).unwrap_or(2)
+    }
 
-            2
+    /// Position of the vertex opposite the edge shared with `neighbor`,
+    /// or `None` if `neighbor` is not adjacent to this triangle.
+    #[inline]
+    pub fn try_opposite(&self, neighbor: usize) -> Option<usize> {
+        self.neighbors.iter().position(|&n| n == neighbor)
+    }
+
+    /// Doubled unsigned area of the triangle.
+    #[inline]
+    pub fn area_two(&self) -> u64 {
+        let [a, b, c] = self.vertices.map(|v| v.point);
+        Triangle::area_two_point(a, b, c).unsigned_abs()
+    }
+
+    /// `true` if the edge opposite the vertex at `position` is on the mesh boundary.
+    #[inline]
+    pub fn is_boundary(&self, position: usize) -> bool {
+        self.neighbors[position] == usize::MAX
+    }
+
+    #[inline]
+    pub(crate) fn abc_by_position(&self, position: usize) -> Abc {
+        match position {
+            0 => self.abc_by_a(),
+            1 => self.abc_by_b(),
+            _ => self.abc_by_c(),
         }
     }
 
@@ -0,0 +1,398 @@
+use crate::int::error::{check_attributes_count, TriangulationError};
+use crate::int::prepared::{sqr_distance_to_segment_i128, Grid};
+use crate::int::provenance::{ProvenanceMap, VertexOrigin};
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_float::int::rect::IntRect;
+use i_overlay::i_shape::int::shape::{IntContour, IntShape};
+
+/// A per-vertex attribute that can be interpolated between vertices.
+///
+/// Implemented for `f32`, `f64` and arrays of them, e.g. `[f32; 2]` for uv
+/// or `[f32; 4]` for a color.
+pub trait Lerp: Clone {
+    /// Returns `self` moved toward `other` by the factor `t` in `0..=1`.
+    fn lerp(&self, other: &Self, t: f64) -> Self;
+
+    /// Returns the weighted mix of three attributes, the weights sum to `1`.
+    #[inline]
+    fn barycentric(a: &Self, b: &Self, c: &Self, weights: [f64; 3]) -> Self {
+        let ab_weight = weights[0] + weights[1];
+        let ab = if ab_weight.abs() > f64::EPSILON {
+            a.lerp(b, weights[1] / ab_weight)
+        } else {
+            a.clone()
+        };
+        ab.lerp(c, weights[2])
+    }
+}
+
+impl Lerp for f32 {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t as f32
+    }
+}
+
+impl Lerp for f64 {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl<T: Lerp, const N: usize> Lerp for [T; N] {
+    #[inline]
+    fn lerp(&self, other: &Self, t: f64) -> Self {
+        core::array::from_fn(|i| self[i].lerp(&other[i], t))
+    }
+}
+
+// input paths with attributes, in the order of the flat attribute list
+pub(crate) struct AttributeSource<'a, A> {
+    paths: Vec<&'a [IntPoint]>,
+    shape_offsets: Vec<usize>,
+    path_offsets: Vec<usize>,
+    count: usize,
+    attributes: &'a [A],
+    map: ProvenanceMap,
+    // the input edges as [path, first vertex], built on the first point off the input
+    edges: Vec<[usize; 2]>,
+    edge_grid: Option<Grid>,
+}
+
+impl<'a, A: Lerp> AttributeSource<'a, A> {
+    #[inline]
+    pub(crate) fn with_contour(
+        contour: &'a [IntPoint],
+        attributes: &'a [A],
+    ) -> Result<Self, TriangulationError> {
+        let mut source = Self::new(attributes);
+        source.add_shape([contour]);
+        source.finish()
+    }
+
+    #[inline]
+    pub(crate) fn with_shape(
+        shape: &'a [IntContour],
+        attributes: &'a [A],
+    ) -> Result<Self, TriangulationError> {
+        let mut source = Self::new(attributes);
+        source.add_shape(shape.iter().map(|c| c.as_slice()));
+        source.finish()
+    }
+
+    #[inline]
+    pub(crate) fn with_shapes(
+        shapes: &'a [IntShape],
+        attributes: &'a [A],
+    ) -> Result<Self, TriangulationError> {
+        let mut source = Self::new(attributes);
+        for shape in shapes.iter() {
+            source.add_shape(shape.iter().map(|c| c.as_slice()));
+        }
+        source.finish()
+    }
+
+    #[inline]
+    fn new(attributes: &'a [A]) -> Self {
+        Self {
+            paths: Vec::new(),
+            shape_offsets: Vec::new(),
+            path_offsets: Vec::new(),
+            count: 0,
+            attributes,
+            map: ProvenanceMap::default(),
+            edges: Vec::new(),
+            edge_grid: None,
+        }
+    }
+
+    fn add_shape<I: IntoIterator<Item = &'a [IntPoint]>>(&mut self, contours: I) {
+        let shape_index = self.shape_offsets.len();
+        self.shape_offsets.push(self.paths.len());
+        for (contour_index, path) in contours.into_iter().enumerate() {
+            self.map.add_contour(shape_index, contour_index, path);
+            self.path_offsets.push(self.count);
+            self.paths.push(path);
+            self.count += path.len();
+        }
+    }
+
+    #[inline]
+    fn finish(self) -> Result<Self, TriangulationError> {
+        check_attributes_count(self.count, self.attributes.len())?;
+        Ok(self)
+    }
+
+    /// Returns an attribute for every output point.
+    pub(crate) fn interpolate(&mut self, points: &[IntPoint]) -> Vec<A> {
+        let origins = self.map.resolve(points);
+        if origins
+            .iter()
+            .any(|origin| !matches!(origin, VertexOrigin::Input { .. }))
+        {
+            self.build_edge_grid();
+        }
+
+        points
+            .iter()
+            .zip(origins.iter())
+            .map(|(&p, origin)| match *origin {
+                VertexOrigin::Input {
+                    shape,
+                    contour,
+                    vertex,
+                } => {
+                    let path_index = self.shape_offsets[shape] + contour;
+                    self.attributes[self.path_offsets[path_index] + vertex].clone()
+                }
+                _ => self.interpolate_on_edge(p),
+            })
+            .collect()
+    }
+
+    fn build_edge_grid(&mut self) {
+        if self.edge_grid.is_some() {
+            return;
+        }
+
+        let mut boxes = Vec::with_capacity(self.count);
+        for (path_index, path) in self.paths.iter().enumerate() {
+            let mut a = match path.last() {
+                Some(&a) => a,
+                None => continue,
+            };
+            for (i, &b) in path.iter().enumerate() {
+                let i0 = if i == 0 { path.len() - 1 } else { i - 1 };
+                self.edges.push([path_index, i0]);
+                boxes.push(IntRect::with_ab(a, b));
+                a = b;
+            }
+        }
+        self.edge_grid = Some(Grid::new(&boxes));
+    }
+
+    #[inline]
+    fn edge_points(&self, edge: [usize; 2]) -> [IntPoint; 2] {
+        let [path_index, i0] = edge;
+        let path = self.paths[path_index];
+        [path[i0], path[(i0 + 1) % path.len()]]
+    }
+
+    // a new boundary point lies on (or next to, after rounding) the input edge it came from,
+    // the edge grid finds that edge without a scan over all input edges, distances are
+    // rounded down to whole units and a crossing point takes the first of its edges
+    fn interpolate_on_edge(&self, p: IntPoint) -> A {
+        let (x, y) = (p.x as i64, p.y as i64);
+        let nearest = self.edge_grid.as_ref().and_then(|grid| {
+            grid.nearest(x, y, |index| {
+                let [a, b] = self.edge_points(self.edges[index]);
+                sqr_distance_to_segment_i128(x, y, a, b)
+            })
+        });
+        let Some(index) = nearest else {
+            return self.attributes[0].clone();
+        };
+
+        let edge = self.edges[index];
+        let [a, b] = self.edge_points(edge);
+        let t = segment_position(p, a, b);
+
+        let [path_index, i0] = edge;
+        let path_len = self.paths[path_index].len();
+        let offset = self.path_offsets[path_index];
+        let a = &self.attributes[offset + i0];
+        let b = &self.attributes[offset + (i0 + 1) % path_len];
+        a.lerp(b, t)
+    }
+}
+
+// returns the position of the closest point on ab
+#[inline]
+fn segment_position(p: IntPoint, a: IntPoint, b: IntPoint) -> f64 {
+    let abx = b.x as f64 - a.x as f64;
+    let aby = b.y as f64 - a.y as f64;
+    let apx = p.x as f64 - a.x as f64;
+    let apy = p.y as f64 - a.y as f64;
+
+    let len = abx * abx + aby * aby;
+    if len > 0.0 {
+        ((apx * abx + apy * aby) / len).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Appends an attribute for every point inserted by refinement.
+///
+/// `parents[i]` is the triangle that contains `points[count + i]`,
+/// where `count` is the number of attributes before the call.
+pub(crate) fn interpolate_refined<A: Lerp>(
+    points: &[IntPoint],
+    parents: &[[usize; 3]],
+    attributes: &mut Vec<A>,
+) {
+    attributes.reserve(parents.len());
+    for parent in parents.iter() {
+        let m = points[attributes.len()];
+        debug_assert!(parent.iter().all(|&v| v < attributes.len()));
+        let [a, b, c] = parent.map(|v| points[v]);
+
+        let area = signed_area(a, b, c);
+        let weights = if area != 0.0 {
+            let wa = signed_area(m, b, c) / area;
+            let wb = signed_area(a, m, c) / area;
+            [wa, wb, 1.0 - wa - wb]
+        } else {
+            [1.0, 0.0, 0.0]
+        };
+
+        let value = A::barycentric(
+            &attributes[parent[0]],
+            &attributes[parent[1]],
+            &attributes[parent[2]],
+            weights,
+        );
+        attributes.push(value);
+    }
+}
+
+#[inline]
+fn signed_area(a: IntPoint, b: IntPoint, c: IntPoint) -> f64 {
+    let abx = b.x as f64 - a.x as f64;
+    let aby = b.y as f64 - a.y as f64;
+    let acx = c.x as f64 - a.x as f64;
+    let acy = c.y as f64 - a.y as f64;
+    abx * acy - aby * acx
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::error::TriangulationError;
+    use crate::int::triangulatable::IntTriangulatable;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::path::IntPath;
+    use rand::RngExt;
+
+    fn linear(p: IntPoint) -> f64 {
+        p.x as f64 + 2.0 * p.y as f64
+    }
+
+    #[test]
+    fn test_0() {
+        // a bow tie, the crossing point is interpolated along the first edge
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 0),
+            IntPoint::new(0, 10),
+        ];
+        let z = [0.0f32, 10.0, 20.0, 30.0];
+
+        let (raw, attributes) = contour.triangulate_with_attributes(&z).unwrap();
+        assert_eq!(raw.points.len(), attributes.len());
+
+        for (p, &a) in raw.points.iter().zip(attributes.iter()) {
+            if let Some(i) = contour.iter().position(|c| c == p) {
+                assert_eq!(a, z[i]);
+            } else {
+                assert_eq!(*p, IntPoint::new(5, 5));
+                assert_eq!(a, 5.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_1() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 500),
+            IntPoint::new(0, 1000),
+        ];
+        let uv: Vec<[f64; 2]> = contour.iter().map(|&p| [linear(p), -linear(p)]).collect();
+
+        let (raw, mut attributes) = contour.triangulate_with_attributes(&uv).unwrap();
+        let delaunay = raw
+            .into_delaunay()
+            .refine_with_circumcenters_and_attributes(1000, &mut attributes)
+            .unwrap();
+
+        assert!(delaunay.points.len() > contour.len());
+        assert_eq!(delaunay.points.len(), attributes.len());
+
+        // barycentric interpolation keeps a linear function exact
+        for (&p, a) in delaunay.points.iter().zip(attributes.iter()) {
+            assert!((a[0] - linear(p)).abs() < 0.001);
+            assert!((a[1] + linear(p)).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_2() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(0, 10),
+        ];
+        assert_eq!(
+            contour.triangulate_with_attributes(&[0.0f64; 2]).err(),
+            Some(TriangulationError::AttributeCountMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+
+        let mut attributes = vec![0.0f64; 2];
+        let mut delaunay = contour.triangulate().into_delaunay();
+        let points = delaunay.points.clone();
+        assert_eq!(
+            delaunay
+                .refine_with_circumcenters_and_attributes_mut(0, &mut attributes)
+                .err(),
+            Some(TriangulationError::AttributeCountMismatch {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(delaunay.points, points);
+        assert_eq!(attributes.len(), 2);
+    }
+
+    #[test]
+    fn test_random_0() {
+        for _ in 0..5_000 {
+            let shapes = vec![vec![random(40, 8), random(20, 5)], vec![random(30, 6)]];
+            let attributes: Vec<f64> = shapes
+                .iter()
+                .flatten()
+                .flatten()
+                .map(|&p| linear(p))
+                .collect();
+
+            let (raw, result) = shapes.triangulate_with_attributes(&attributes).unwrap();
+            assert_eq!(raw.points.len(), result.len());
+
+            // new points are rounded, they stay close to the source edge
+            for (&p, &a) in raw.points.iter().zip(result.iter()) {
+                assert!((a - linear(p)).abs() <= 4.0);
+            }
+        }
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
@@ -0,0 +1,263 @@
+use crate::int::attribute::Lerp;
+use crate::int::prepared::IntPreparedTriangulation;
+use crate::int::triangulation::{IndexType, IntTriangulation};
+use crate::location::TriangleIndex;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_float::triangle::Triangle;
+
+/// Exact barycentric coordinates of a point in a mesh triangle.
+///
+/// The weight of the triangle vertex `i` is `weights[i] / denominator`. The weights
+/// sum to `denominator`, a point on an edge has a zero weight at the opposite
+/// vertex and a point on a vertex has the whole weight there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntBarycentric {
+    pub triangle: TriangleIndex,
+    pub weights: [u64; 3],
+    pub denominator: u64,
+}
+
+impl IntBarycentric {
+    /// Computes the coordinates of `p` in the triangle `abc`, `p` must be inside
+    /// the triangle or on its border.
+    ///
+    /// Returns `None` for a degenerate triangle, it has no coordinates.
+    #[inline]
+    pub fn new(triangle: TriangleIndex, [a, b, c]: [IntPoint; 3], p: IntPoint) -> Option<Self> {
+        let abc = Triangle::area_two_point(a, b, c);
+        if abc == 0 {
+            return None;
+        }
+        // the sub-triangles have the orientation of abc
+        let sign = abc.signum();
+        let weight = |area: i64| (sign * area).max(0) as u64;
+        Some(Self {
+            triangle,
+            weights: [
+                weight(Triangle::area_two_point(p, b, c)),
+                weight(Triangle::area_two_point(a, p, c)),
+                weight(Triangle::area_two_point(a, b, p)),
+            ],
+            denominator: abc.unsigned_abs(),
+        })
+    }
+
+    /// Returns the weights as fractions that sum to `1`.
+    #[inline]
+    pub fn to_f64(&self) -> [f64; 3] {
+        let d = self.denominator as f64;
+        self.weights.map(|w| w as f64 / d)
+    }
+
+    /// Mixes the values at the triangle vertices.
+    ///
+    /// A point on a vertex gets the vertex value unchanged, a point on an edge
+    /// only mixes the values of the edge ends.
+    #[inline]
+    pub fn interpolate<A: Lerp>(&self, values: [&A; 3]) -> A {
+        mix(values, self.to_f64())
+    }
+}
+
+// exact zero weights keep the values of the vertex or the edge ends unchanged
+pub(crate) fn mix<A: Lerp>(values: [&A; 3], w: [f64; 3]) -> A {
+    match w {
+        [_, 0.0, 0.0] => values[0].clone(),
+        [0.0, _, 0.0] => values[1].clone(),
+        [0.0, 0.0, _] => values[2].clone(),
+        [0.0, _, _] => values[1].lerp(values[2], w[2]),
+        [_, 0.0, _] => values[2].lerp(values[0], w[0]),
+        [_, _, 0.0] => values[0].lerp(values[1], w[1]),
+        _ => A::barycentric(values[0], values[1], values[2], w),
+    }
+}
+
+impl<I: IndexType> IntTriangulation<I> {
+    /// Locates the points like [`locate_points`](Self::locate_points) and returns
+    /// the exact barycentric coordinates in the holding triangle, `None` for points
+    /// outside the mesh or in a degenerate triangle.
+    pub fn locate_barycentric(&self, points: &[IntPoint]) -> Vec<Option<IntBarycentric>> {
+        self.locate_points(points)
+            .iter()
+            .zip(points.iter())
+            .map(|(location, &p)| {
+                let triangle = location.triangle()?;
+                IntBarycentric::new(triangle, self.triangle_points(triangle), p)
+            })
+            .collect()
+    }
+
+    /// Interpolates a per-vertex field at the points, `None` for points outside the mesh
+    /// or in a degenerate triangle.
+    ///
+    /// `values[i]` is the value at `self.points[i]`, see [`IntBarycentric::interpolate`].
+    ///
+    /// # Panics
+    /// Panics if `values` and `points` of the triangulation have different lengths.
+    pub fn interpolate<A: Lerp>(&self, values: &[A], points: &[IntPoint]) -> Vec<Option<A>> {
+        assert_eq!(
+            values.len(),
+            self.points.len(),
+            "every mesh point must have a value"
+        );
+        self.locate_barycentric(points)
+            .into_iter()
+            .map(|barycentric| {
+                let barycentric = barycentric?;
+                let [i0, i1, i2] = barycentric.triangle.to_vertex_indices();
+                Some(barycentric.interpolate([
+                    &values[self.indices[i0].into_usize()],
+                    &values[self.indices[i1].into_usize()],
+                    &values[self.indices[i2].into_usize()],
+                ]))
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn triangle_points(&self, triangle: TriangleIndex) -> [IntPoint; 3] {
+        triangle
+            .to_vertex_indices()
+            .map(|i| self.points[self.indices[i].into_usize()])
+    }
+}
+
+impl IntPreparedTriangulation {
+    /// Locates the point like [`locate_point`](Self::locate_point) and returns
+    /// the exact barycentric coordinates in the holding triangle, `None` outside
+    /// the mesh or in a degenerate triangle.
+    #[inline]
+    pub fn locate_barycentric(&self, p: IntPoint) -> Option<IntBarycentric> {
+        let triangle = self.locate_point(p).triangle()?;
+        IntBarycentric::new(triangle, self.triangles[triangle.index()], p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::barycentric::IntBarycentric;
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::int::triangulation::IntTriangulation;
+    use crate::location::TriangleIndex;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use rand::RngExt;
+
+    fn square() -> IntTriangulation<u16> {
+        IntTriangulation {
+            points: vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(4, 0),
+                IntPoint::new(4, 4),
+                IntPoint::new(0, 4),
+            ],
+            indices: vec![0, 1, 2, 0, 2, 3],
+        }
+    }
+
+    #[test]
+    fn test_0() {
+        let triangulation = square();
+        let points = vec![
+            IntPoint::new(3, 1),
+            IntPoint::new(2, 2),
+            IntPoint::new(2, 0),
+            IntPoint::new(4, 4),
+            IntPoint::new(5, 1),
+        ];
+        let result = triangulation.locate_barycentric(&points);
+
+        let t0 = TriangleIndex::new(0);
+        assert_eq!(
+            result[0],
+            Some(IntBarycentric {
+                triangle: t0,
+                weights: [4, 8, 4],
+                denominator: 16
+            })
+        );
+        // on the diagonal, on the outer edge, on a vertex
+        assert_eq!(result[1].unwrap().weights, [8, 0, 8]);
+        assert_eq!(result[2].unwrap().weights, [8, 8, 0]);
+        assert_eq!(result[3].unwrap().weights, [0, 0, 16]);
+        assert_eq!(result[4], None);
+
+        // a degenerate triangle has no coordinates
+        let line = [
+            IntPoint::new(0, 0),
+            IntPoint::new(2, 2),
+            IntPoint::new(4, 4),
+        ];
+        assert_eq!(IntBarycentric::new(t0, line, IntPoint::new(1, 1)), None);
+    }
+
+    #[test]
+    fn test_1() {
+        let triangulation = square();
+        let values = vec![[0.0, 1.0], [4.0, 1.0], [4.0, 5.0], [0.0, 5.0]];
+        let points = vec![
+            IntPoint::new(1, 3),
+            IntPoint::new(0, 4),
+            IntPoint::new(0, 1),
+            IntPoint::new(-1, 1),
+        ];
+
+        let result = triangulation.interpolate(&values, &points);
+
+        assert_eq!(result[0], Some([1.0, 4.0]));
+        assert_eq!(result[1], Some([0.0, 5.0]));
+        assert_eq!(result[2], Some([0.0, 2.0]));
+        assert_eq!(result[3], None);
+    }
+
+    #[test]
+    fn test_random_0() {
+        // a linear field is reproduced exactly up to rounding
+        let field = |p: IntPoint| 2.0 * p.x as f64 - 3.0 * p.y as f64 + 1.0;
+
+        let mut rng = rand::rng();
+        for _ in 0..200 {
+            let path: Vec<_> = (0..8)
+                .map(|_| IntPoint::new(rng.random_range(-20..=20), rng.random_range(-20..=20)))
+                .collect();
+            let shapes = path.simplify(FillRule::NonZero, IntOverlayOptions::default());
+            let Some(first) = shapes.first() else {
+                continue;
+            };
+
+            let triangulation = first.triangulate().into_triangulation::<u32>();
+            let values: Vec<_> = triangulation.points.iter().map(|&p| field(p)).collect();
+
+            let points: Vec<_> = (-22..=22)
+                .flat_map(|x| (-22..=22).map(move |y| IntPoint::new(x, y)))
+                .collect();
+            let locations = triangulation.locate_points(&points);
+            let weights = triangulation.locate_barycentric(&points);
+            let result = triangulation.interpolate(&values, &points);
+
+            for i in 0..points.len() {
+                assert_eq!(locations[i].triangle().is_some(), result[i].is_some());
+                if let Some(barycentric) = weights[i] {
+                    assert_eq!(
+                        barycentric.weights.iter().sum::<u64>(),
+                        barycentric.denominator
+                    );
+                }
+                if let Some(value) = result[i] {
+                    assert!((value - field(points[i])).abs() < 1e-9);
+                }
+            }
+
+            // mesh vertices keep their values
+            let result = triangulation.interpolate(&values, &triangulation.points);
+            for (value, expected) in result.iter().zip(values.iter()) {
+                assert_eq!(value, &Some(*expected));
+            }
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::int::constraint::EdgePoint;
 use crate::int::monotone::v_segment::VSegment;
 use alloc::vec;
 use alloc::vec::Vec;
@@ -12,7 +13,7 @@ use i_tree::ExpiredKey;
 struct ShapeEdge {
     a: IntPoint,
     b: IntPoint,
-    shape_index: usize,
+    id: EdgeId,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -20,6 +21,30 @@ struct VEdge {
     min_y: i32,
     max_y: i32,
     x: i32,
+    id: EdgeId,
+}
+
+// the edge starts at path[edge_index]
+#[derive(Debug, Clone, Copy)]
+struct EdgeId {
+    shape_index: usize,
+    path_index: usize,
+    edge_index: usize,
+}
+
+/// Where a Steiner point lies relative to the shapes.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum PointBinding {
+    Inner(usize),
+    Edge(EdgePoint),
+    Vertex,
+    Outside,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BoundPoint {
+    pub(super) point: IntPoint,
+    pub(super) binding: PointBinding,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -36,18 +61,40 @@ impl ExpiredKey<i32> for VSegment {
 
 pub(super) trait SteinerInference {
     fn group_by_shapes(&self, points: &[IntPoint]) -> Vec<Vec<IntPoint>>;
+
+    /// Binds every unique point to a shape interior, a boundary edge or a vertex.
+    /// The result is sorted by points.
+    fn bind_points(&self, points: &[IntPoint]) -> Vec<BoundPoint>;
 }
 
 impl SteinerInference for [IntShape] {
     fn group_by_shapes(&self, points: &[IntPoint]) -> Vec<Vec<IntPoint>> {
-        if points.is_empty() {
-            return vec![Vec::new(); self.len()];
+        let mut groups = vec![Vec::new(); self.len()];
+        for bound in self.bind_points(points) {
+            if let PointBinding::Inner(shape_index) = bound.binding {
+                groups[shape_index].push(bound.point);
+            }
         }
+        groups
+    }
 
+    fn bind_points(&self, points: &[IntPoint]) -> Vec<BoundPoint> {
         let mut points = points.to_vec();
-        points.sort_unstable_by(|a, b| a.x.cmp(&b.x));
+        points.sort_unstable();
         points.dedup();
 
+        let mut result: Vec<_> = points
+            .iter()
+            .map(|&point| BoundPoint {
+                point,
+                binding: PointBinding::Outside,
+            })
+            .collect();
+
+        if points.is_empty() {
+            return result;
+        }
+
         let x_min = points[0].x;
         let x_max = points.last().unwrap().x;
 
@@ -56,9 +103,9 @@ impl SteinerInference for [IntShape] {
         let mut x_points = Vec::new();
 
         for (shape_index, shape) in self.iter().enumerate() {
-            for path in shape.iter() {
+            for (path_index, path) in shape.iter().enumerate() {
                 let mut a = *path.last().unwrap();
-                for &b in path.iter() {
+                for (b_index, &b) in path.iter().enumerate() {
                     if a.x < x_min && b.x < x_min || a.x > x_max && b.x > x_max {
                         a = b;
                         continue;
@@ -66,8 +113,18 @@ impl SteinerInference for [IntShape] {
 
                     x_points.push(a);
 
+                    let id = EdgeId {
+                        shape_index,
+                        path_index,
+                        edge_index: if b_index == 0 {
+                            path.len() - 1
+                        } else {
+                            b_index - 1
+                        },
+                    };
+
                     if a.x == b.x {
-                        v_edges.push(VEdge::new(a, b));
+                        v_edges.push(VEdge::new(a, b, id));
                         a = b;
                         continue;
                     };
@@ -78,7 +135,7 @@ impl SteinerInference for [IntShape] {
                         VSegment { a: b, b: a }
                     };
                     segments.push(TargetSegment {
-                        edge: ShapeEdge { a, b, shape_index },
+                        edge: ShapeEdge { a, b, id },
                         v_segment,
                     });
 
@@ -88,27 +145,30 @@ impl SteinerInference for [IntShape] {
         }
 
         if segments.is_empty() {
-            return vec![Vec::new(); self.len()];
+            return result;
         }
 
-        segments.sort_unstable_by(|s0, s1| s0.v_segment.a.cmp(&s1.v_segment.a));
-        v_edges.sort_unstable_by(|e0, e1| e0.x.cmp(&e1.x));
-        x_points.sort_unstable_by(|p0, p1| p0.x.cmp(&p1.x));
+        segments.sort_unstable_by_key(|s| s.v_segment.a);
+        v_edges.sort_unstable_by_key(|e| e.x);
+        x_points.sort_unstable_by_key(|p| p.x);
 
-        let mut groups = vec![Vec::new(); self.len()];
         let capacity = segments.len().ilog2() as usize;
         let mut tree = KeyExpTree::new(capacity);
 
         let empty_edge = ShapeEdge {
             a: IntPoint::EMPTY,
             b: IntPoint::EMPTY,
-            shape_index: usize::MAX,
+            id: EdgeId {
+                shape_index: usize::MAX,
+                path_index: 0,
+                edge_index: 0,
+            },
         };
 
         let mut i = 0;
         let mut j = 0;
         let mut t = 0;
-        'loop_by_points: for &p in points.iter() {
+        'loop_by_points: for (index, &p) in points.iter().enumerate() {
             while i < segments.len() {
                 let id_segment = &segments[i];
                 if p.x < id_segment.v_segment.a.x {
@@ -132,9 +192,11 @@ impl SteinerInference for [IntShape] {
 
             // TODO can be adjusted for binary search
             let mut k = j;
+            let mut v_edge = None;
             while k < v_edges.len() && v_edges[k].x == p.x {
                 if v_edges[k].contains(p.y) {
-                    continue 'loop_by_points;
+                    v_edge = Some(v_edges[k].id);
+                    break;
                 }
                 k += 1;
             }
@@ -152,19 +214,41 @@ impl SteinerInference for [IntShape] {
             let mut k = t;
             while k < x_points.len() && x_points[k].x == p.x {
                 if x_points[k].y == p.y {
+                    result[index].binding = PointBinding::Vertex;
                     continue 'loop_by_points;
                 }
                 k += 1;
             }
 
+            if let Some(id) = v_edge {
+                result[index].binding = PointBinding::Edge(id.edge_point(p));
+                continue;
+            }
+
             let edge = tree.first_less_or_equal_by(p.x, empty_edge, |s| s.is_under_point_order(p));
 
-            if edge.shape_index < groups.len() && edge.not_contains(p) && edge.is_direct() {
-                groups[edge.shape_index].push(p);
+            if edge.id.shape_index < self.len() {
+                if !edge.not_contains(p) {
+                    result[index].binding = PointBinding::Edge(edge.id.edge_point(p));
+                } else if edge.is_direct() {
+                    result[index].binding = PointBinding::Inner(edge.id.shape_index);
+                }
             }
         }
 
-        groups
+        result
+    }
+}
+
+impl EdgeId {
+    #[inline]
+    fn edge_point(&self, point: IntPoint) -> EdgePoint {
+        EdgePoint {
+            shape_index: self.shape_index,
+            path_index: self.path_index,
+            edge_index: self.edge_index,
+            point,
+        }
     }
 }
 
@@ -182,13 +266,14 @@ impl ShapeEdge {
 
 impl VEdge {
     #[inline]
-    fn new(a: IntPoint, b: IntPoint) -> Self {
+    fn new(a: IntPoint, b: IntPoint, id: EdgeId) -> Self {
         let (min_y, max_y) = if a.y < b.y { (a.y, b.y) } else { (b.y, a.y) };
 
         Self {
             min_y,
             max_y,
             x: a.x,
+            id,
         }
     }
 
@@ -0,0 +1,241 @@
+use alloc::vec::Vec;
+use i_overlay::core::fill_rule::FillRule;
+use i_overlay::core::overlay::IntOverlayOptions;
+use i_overlay::core::simplify::Simplify;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_shape::int::path::IntPath;
+use i_overlay::i_shape::int::shape::{IntShape, IntShapes};
+use i_overlay::string::clip::{ClipRule, IntClip};
+
+pub(super) struct ConstraintLayout {
+    pub(super) shapes: IntShapes,
+    pub(super) steiner_points: Vec<IntPoint>,
+    pub(super) segments: Vec<[IntPoint; 2]>,
+}
+
+pub(super) trait ConstraintInference {
+    fn layout_constraints(&self, segments: &[[IntPoint; 2]]) -> ConstraintLayout;
+}
+
+pub(super) enum BoundaryPoint {
+    Vertex(IntPoint),
+    Edge(EdgePoint),
+    Inner,
+}
+
+// a point to insert after path[edge_index]
+#[derive(Debug, Clone, Copy)]
+pub(super) struct EdgePoint {
+    pub(super) shape_index: usize,
+    pub(super) path_index: usize,
+    pub(super) edge_index: usize,
+    pub(super) point: IntPoint,
+}
+
+impl ConstraintInference for IntShapes {
+    // Clips segments by shapes and splits them at mutual intersections.
+    // Points where segments cross the boundary are inserted into contours,
+    // all other points become Steiner points.
+    fn layout_constraints(&self, segments: &[[IntPoint; 2]]) -> ConstraintLayout {
+        let clip_rule = ClipRule {
+            invert: false,
+            boundary_included: false,
+        };
+        let paths = self.clip_lines(segments, FillRule::NonZero, clip_rule);
+
+        let mut steiner_points = Vec::new();
+        let mut edge_points = Vec::new();
+        let mut sub_segments = Vec::new();
+
+        for path in paths.iter() {
+            if path.len() < 2 {
+                continue;
+            }
+
+            let mut path = path.clone();
+            for p in path.iter_mut() {
+                if self.has_vertex(*p) {
+                    continue;
+                }
+                match self.nearest_boundary(*p) {
+                    BoundaryPoint::Vertex(v) => *p = v,
+                    BoundaryPoint::Edge(e) => edge_points.push(e),
+                    BoundaryPoint::Inner => steiner_points.push(*p),
+                }
+            }
+
+            for w in path.windows(2) {
+                if w[0] != w[1] {
+                    sub_segments.push([w[0], w[1]]);
+                }
+            }
+        }
+
+        steiner_points.sort_unstable();
+        steiner_points.dedup();
+
+        let shapes = if edge_points.is_empty() {
+            self.to_vec()
+        } else {
+            // a rounded point can shift an edge onto another vertex,
+            // so contours must be resolved again
+            self.insert_edge_points(edge_points)
+                .simplify(FillRule::NonZero, IntOverlayOptions::keep_all_points())
+        };
+
+        ConstraintLayout {
+            shapes,
+            steiner_points,
+            segments: sub_segments,
+        }
+    }
+}
+
+pub(super) trait EdgePointInsertion {
+    fn has_vertex(&self, p: IntPoint) -> bool;
+    fn nearest_boundary(&self, p: IntPoint) -> BoundaryPoint;
+    fn insert_edge_points(&self, edge_points: Vec<EdgePoint>) -> IntShapes;
+}
+
+impl EdgePointInsertion for [IntShape] {
+    #[inline]
+    fn has_vertex(&self, p: IntPoint) -> bool {
+        self.iter()
+            .any(|shape| shape.iter().any(|path| path.contains(&p)))
+    }
+
+    // a rounded crossing point is not further than 1 from the boundary
+    fn nearest_boundary(&self, p: IntPoint) -> BoundaryPoint {
+        let mut nearest = BoundaryPoint::Inner;
+        let mut min_dist = 1.0;
+        for (shape_index, shape) in self.iter().enumerate() {
+            for (path_index, path) in shape.iter().enumerate() {
+                let n = path.len();
+                for edge_index in 0..n {
+                    let a = path[edge_index];
+                    let b = path[(edge_index + 1) % n];
+
+                    let vertex_dist = p.sqr_distance(a) as f64;
+                    if vertex_dist <= min_dist {
+                        min_dist = vertex_dist;
+                        nearest = BoundaryPoint::Vertex(a);
+                    }
+
+                    if let Some(edge_dist) = sqr_distance_to_edge(p, a, b) {
+                        if edge_dist < min_dist {
+                            min_dist = edge_dist;
+                            nearest = BoundaryPoint::Edge(EdgePoint {
+                                shape_index,
+                                path_index,
+                                edge_index,
+                                point: p,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        nearest
+    }
+
+    fn insert_edge_points(&self, mut edge_points: Vec<EdgePoint>) -> IntShapes {
+        edge_points.sort_unstable_by(|e0, e1| {
+            (e0.shape_index, e0.path_index, e0.edge_index)
+                .cmp(&(e1.shape_index, e1.path_index, e1.edge_index))
+                .then_with(|| {
+                    let a = self[e0.shape_index][e0.path_index][e0.edge_index];
+                    let d0 = distance_along(a, e0.point);
+                    let d1 = distance_along(a, e1.point);
+                    d0.cmp(&d1)
+                })
+        });
+        edge_points.dedup_by_key(|e| e.point);
+
+        let mut shapes = Vec::with_capacity(self.len());
+        let mut iter = edge_points.iter().peekable();
+        for (shape_index, shape) in self.iter().enumerate() {
+            let mut new_shape = Vec::with_capacity(shape.len());
+            for (path_index, path) in shape.iter().enumerate() {
+                let mut new_path = IntPath::with_capacity(path.len());
+                for (edge_index, &a) in path.iter().enumerate() {
+                    new_path.push(a);
+                    while let Some(e) = iter.peek() {
+                        if e.shape_index != shape_index
+                            || e.path_index != path_index
+                            || e.edge_index != edge_index
+                        {
+                            break;
+                        }
+                        new_path.push(e.point);
+                        iter.next();
+                    }
+                }
+                new_shape.push(new_path);
+            }
+            shapes.push(new_shape);
+        }
+
+        shapes
+    }
+}
+
+// returns None if p is not projected on the inner part of the edge ab
+#[inline]
+fn sqr_distance_to_edge(p: IntPoint, a: IntPoint, b: IntPoint) -> Option<f64> {
+    let ab = b.subtract(a);
+    let ap = p.subtract(a);
+    let t = ab.dot_product(ap);
+    let len = ab.sqr_length();
+    if t <= 0 || t >= len {
+        return None;
+    }
+    let cross = ab.cross_product(ap) as f64;
+    Some(cross * cross / len as f64)
+}
+
+#[inline]
+fn distance_along(a: IntPoint, p: IntPoint) -> i64 {
+    a.sqr_distance(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::constraint::ConstraintInference;
+    use alloc::vec;
+    use i_overlay::i_float::int::point::IntPoint;
+
+    #[test]
+    fn test_0() {
+        let shapes = vec![vec![vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ]]];
+
+        let layout = shapes.layout_constraints(&[[IntPoint::new(-5, 5), IntPoint::new(15, 5)]]);
+
+        assert_eq!(layout.segments.len(), 1);
+        assert!(layout.steiner_points.is_empty());
+        assert_eq!(layout.shapes[0][0].len(), 6);
+    }
+
+    #[test]
+    fn test_1() {
+        let shapes = vec![vec![vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ]]];
+
+        let layout = shapes.layout_constraints(&[
+            [IntPoint::new(2, 2), IntPoint::new(8, 8)],
+            [IntPoint::new(2, 8), IntPoint::new(8, 2)],
+        ]);
+
+        assert_eq!(layout.segments.len(), 4);
+        assert_eq!(layout.steiner_points.len(), 5);
+        assert_eq!(layout.shapes[0][0].len(), 4);
+    }
+}
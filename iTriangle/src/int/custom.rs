@@ -2,6 +2,7 @@ use crate::int::solver::ShapesSolver;
 use crate::int::solver::{ContourSolver, ShapeSolver};
 use crate::int::triangulation::RawIntTriangulation;
 use crate::int::validation::Validation;
+use alloc::vec::Vec;
 use i_overlay::i_float::int::point::IntPoint;
 use i_overlay::i_shape::int::shape::{IntContour, IntShape, IntShapes};
 
@@ -24,6 +25,13 @@ pub trait IntCustomTriangulatable {
         points: &[IntPoint],
         validation: Validation,
     ) -> RawIntTriangulation;
+
+    /// Triangulates the shape(s), forcing constraint segments to become mesh edges and using the specified [`Validation`] settings.
+    fn custom_triangulate_with_constraints(
+        &self,
+        segments: &[[IntPoint; 2]],
+        validation: Validation,
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>);
 }
 
 impl IntCustomTriangulatable for IntContour {
@@ -40,6 +48,15 @@ impl IntCustomTriangulatable for IntContour {
     ) -> RawIntTriangulation {
         ContourSolver::triangulate_with_steiner_points(validation, self, points)
     }
+
+    #[inline]
+    fn custom_triangulate_with_constraints(
+        &self,
+        segments: &[[IntPoint; 2]],
+        validation: Validation,
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        ContourSolver::triangulate_with_constraints(validation, self, segments)
+    }
 }
 
 impl IntCustomTriangulatable for IntShape {
@@ -56,6 +73,15 @@ impl IntCustomTriangulatable for IntShape {
     ) -> RawIntTriangulation {
         ShapeSolver::triangulate_with_steiner_points(validation, self, points)
     }
+
+    #[inline]
+    fn custom_triangulate_with_constraints(
+        &self,
+        segments: &[[IntPoint; 2]],
+        validation: Validation,
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        ShapeSolver::triangulate_with_constraints(validation, self, segments)
+    }
 }
 
 impl IntCustomTriangulatable for IntShapes {
@@ -72,4 +98,13 @@ impl IntCustomTriangulatable for IntShapes {
     ) -> RawIntTriangulation {
         ShapesSolver::triangulate_with_steiner_points(validation, self, points)
     }
+
+    #[inline]
+    fn custom_triangulate_with_constraints(
+        &self,
+        segments: &[[IntPoint; 2]],
+        validation: Validation,
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        ShapesSolver::triangulate_with_constraints(validation, self, segments)
+    }
 }
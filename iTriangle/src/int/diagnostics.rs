@@ -0,0 +1,405 @@
+use crate::int::provenance::{ProvenanceMap, VertexOrigin};
+use alloc::vec::Vec;
+use i_overlay::i_float::adapter::FloatPointAdapter;
+use i_overlay::i_float::float::compatible::FloatPointCompatible;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_shape::flat::buffer::FlatContoursBuffer;
+use i_overlay::i_shape::int::shape::{IntContour, IntShape};
+
+/// A change made by the validation pass.
+///
+/// `shape` and `contour` refer to the input: `shapes[shape][contour]`. A flat buffer
+/// has no shape grouping, there `shape` is always `0` and `contour` counts the paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue<P = IntPoint> {
+    /// The contour was removed entirely, e.g. it had no area or the fill rule
+    /// left nothing of it.
+    DroppedContour { shape: usize, contour: usize },
+    /// `count` points of the contour were removed, e.g. collinear or repeated points.
+    RemovedPoints {
+        shape: usize,
+        contour: usize,
+        count: usize,
+    },
+    /// The contour was reversed to match the orientation of its role,
+    /// counter-clockwise for outer contours and clockwise for holes.
+    ReversedContour { shape: usize, contour: usize },
+    /// Edges cross at `point`, the contours were split there.
+    SelfIntersection { point: P },
+    /// The fill rule merged these input shapes into one output shape.
+    MergedShapes { shapes: Vec<usize> },
+}
+
+impl ValidationIssue<IntPoint> {
+    /// Maps the issue location into float space.
+    #[inline]
+    pub fn to_float<P: FloatPointCompatible>(
+        &self,
+        adapter: &FloatPointAdapter<P>,
+    ) -> ValidationIssue<P> {
+        match self {
+            &Self::DroppedContour { shape, contour } => {
+                ValidationIssue::DroppedContour { shape, contour }
+            }
+            &Self::RemovedPoints {
+                shape,
+                contour,
+                count,
+            } => ValidationIssue::RemovedPoints {
+                shape,
+                contour,
+                count,
+            },
+            &Self::ReversedContour { shape, contour } => {
+                ValidationIssue::ReversedContour { shape, contour }
+            }
+            Self::SelfIntersection { point } => ValidationIssue::SelfIntersection {
+                point: adapter.int_to_float(point),
+            },
+            Self::MergedShapes { shapes } => ValidationIssue::MergedShapes {
+                shapes: shapes.clone(),
+            },
+        }
+    }
+}
+
+struct ContourInfo {
+    shape: usize,
+    contour: usize,
+    // the first point in `visited`
+    offset: usize,
+    len: usize,
+    forward: usize,
+    backward: usize,
+}
+
+// compares the validated contours with the input, every output point is matched with
+// the input point whose edges it follows
+#[derive(Default)]
+pub(crate) struct ValidationDiagnostics {
+    contours: Vec<ContourInfo>,
+    shape_offsets: Vec<usize>,
+    visited: Vec<bool>,
+    origins: Vec<VertexOrigin>,
+    intersections: Vec<IntPoint>,
+    sources: Vec<(usize, usize)>,
+}
+
+impl ValidationDiagnostics {
+    pub(crate) fn set_with_contour(&mut self, contour: &[IntPoint]) {
+        self.clear();
+        self.add_shape([contour.len()].into_iter());
+    }
+
+    pub(crate) fn set_with_shape(&mut self, shape: &[IntContour]) {
+        self.clear();
+        self.add_shape(shape.iter().map(|c| c.len()));
+    }
+
+    pub(crate) fn set_with_shapes(&mut self, shapes: &[IntShape]) {
+        self.clear();
+        for shape in shapes.iter() {
+            self.add_shape(shape.iter().map(|c| c.len()));
+        }
+    }
+
+    pub(crate) fn set_with_flat(&mut self, flat: &FlatContoursBuffer) {
+        self.clear();
+        self.add_shape(flat.ranges.iter().map(|r| r.len()));
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.contours.clear();
+        self.shape_offsets.clear();
+        self.visited.clear();
+    }
+
+    fn add_shape<I: Iterator<Item = usize>>(&mut self, lengths: I) {
+        let shape = self.shape_offsets.len();
+        self.shape_offsets.push(self.contours.len());
+        for (contour, len) in lengths.enumerate() {
+            self.contours.push(ContourInfo {
+                shape,
+                contour,
+                offset: self.visited.len(),
+                len,
+                forward: 0,
+                backward: 0,
+            });
+            self.visited.resize(self.visited.len() + len, false);
+        }
+    }
+
+    /// Appends the issues found in the validated paths.
+    ///
+    /// `paths` yields every output contour together with its output shape index.
+    pub(crate) fn collect<'a, I>(
+        &mut self,
+        map: &mut ProvenanceMap,
+        paths: I,
+        issues: &mut Vec<ValidationIssue>,
+    ) where
+        I: Iterator<Item = (usize, &'a [IntPoint])>,
+    {
+        self.visited.fill(false);
+        for info in self.contours.iter_mut() {
+            info.forward = 0;
+            info.backward = 0;
+        }
+        self.intersections.clear();
+        self.sources.clear();
+
+        for (output_shape, path) in paths {
+            map.resolve_path_into(path, &mut self.origins);
+            self.visit_path(output_shape, path);
+        }
+
+        for info in self.contours.iter() {
+            let found = self.visited[info.offset..info.offset + info.len]
+                .iter()
+                .filter(|&&v| v)
+                .count();
+
+            if found == 0 {
+                issues.push(ValidationIssue::DroppedContour {
+                    shape: info.shape,
+                    contour: info.contour,
+                });
+                continue;
+            }
+
+            if found < info.len {
+                issues.push(ValidationIssue::RemovedPoints {
+                    shape: info.shape,
+                    contour: info.contour,
+                    count: info.len - found,
+                });
+            }
+
+            if info.backward > info.forward {
+                issues.push(ValidationIssue::ReversedContour {
+                    shape: info.shape,
+                    contour: info.contour,
+                });
+            }
+        }
+
+        self.intersections.sort_unstable();
+        self.intersections.dedup();
+        issues.extend(
+            self.intersections
+                .iter()
+                .map(|&point| ValidationIssue::SelfIntersection { point }),
+        );
+
+        self.sources.sort_unstable();
+        self.sources.dedup();
+        for group in self.sources.chunk_by(|a, b| a.0 == b.0) {
+            if group.len() > 1 {
+                issues.push(ValidationIssue::MergedShapes {
+                    shapes: group.iter().map(|s| s.1).collect(),
+                });
+            }
+        }
+    }
+
+    fn visit_path(&mut self, output_shape: usize, path: &[IntPoint]) {
+        let n = self.origins.len();
+        for (i, origin) in self.origins.iter().enumerate() {
+            let (shape, contour, vertex) = match *origin {
+                VertexOrigin::Input {
+                    shape,
+                    contour,
+                    vertex,
+                } => (shape, contour, vertex),
+                VertexOrigin::Intersection => {
+                    self.intersections.push(path[i]);
+                    continue;
+                }
+                _ => continue,
+            };
+
+            self.sources.push((output_shape, shape));
+            let info = &mut self.contours[self.shape_offsets[shape] + contour];
+            self.visited[info.offset + vertex] = true;
+
+            // the direction of a step between two points of the same input contour
+            let VertexOrigin::Input {
+                shape: next_shape,
+                contour: next_contour,
+                vertex: next_vertex,
+            } = self.origins[(i + 1) % n]
+            else {
+                continue;
+            };
+            if next_shape != shape || next_contour != contour {
+                continue;
+            }
+            let step = (next_vertex + info.len - vertex) % info.len;
+            if step == 0 {
+                continue;
+            }
+            if 2 * step <= info.len {
+                info.forward += 1;
+            } else {
+                info.backward += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::diagnostics::ValidationIssue;
+    use crate::int::triangulator::IntTriangulator;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::path::IntPath;
+    use rand::RngExt;
+
+    fn square(x: i32, y: i32, size: i32) -> IntPath {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x + size, y),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x, y + size),
+        ]
+    }
+
+    #[test]
+    fn test_0() {
+        let mut triangulator = IntTriangulator::<u16>::default();
+        triangulator.diagnostics = true;
+
+        triangulator.triangulate_contour(&square(0, 0, 10));
+        assert!(triangulator.validation_issues().is_empty());
+
+        // clockwise with a collinear point
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(0, 10),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 5),
+            IntPoint::new(10, 0),
+        ];
+        triangulator.triangulate_contour(&contour);
+        assert_eq!(
+            triangulator.validation_issues(),
+            &[
+                ValidationIssue::RemovedPoints {
+                    shape: 0,
+                    contour: 0,
+                    count: 1
+                },
+                ValidationIssue::ReversedContour {
+                    shape: 0,
+                    contour: 0
+                },
+            ]
+        );
+
+        triangulator.diagnostics = false;
+        triangulator.triangulate_contour(&contour);
+        assert!(triangulator.validation_issues().is_empty());
+    }
+
+    #[test]
+    fn test_1() {
+        // a bow tie
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 0),
+            IntPoint::new(0, 10),
+        ];
+
+        let mut triangulator = IntTriangulator::<u16>::default();
+        triangulator.diagnostics = true;
+        triangulator.triangulate_contour(&contour);
+
+        let issues = triangulator.validation_issues();
+        assert!(issues.contains(&ValidationIssue::SelfIntersection {
+            point: IntPoint::new(5, 5)
+        }));
+        assert!(!issues
+            .iter()
+            .any(|i| matches!(i, ValidationIssue::DroppedContour { .. })));
+    }
+
+    #[test]
+    fn test_2() {
+        let degenerate = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(20, 0),
+        ];
+        let shapes = vec![
+            vec![square(0, 0, 10)],
+            vec![square(20, 0, 10)],
+            vec![square(5, 5, 10)],
+            vec![degenerate],
+        ];
+
+        let mut triangulator = IntTriangulator::<u16>::default();
+        triangulator.diagnostics = true;
+        triangulator.triangulate_shapes(&shapes);
+
+        let issues = triangulator.validation_issues();
+        assert!(issues.contains(&ValidationIssue::DroppedContour {
+            shape: 3,
+            contour: 0
+        }));
+        assert!(issues.contains(&ValidationIssue::MergedShapes { shapes: vec![0, 2] }));
+        assert!(issues.contains(&ValidationIssue::SelfIntersection {
+            point: IntPoint::new(10, 5)
+        }));
+        assert!(!issues.iter().any(|i| matches!(
+            i,
+            ValidationIssue::DroppedContour { shape: 1, .. }
+                | ValidationIssue::RemovedPoints { shape: 1, .. }
+        )));
+    }
+
+    #[test]
+    fn test_random_0() {
+        let mut triangulator = IntTriangulator::<u32>::default();
+        triangulator.diagnostics = true;
+        for _ in 0..5_000 {
+            let shapes = vec![vec![random(20, 8), random(10, 4)], vec![random(12, 6)]];
+            let triangulation = triangulator.triangulate_shapes(&shapes);
+
+            for issue in triangulator.validation_issues().iter() {
+                match issue {
+                    &ValidationIssue::DroppedContour { shape, contour }
+                    | &ValidationIssue::ReversedContour { shape, contour } => {
+                        assert!(contour < shapes[shape].len())
+                    }
+                    &ValidationIssue::RemovedPoints {
+                        shape,
+                        contour,
+                        count,
+                    } => assert!(count < shapes[shape][contour].len()),
+                    ValidationIssue::SelfIntersection { point } => {
+                        assert!(triangulation.points.contains(point))
+                    }
+                    ValidationIssue::MergedShapes { shapes } => assert_eq!(shapes, &[0, 1]),
+                }
+            }
+        }
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
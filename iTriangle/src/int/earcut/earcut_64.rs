@@ -5,12 +5,19 @@ use crate::int::earcut::util::{ABCExcludeResult, Abc, AB};
 use crate::int::meta::TrianglesCount;
 use crate::int::triangulation::{IndexType, IntTriangulation, RawIntTriangulation};
 use core::cmp::Ordering;
+use core::ops::{BitAnd, BitAndAssign, BitOr, Not};
 use i_overlay::i_float::int::point::IntPoint;
 use i_overlay::i_float::int::rect::IntRect;
 use i_overlay::i_shape::util::reserve::Reserve;
 
 pub(super) trait EarcutStore {
-    fn collect_triangles(&mut self, contour: &[IntPoint], start: usize, bits: u64, count: u32);
+    fn collect_triangles<B: Bit>(
+        &mut self,
+        contour: &[IntPoint],
+        start: usize,
+        bits: B,
+        count: u32,
+    );
 }
 
 pub trait Earcut64 {
@@ -62,18 +69,22 @@ enum ConvexSearchResult {
     None,
 }
 
-struct EarcutSolver<'a, S> {
+// the solver over a single word, up to 64 points
+type EarcutSolver<'a, S> = EarcutBitSolver<'a, S, u64>;
+
+/// Ear clipping over a bit set of available points, the bit set limits the contour size.
+pub(super) struct EarcutBitSolver<'a, S, B> {
     store: S,
     contour: &'a [IntPoint],
-    available: u64,
+    available: B,
 }
 
-impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
-    fn new(contour: &'a [IntPoint], store: S) -> Self {
+impl<'a, S: EarcutStore, B: Bit> EarcutBitSolver<'a, S, B> {
+    pub(super) fn new(contour: &'a [IntPoint], store: S) -> Self {
         Self {
             store,
             contour,
-            available: u64::ones_start_to_index(contour.len()),
+            available: B::ones_start_to_index(contour.len()),
         }
     }
 
@@ -82,9 +93,9 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
         unsafe { self.contour.get_unchecked(index) }
     }
 
-    fn triangulate(&mut self) {
+    pub(super) fn triangulate(&mut self) {
         let mut i = 0;
-        let mut cnt = self.available.count_ones();
+        let mut cnt = self.available.ones_count();
         while cnt >= 3 {
             i = self.scroll_to_first_ccw_angle(i, cnt);
             match self.find_convex_part(i) {
@@ -101,28 +112,28 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
                 }
             }
             i = self.available.next_wrapped_index(i);
-            cnt = self.available.count_ones();
+            cnt = self.available.ones_count();
         }
     }
 
     #[inline(always)]
     fn collect_ear_triangles(&mut self, start: usize, end: usize, same_point: bool) {
         // ear indices
-        let bits = self.available & u64::ones_in_range_include(start, end);
+        let bits = self.available & B::ones_in_range_include(start, end);
 
         // indices to remove
         let mut invert = !bits;
         // we keep end points
-        invert |= 1 << start;
-        invert |= 1 << end;
+        invert.set(start);
+        invert.set(end);
 
-        let mut n = bits.count_ones() - 2;
+        let mut n = bits.ones_count() - 2;
 
         // Handles degenerate case where start and end share the same position,
         // like self-touches contours (e.g. sand clock).
         if same_point {
             // remove same point
-            invert &= !(1 << start);
+            invert.unset(start);
             // no need last zero triangle
             n -= 1;
         }
@@ -135,7 +146,7 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
     fn collect_last_ear_triangles(&mut self, start: usize) {
         let bits = self.available;
         self.store
-            .collect_triangles(self.contour, start, bits, bits.count_ones() - 2);
+            .collect_triangles(self.contour, start, bits, bits.ones_count() - 2);
     }
 
     #[inline(always)]
@@ -224,7 +235,7 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
 
     #[inline]
     fn validate_and_shrink_ear(&self, start: usize, end: usize, same_point: bool) -> Option<usize> {
-        let range_mask = u64::ones_in_range_include(start, end);
+        let range_mask = B::ones_in_range_include(start, end);
         let mut ear_indices = self.available & range_mask;
 
         let i0 = start;
@@ -239,14 +250,14 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
         let e = if same_point {
             // remove same point
             let prev = ear_indices.prev_wrapped_index(end);
-            ear_indices &= !(1 << end);
+            ear_indices.unset(end);
             *self.point(prev)
         } else {
             *self.point(end)
         };
 
         // fast test for single triangle
-        if ear_indices.count_ones() == 3 {
+        if ear_indices.ones_count() == 3 {
             return if self.triangle_contains(a, b, c, ear_indices, same_point) {
                 None
             } else {
@@ -293,7 +304,7 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
         a: IntPoint,
         b: IntPoint,
         c: IntPoint,
-        ear_indices: u64,
+        ear_indices: B,
         same_point: bool,
     ) -> bool {
         let abc = Abc::new(a, b, c);
@@ -322,7 +333,7 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
         b: IntPoint,
         c: IntPoint,
         e: IntPoint,
-        ear_indices: u64,
+        ear_indices: B,
         same_point: bool,
     ) -> Option<ClockOrderHeap> {
         // filter by bounding box and first triangle
@@ -338,12 +349,9 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
         // last edge
         let ee = a.subtract(e);
 
-        let mut bits = self.available & !ear_indices;
+        let bits = self.available & !ear_indices;
         let mut heap = ClockOrderHeap::with_center(a);
-        while bits != 0 {
-            let index = bits.trailing_zeros() as usize;
-            bits &= !(1 << index);
-
+        for index in BitIter(bits) {
             let p = *self.point(index);
 
             if !rect.contains(p) {
@@ -401,10 +409,10 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
     }
 
     #[inline(always)]
-    fn bounding_box(&self, indices: u64) -> IntRect {
+    fn bounding_box(&self, indices: B) -> IntRect {
         let mut bits = indices;
-        let i0 = bits.trailing_zeros() as usize;
-        bits &= !(1 << i0);
+        let i0 = bits.first_index();
+        bits.unset(i0);
 
         let mut rect = IntRect::with_point(*self.point(i0));
         for i in BitIter(bits) {
@@ -415,15 +423,15 @@ impl<'a, S: EarcutStore> EarcutSolver<'a, S> {
     }
 }
 
-struct Ear {
+struct Ear<B = u64> {
     a: IntPoint,
     active_index: usize,
     active_point: IntPoint,
     start: usize,
-    indices: u64,
+    indices: B,
 }
 
-impl Ear {
+impl<B: Bit> Ear<B> {
     #[inline(always)]
     fn cut(&mut self, p: IntPoint, contour: &[IntPoint]) -> bool {
         let mut i = self.active_index;
@@ -460,30 +468,37 @@ impl Ear {
     }
 }
 
-struct BitIter(u64);
+struct BitIter<B>(B);
 
-impl Iterator for BitIter {
+impl<B: Bit> Iterator for BitIter<B> {
     type Item = usize;
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.0 == 0 {
+        if self.0.is_zero() {
             None
         } else {
-            let i = self.0.trailing_zeros() as usize;
-            self.0 &= !(1 << i);
+            let i = self.0.first_index();
+            self.0.unset(i);
             Some(i)
         }
     }
 }
 
-pub(super) trait Bit {
+pub(super) trait Bit:
+    Copy + BitAnd<Output = Self> + BitOr<Output = Self> + Not<Output = Self> + BitAndAssign
+{
     fn ones_start_to_index(count: usize) -> Self;
     fn ones_index_to_end(count: usize) -> Self;
     fn ones_in_sorted_closed_range(start: usize, end: usize) -> Self;
     fn ones_in_range_include(start: usize, end: usize) -> Self;
     fn next_wrapped_index(&self, after: usize) -> usize;
     fn prev_wrapped_index(&self, before: usize) -> usize;
+    fn ones_count(&self) -> u32;
+    fn is_zero(&self) -> bool;
+    fn first_index(&self) -> usize;
+    fn set(&mut self, index: usize);
+    fn unset(&mut self, index: usize);
 }
 
 impl Bit for u64 {
@@ -543,6 +558,31 @@ impl Bit for u64 {
             63 - self.leading_zeros() as usize
         }
     }
+
+    #[inline(always)]
+    fn ones_count(&self) -> u32 {
+        self.count_ones()
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    #[inline(always)]
+    fn first_index(&self) -> usize {
+        self.trailing_zeros() as usize
+    }
+
+    #[inline(always)]
+    fn set(&mut self, index: usize) {
+        *self |= 1 << index;
+    }
+
+    #[inline(always)]
+    fn unset(&mut self, index: usize) {
+        *self &= !(1 << index);
+    }
 }
 
 #[cfg(test)]
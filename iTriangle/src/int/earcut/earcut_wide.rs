@@ -0,0 +1,281 @@
+use crate::int::earcut::earcut_64::{Earcut64, EarcutBitSolver};
+use crate::int::earcut::flat::FlatEarcutStore;
+use crate::int::earcut::net::NetEarcutStore;
+use crate::int::earcut::wide::WideBits;
+use crate::int::meta::TrianglesCount;
+use crate::int::triangulation::{IndexType, IntTriangulation, RawIntTriangulation};
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_shape::util::reserve::Reserve;
+
+/// The largest contour the earcut solvers can triangulate.
+pub const EARCUT_MAX_POINTS: usize = 256;
+
+/// Ear clipping triangulation for contours with up to 128 points.
+///
+/// Works like [`Earcut64`] over a two-word bit set.
+pub trait Earcut128 {
+    fn earcut128_flat_triangulate_into<I: IndexType>(
+        &self,
+        triangulation: &mut IntTriangulation<I>,
+    );
+
+    fn earcut128_net_triangulate_into(&self, triangulation: &mut RawIntTriangulation);
+}
+
+/// Ear clipping triangulation for contours with up to 256 points.
+///
+/// Works like [`Earcut64`] over a four-word bit set.
+pub trait Earcut256 {
+    fn earcut256_flat_triangulate_into<I: IndexType>(
+        &self,
+        triangulation: &mut IntTriangulation<I>,
+    );
+
+    fn earcut256_net_triangulate_into(&self, triangulation: &mut RawIntTriangulation);
+}
+
+impl Earcut128 for [IntPoint] {
+    #[inline]
+    fn earcut128_flat_triangulate_into<I: IndexType>(
+        &self,
+        triangulation: &mut IntTriangulation<I>,
+    ) {
+        debug_assert!(self.len() <= 128);
+        self.wide_flat_triangulate_into::<I, 2>(triangulation);
+    }
+
+    #[inline]
+    fn earcut128_net_triangulate_into(&self, triangulation: &mut RawIntTriangulation) {
+        debug_assert!(self.len() <= 128);
+        self.wide_net_triangulate_into::<2>(triangulation);
+    }
+}
+
+impl Earcut256 for [IntPoint] {
+    #[inline]
+    fn earcut256_flat_triangulate_into<I: IndexType>(
+        &self,
+        triangulation: &mut IntTriangulation<I>,
+    ) {
+        debug_assert!(self.len() <= 256);
+        self.wide_flat_triangulate_into::<I, 4>(triangulation);
+    }
+
+    #[inline]
+    fn earcut256_net_triangulate_into(&self, triangulation: &mut RawIntTriangulation) {
+        debug_assert!(self.len() <= 256);
+        self.wide_net_triangulate_into::<4>(triangulation);
+    }
+}
+
+trait WideEarcut {
+    fn wide_flat_triangulate_into<I: IndexType, const N: usize>(
+        &self,
+        triangulation: &mut IntTriangulation<I>,
+    );
+
+    fn wide_net_triangulate_into<const N: usize>(&self, triangulation: &mut RawIntTriangulation);
+}
+
+impl WideEarcut for [IntPoint] {
+    fn wide_flat_triangulate_into<I: IndexType, const N: usize>(
+        &self,
+        triangulation: &mut IntTriangulation<I>,
+    ) {
+        triangulation
+            .indices
+            .reserve_capacity(self.triangles_count(0));
+        triangulation.indices.clear();
+
+        EarcutBitSolver::<_, WideBits<N>>::new(self, FlatEarcutStore::new(triangulation))
+            .triangulate();
+
+        triangulation.points.clear();
+        triangulation.points.extend_from_slice(self);
+    }
+
+    fn wide_net_triangulate_into<const N: usize>(&self, triangulation: &mut RawIntTriangulation) {
+        triangulation
+            .triangles
+            .reserve_capacity(self.triangles_count(0));
+        triangulation.triangles.clear();
+
+        EarcutBitSolver::<_, WideBits<N>>::new(
+            self,
+            NetEarcutStore::new(self.len(), triangulation),
+        )
+        .triangulate();
+
+        triangulation.points.clear();
+        triangulation.points.extend_from_slice(self);
+    }
+}
+
+// picks the narrowest earcut solver that fits the contour
+pub(crate) trait AdaptiveEarcut {
+    fn adaptive_earcut_flat_into<I: IndexType>(&self, triangulation: &mut IntTriangulation<I>);
+
+    fn adaptive_earcut_net_into(&self, triangulation: &mut RawIntTriangulation);
+}
+
+impl AdaptiveEarcut for [IntPoint] {
+    #[inline]
+    fn adaptive_earcut_flat_into<I: IndexType>(&self, triangulation: &mut IntTriangulation<I>) {
+        match self.len() {
+            0..=64 => self.earcut_flat_triangulate_into(triangulation),
+            65..=128 => self.earcut128_flat_triangulate_into(triangulation),
+            _ => self.earcut256_flat_triangulate_into(triangulation),
+        }
+    }
+
+    #[inline]
+    fn adaptive_earcut_net_into(&self, triangulation: &mut RawIntTriangulation) {
+        match self.len() {
+            0..=64 => self.earcut_net_triangulate_into(triangulation),
+            65..=128 => self.earcut128_net_triangulate_into(triangulation),
+            _ => self.earcut256_net_triangulate_into(triangulation),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::earcut::earcut_64::Earcut64;
+    use crate::int::earcut::earcut_wide::{AdaptiveEarcut, Earcut128, Earcut256};
+    use crate::int::triangulation::{IntTriangulation, RawIntTriangulation};
+    use crate::int::triangulator::IntTriangulator;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::area::Area;
+    use i_overlay::i_shape::int::path::IntPath;
+    use i_overlay::i_shape::int::shape::IntContour;
+    use rand::RngExt;
+
+    // a comb with `n` teeth, `2 * n + 3` points
+    fn comb(n: usize) -> IntContour {
+        let mut contour = Vec::with_capacity(2 * n + 3);
+        for i in 0..n as i32 {
+            contour.push(IntPoint::new(10 * i, 0));
+            contour.push(IntPoint::new(10 * i + 5, 10));
+        }
+        contour.push(IntPoint::new(10 * n as i32, 0));
+        contour.push(IntPoint::new(10 * n as i32, -10));
+        contour.push(IntPoint::new(0, -10));
+
+        contour
+            .simplify(FillRule::NonZero, IntOverlayOptions::keep_output_points())
+            .swap_remove(0)
+            .swap_remove(0)
+    }
+
+    #[test]
+    fn test_0() {
+        for n in [20, 31, 40, 62, 63, 90, 126] {
+            let contour = comb(n);
+            assert_eq!(contour.len(), 2 * n + 3);
+            if contour.len() <= 128 {
+                single_test_128(&contour);
+            }
+            single_test_256(&contour);
+        }
+    }
+
+    #[test]
+    fn test_1() {
+        // a small contour gives the same result with every solver
+        let contour = comb(16);
+
+        let mut t64 = IntTriangulation::<u16>::default();
+        contour.earcut_flat_triangulate_into(&mut t64);
+
+        let mut t128 = IntTriangulation::<u16>::default();
+        contour.earcut128_flat_triangulate_into(&mut t128);
+
+        let mut t256 = IntTriangulation::<u16>::default();
+        contour.earcut256_flat_triangulate_into(&mut t256);
+
+        assert_eq!(t64.indices, t128.indices);
+        assert_eq!(t64.indices, t256.indices);
+    }
+
+    #[test]
+    fn test_2() {
+        // the triangulator picks the solver by size
+        let mut triangulator = IntTriangulator::<u16>::default();
+        triangulator.earcut = true;
+        for n in [30, 60, 120] {
+            let contour = comb(n);
+            let triangulation = triangulator.triangulate_contour(&contour);
+            triangulation.validate(contour.area_two());
+            assert_eq!(triangulation.indices.len() / 3, contour.len() - 2);
+
+            let mut expected = IntTriangulation::<u16>::default();
+            contour.adaptive_earcut_flat_into(&mut expected);
+            assert_eq!(triangulation.indices, expected.indices);
+        }
+    }
+
+    #[test]
+    fn test_random_0() {
+        for _ in 0..2_000 {
+            if let Some(first) = random(32, 200)
+                .simplify(FillRule::NonZero, IntOverlayOptions::keep_output_points())
+                .first()
+            {
+                if let Some(contour) = first.first() {
+                    let n = contour.len();
+                    if (3..=128).contains(&n) {
+                        single_test_128(contour);
+                    }
+                    if (3..=256).contains(&n) {
+                        single_test_256(contour);
+                    }
+                }
+            }
+        }
+    }
+
+    fn single_test_128(contour: &IntContour) {
+        let mut flat = IntTriangulation::<u16>::default();
+        contour.earcut128_flat_triangulate_into(&mut flat);
+
+        flat.validate(contour.area_two());
+        assert!(flat.indices.len() / 3 <= contour.len() - 2);
+
+        let mut net = RawIntTriangulation::default();
+        contour.earcut128_net_triangulate_into(&mut net);
+
+        net.validate();
+        assert_eq!(net.area_two(), contour.area_two());
+    }
+
+    fn single_test_256(contour: &IntContour) {
+        let mut flat = IntTriangulation::<u16>::default();
+        contour.earcut256_flat_triangulate_into(&mut flat);
+
+        flat.validate(contour.area_two());
+        assert!(flat.indices.len() / 3 <= contour.len() - 2);
+
+        let mut net = RawIntTriangulation::default();
+        contour.earcut256_net_triangulate_into(&mut net);
+
+        net.validate();
+        assert_eq!(net.area_two(), contour.area_two());
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
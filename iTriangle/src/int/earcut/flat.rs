@@ -15,7 +15,7 @@ impl<'a, I: IndexType> FlatEarcutStore<'a, I> {
 
 impl<I: IndexType> EarcutStore for FlatEarcutStore<'_, I> {
     #[inline]
-    fn collect_triangles(&mut self, _: &[IntPoint], start: usize, bits: u64, count: u32) {
+    fn collect_triangles<B: Bit>(&mut self, _: &[IntPoint], start: usize, bits: B, count: u32) {
         let mut i = start;
         let a = unsafe { I::try_from(i).unwrap_unchecked() };
         i = bits.next_wrapped_index(i);
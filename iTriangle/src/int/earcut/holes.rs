@@ -0,0 +1,500 @@
+use crate::int::earcut::earcut_wide::{AdaptiveEarcut, EARCUT_MAX_POINTS};
+use crate::int::triangulation::{IndexType, IntTriangulation, RawIntTriangulation};
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_shape::int::shape::IntContour;
+
+/// Ear clipping for a shape with holes.
+///
+/// Every hole is cut into the outer contour with a zero-width bridge, the resulting
+/// single contour is triangulated by the earcut solver that fits its size and the
+/// duplicated bridge points are merged back.
+pub(crate) trait EarcutShape {
+    /// The points count of the bridged contour.
+    fn bridged_count(&self) -> usize;
+
+    /// Returns `false` if the contours touch each other or a hole could not be bridged,
+    /// the triangulation is left in an unspecified state then.
+    fn earcut_shape_flat_into<I: IndexType>(&self, triangulation: &mut IntTriangulation<I>)
+        -> bool;
+
+    /// Returns `false` if the contours touch each other or a hole could not be bridged,
+    /// the triangulation is left in an unspecified state then.
+    fn earcut_shape_net_into(&self, triangulation: &mut RawIntTriangulation) -> bool;
+}
+
+impl EarcutShape for [IntContour] {
+    #[inline]
+    fn bridged_count(&self) -> usize {
+        let points: usize = self.iter().map(|c| c.len()).sum();
+        points + 2 * self.len().saturating_sub(1)
+    }
+
+    fn earcut_shape_flat_into<I: IndexType>(
+        &self,
+        triangulation: &mut IntTriangulation<I>,
+    ) -> bool {
+        let Some(bridged) = BridgedContour::new(self) else {
+            return false;
+        };
+
+        bridged.points.adaptive_earcut_flat_into(triangulation);
+
+        for index in triangulation.indices.iter_mut() {
+            let source = bridged.sources[index.into_usize()];
+            *index = unsafe { I::try_from(source).unwrap_unchecked() };
+        }
+        bridged.fill_points(&mut triangulation.points);
+
+        true
+    }
+
+    fn earcut_shape_net_into(&self, triangulation: &mut RawIntTriangulation) -> bool {
+        let Some(bridged) = BridgedContour::new(self) else {
+            return false;
+        };
+
+        bridged.points.adaptive_earcut_net_into(triangulation);
+
+        // the bridge edges are contour edges for the solver, both sides of a bridge
+        // become neighbors after the merge
+        let mut bridge_sides = Vec::new();
+        for (t, triangle) in triangulation.triangles.iter_mut().enumerate() {
+            for vertex in triangle.vertices.iter_mut() {
+                vertex.index = bridged.sources[vertex.index];
+            }
+            for side in 0..3 {
+                if triangle.neighbors[side] != usize::MAX {
+                    continue;
+                }
+                let a = triangle.vertices[(side + 1) % 3].index;
+                let b = triangle.vertices[(side + 2) % 3].index;
+                let edge = if a < b { (a, b) } else { (b, a) };
+                if bridged.bridges.contains(&edge) {
+                    bridge_sides.push((edge, t, side));
+                }
+            }
+        }
+
+        bridge_sides.sort_unstable();
+        for pair in bridge_sides.windows(2) {
+            let (e0, t0, s0) = pair[0];
+            let (e1, t1, s1) = pair[1];
+            if e0 == e1 {
+                triangulation.triangles[t0].neighbors[s0] = t1;
+                triangulation.triangles[t1].neighbors[s1] = t0;
+            }
+        }
+
+        bridged.fill_points(&mut triangulation.points);
+
+        true
+    }
+}
+
+// a single contour made from a shape, `sources` maps every point to its index in the
+// flattened shape
+struct BridgedContour<'a> {
+    shape: &'a [IntContour],
+    points: Vec<IntPoint>,
+    sources: Vec<usize>,
+    // sorted index pairs of the bridge edges in the flattened shape
+    bridges: Vec<(usize, usize)>,
+}
+
+impl<'a> BridgedContour<'a> {
+    fn new(shape: &'a [IntContour]) -> Option<Self> {
+        let count = shape.bridged_count();
+        if count > EARCUT_MAX_POINTS || shape.has_touches() {
+            return None;
+        }
+
+        let mut bridged = Self {
+            shape,
+            points: Vec::with_capacity(count),
+            sources: Vec::with_capacity(count),
+            bridges: Vec::with_capacity(shape.len() - 1),
+        };
+
+        bridged.points.extend_from_slice(&shape[0]);
+        bridged.sources.extend(0..shape[0].len());
+
+        // (hole, offset in the flattened shape, leftmost point index)
+        let mut holes = Vec::with_capacity(shape.len() - 1);
+        let mut offset = shape[0].len();
+        for (i, hole) in shape.iter().enumerate().skip(1) {
+            holes.push((i, offset, hole.leftmost()));
+            offset += hole.len();
+        }
+
+        // a hole sees the contour to its left once all holes on the left are bridged
+        holes.sort_unstable_by_key(|&(i, _, m)| shape[i][m]);
+
+        for (n, &(i, offset, m)) in holes.iter().enumerate() {
+            let remaining = holes[n + 1..].iter().map(|&(j, _, _)| shape[j].as_slice());
+            let v = bridged.find_bridge(&shape[i], m, remaining)?;
+            bridged.insert_hole(v, &shape[i], offset, m);
+        }
+
+        Some(bridged)
+    }
+
+    // finds the nearest point of the contour visible from the hole point `m`
+    fn find_bridge<'b, R>(&self, hole: &[IntPoint], m: usize, remaining: R) -> Option<usize>
+    where
+        R: Iterator<Item = &'b [IntPoint]> + Clone,
+    {
+        let pm = hole[m];
+        let n = hole.len();
+        let m_prev = hole[(m + n - 1) % n];
+        let m_next = hole[(m + 1) % n];
+
+        let mut candidates: Vec<(i64, usize)> = self
+            .points
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| **p != pm)
+            .map(|(v, p)| (p.sqr_distance(pm), v))
+            .collect();
+        candidates.sort_unstable();
+
+        let count = self.points.len();
+        candidates.into_iter().map(|(_, v)| v).find(|&v| {
+            let pv = self.points[v];
+            let v_prev = self.points[(v + count - 1) % count];
+            let v_next = self.points[(v + 1) % count];
+
+            is_inside_cone(v_prev, pv, v_next, pm)
+                && is_inside_cone(m_prev, pm, m_next, pv)
+                && !crosses_path(pm, pv, &self.points)
+                && !crosses_path(pm, pv, hole)
+                && !remaining.clone().any(|path| crosses_path(pm, pv, path))
+        })
+    }
+
+    fn insert_hole(&mut self, v: usize, hole: &[IntPoint], offset: usize, m: usize) {
+        let n = hole.len();
+        let pv = self.points[v];
+        let sv = self.sources[v];
+
+        let points = (0..=n)
+            .map(|k| hole[(m + k) % n])
+            .chain(core::iter::once(pv));
+        let sources = (0..=n)
+            .map(|k| offset + (m + k) % n)
+            .chain(core::iter::once(sv));
+
+        self.points.splice(v + 1..v + 1, points);
+        self.sources.splice(v + 1..v + 1, sources);
+
+        let sm = offset + m;
+        self.bridges.push(if sv < sm { (sv, sm) } else { (sm, sv) });
+    }
+
+    #[inline]
+    fn fill_points(&self, points: &mut Vec<IntPoint>) {
+        points.clear();
+        for contour in self.shape.iter() {
+            points.extend_from_slice(contour);
+        }
+    }
+}
+
+trait Touches {
+    fn has_touches(&self) -> bool;
+}
+
+impl Touches for [IntContour] {
+    // the solver expects the only repeated points to be the bridge ends
+    #[inline]
+    fn has_touches(&self) -> bool {
+        let mut points: Vec<IntPoint> = self.iter().flatten().copied().collect();
+        points.sort_unstable();
+        points.windows(2).any(|w| w[0] == w[1])
+    }
+}
+
+trait Leftmost {
+    fn leftmost(&self) -> usize;
+}
+
+impl Leftmost for [IntPoint] {
+    #[inline]
+    fn leftmost(&self) -> usize {
+        let mut index = 0;
+        for (i, p) in self.iter().enumerate() {
+            if *p < self[index] {
+                index = i;
+            }
+        }
+        index
+    }
+}
+
+// `true` if the direction from `b` to `p` goes strictly inside the contour at the
+// vertex `b`, the inner side is on the left of `a -> b -> c`
+#[inline]
+fn is_inside_cone(a: IntPoint, b: IntPoint, c: IntPoint, p: IntPoint) -> bool {
+    let e0 = c.subtract(b);
+    let e1 = a.subtract(b);
+    let d = p.subtract(b);
+    if e0.cross_product(e1) > 0 {
+        // convex
+        e0.cross_product(d) > 0 && d.cross_product(e1) > 0
+    } else {
+        // reflex or straight, outside is the closed cone from e1 to e0
+        !(e1.cross_product(d) >= 0 && d.cross_product(e0) >= 0)
+    }
+}
+
+// `true` if any edge of the closed path touches the segment `a b`, edges which
+// end at `a` or `b` are ignored
+fn crosses_path(a: IntPoint, b: IntPoint, path: &[IntPoint]) -> bool {
+    let mut p0 = path[path.len() - 1];
+    for &p1 in path.iter() {
+        let is_adjacent = p0 == a || p0 == b || p1 == a || p1 == b;
+        if !is_adjacent && is_segments_touch(a, b, p0, p1) {
+            return true;
+        }
+        if is_adjacent && (is_inner_point(a, b, p0) || is_inner_point(a, b, p1)) {
+            return true;
+        }
+        p0 = p1;
+    }
+    false
+}
+
+#[inline]
+fn orientation(a: IntPoint, b: IntPoint, c: IntPoint) -> i64 {
+    b.subtract(a).cross_product(c.subtract(a)).signum()
+}
+
+// `true` if `p` lies on the segment `a b` and is not one of its ends
+#[inline]
+fn is_inner_point(a: IntPoint, b: IntPoint, p: IntPoint) -> bool {
+    p != a && p != b && orientation(a, b, p) == 0 && is_in_box(a, b, p)
+}
+
+#[inline]
+fn is_in_box(a: IntPoint, b: IntPoint, p: IntPoint) -> bool {
+    a.x.min(b.x) <= p.x && p.x <= a.x.max(b.x) && a.y.min(b.y) <= p.y && p.y <= a.y.max(b.y)
+}
+
+fn is_segments_touch(a: IntPoint, b: IntPoint, c: IntPoint, d: IntPoint) -> bool {
+    let o0 = orientation(a, b, c);
+    let o1 = orientation(a, b, d);
+    let o2 = orientation(c, d, a);
+    let o3 = orientation(c, d, b);
+
+    if o0 * o1 < 0 && o2 * o3 < 0 {
+        return true;
+    }
+
+    o0 == 0 && is_in_box(a, b, c)
+        || o1 == 0 && is_in_box(a, b, d)
+        || o2 == 0 && is_in_box(c, d, a)
+        || o3 == 0 && is_in_box(c, d, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::earcut::holes::{BridgedContour, EarcutShape};
+    use crate::int::triangulation::{IntTriangulation, RawIntTriangulation};
+    use crate::int::triangulator::IntTriangulator;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::area::Area;
+    use i_overlay::i_shape::int::path::IntPath;
+    use i_overlay::i_shape::int::shape::IntShape;
+    use rand::RngExt;
+
+    // an octagon for `n = 8` or a diamond for `n = 4`, counter-clockwise
+    fn ring(x: i32, y: i32, r: i32, n: usize) -> IntPath {
+        let a = 7 * r / 10;
+        let octagon = [
+            (r, 0),
+            (a, a),
+            (0, r),
+            (-a, a),
+            (-r, 0),
+            (-a, -a),
+            (0, -r),
+            (a, -a),
+        ];
+        octagon
+            .iter()
+            .step_by(8 / n)
+            .map(|&(dx, dy)| IntPoint::new(x + dx, y + dy))
+            .collect()
+    }
+
+    fn hole(x: i32, y: i32, r: i32, n: usize) -> IntPath {
+        let mut path = ring(x, y, r, n);
+        path.reverse();
+        path
+    }
+
+    #[test]
+    fn test_0() {
+        // a washer with 8 + 8 points
+        let shape = vec![ring(0, 0, 10, 8), hole(0, 0, 5, 8)];
+        single_test(&shape);
+    }
+
+    #[test]
+    fn test_1() {
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(40, 0),
+                IntPoint::new(40, 10),
+                IntPoint::new(0, 10),
+            ],
+            hole(5, 5, 2, 4),
+            hole(15, 5, 2, 8),
+            hole(25, 5, 2, 4),
+            hole(35, 5, 2, 8),
+        ];
+        single_test(&shape);
+    }
+
+    #[test]
+    fn test_2() {
+        // the hole is bridged through a bridge point of another hole
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(30, 0),
+                IntPoint::new(30, 30),
+                IntPoint::new(0, 30),
+            ],
+            hole(10, 15, 3, 8),
+            hole(20, 15, 3, 8),
+            hole(15, 5, 2, 4),
+            hole(15, 25, 2, 4),
+        ];
+        single_test(&shape);
+    }
+
+    #[test]
+    fn test_3() {
+        let shape = vec![ring(0, 0, 10, 8), hole(0, 0, 5, 8)];
+
+        let mut triangulator = IntTriangulator::<u16>::default();
+        let monotone = triangulator.triangulate_shape(&shape);
+
+        triangulator.earcut = true;
+        let earcut = triangulator.triangulate_shape(&shape);
+        earcut.validate(shape.area_two());
+        assert_eq!(earcut.indices.len(), monotone.indices.len());
+
+        triangulator.delaunay = true;
+        let delaunay = triangulator.triangulate_shape(&shape);
+        delaunay.validate(shape.area_two());
+    }
+
+    #[test]
+    fn test_4() {
+        // the holes touch each other, the monotone triangulator takes over
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(20, 0),
+                IntPoint::new(20, 20),
+                IntPoint::new(0, 20),
+            ],
+            vec![
+                IntPoint::new(5, 5),
+                IntPoint::new(5, 15),
+                IntPoint::new(10, 10),
+            ],
+            vec![
+                IntPoint::new(10, 10),
+                IntPoint::new(15, 15),
+                IntPoint::new(15, 5),
+            ],
+        ];
+
+        let mut flat = IntTriangulation::<u16>::default();
+        assert!(!shape.earcut_shape_flat_into(&mut flat));
+
+        let mut triangulator = IntTriangulator::<u16>::default();
+        triangulator.earcut = true;
+        let triangulation = triangulator.triangulate_shape(&shape);
+        triangulation.validate(shape.area_two());
+    }
+
+    #[test]
+    fn test_random_0() {
+        for _ in 0..5_000 {
+            let shapes =
+                random(32, 40).simplify(FillRule::NonZero, IntOverlayOptions::keep_output_points());
+            for shape in shapes.iter() {
+                if shape.len() > 1 && BridgedContour::new(shape).is_some() {
+                    single_test(shape);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_1() {
+        let mut triangulator = IntTriangulator::<u16>::default();
+        triangulator.earcut = true;
+        triangulator.delaunay = true;
+        for _ in 0..2_000 {
+            let shape = vec![random(64, 24), random(32, 12), random(32, 12)];
+            let triangulation = triangulator.triangulate_shape(&shape);
+            let area = shape
+                .simplify(FillRule::NonZero, IntOverlayOptions::keep_output_points())
+                .area_two();
+            triangulation.validate(area);
+        }
+    }
+
+    fn single_test(shape: &IntShape) {
+        let points_count: usize = shape.iter().map(|c| c.len()).sum();
+
+        let mut flat = IntTriangulation::<u16>::default();
+        assert!(shape.earcut_shape_flat_into(&mut flat));
+        flat.validate(shape.area_two());
+        assert_eq!(flat.points.len(), points_count);
+        assert_eq!(flat.indices.len() / 3, points_count + 2 * shape.len() - 4);
+
+        let mut net = RawIntTriangulation::default();
+        assert!(shape.earcut_shape_net_into(&mut net));
+        net.validate();
+        assert_eq!(net.area_two(), shape.area_two());
+        for t in net.triangles.iter() {
+            for v in t.vertices.iter() {
+                assert_eq!(net.points[v.index], v.point);
+            }
+        }
+
+        // every contour edge is a border, the bridges are linked
+        let borders = net
+            .triangles
+            .iter()
+            .flat_map(|t| t.neighbors.iter())
+            .filter(|&&n| n == usize::MAX)
+            .count();
+        assert_eq!(borders, points_count);
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
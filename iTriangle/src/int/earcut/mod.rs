@@ -1,5 +1,8 @@
 pub mod earcut_64;
+pub mod earcut_wide;
 mod flat;
 mod heap;
+pub(crate) mod holes;
 mod net;
 mod util;
+mod wide;
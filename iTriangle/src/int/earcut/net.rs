@@ -72,7 +72,13 @@ impl<'a> NetEarcutStore<'a> {
 
 impl EarcutStore for NetEarcutStore<'_> {
     #[inline]
-    fn collect_triangles(&mut self, contour: &[IntPoint], start: usize, bits: u64, count: u32) {
+    fn collect_triangles<B: Bit>(
+        &mut self,
+        contour: &[IntPoint],
+        start: usize,
+        bits: B,
+        count: u32,
+    ) {
         let ai = start;
         let a = IndexPoint::new(ai, contour[ai]);
 
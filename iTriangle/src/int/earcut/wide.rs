@@ -0,0 +1,212 @@
+use crate::int::earcut::earcut_64::Bit;
+use core::ops::{BitAnd, BitAndAssign, BitOr, Not};
+
+/// A bit set of `64 * N` bits, the lowest index is stored in the lowest bit of the first word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct WideBits<const N: usize>([u64; N]);
+
+impl<const N: usize> WideBits<N> {
+    #[inline(always)]
+    fn last_index(&self) -> usize {
+        for (w, word) in self.0.iter().enumerate().rev() {
+            if *word != 0 {
+                return 64 * w + 63 - word.leading_zeros() as usize;
+            }
+        }
+        64 * N
+    }
+}
+
+impl<const N: usize> BitAnd for WideBits<N> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitand(self, other: Self) -> Self {
+        Self(core::array::from_fn(|w| self.0[w] & other.0[w]))
+    }
+}
+
+impl<const N: usize> BitOr for WideBits<N> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitor(self, other: Self) -> Self {
+        Self(core::array::from_fn(|w| self.0[w] | other.0[w]))
+    }
+}
+
+impl<const N: usize> Not for WideBits<N> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn not(self) -> Self {
+        Self(self.0.map(|word| !word))
+    }
+}
+
+impl<const N: usize> BitAndAssign for WideBits<N> {
+    #[inline(always)]
+    fn bitand_assign(&mut self, other: Self) {
+        for (word, other) in self.0.iter_mut().zip(other.0) {
+            *word &= other;
+        }
+    }
+}
+
+impl<const N: usize> Bit for WideBits<N> {
+    #[inline(always)]
+    fn ones_start_to_index(index: usize) -> Self {
+        // index is excluded
+        debug_assert!(index <= 64 * N);
+        Self(core::array::from_fn(|w| {
+            let start = 64 * w;
+            if index >= start + 64 {
+                u64::MAX
+            } else if index <= start {
+                0
+            } else {
+                u64::ones_start_to_index(index - start)
+            }
+        }))
+    }
+
+    #[inline(always)]
+    fn ones_index_to_end(index: usize) -> Self {
+        // index is included
+        Self(core::array::from_fn(|w| {
+            let start = 64 * w;
+            if index <= start {
+                u64::MAX
+            } else if index >= start + 64 {
+                0
+            } else {
+                u64::ones_index_to_end(index - start)
+            }
+        }))
+    }
+
+    #[inline(always)]
+    fn ones_in_sorted_closed_range(min: usize, max: usize) -> Self {
+        debug_assert!(min < max);
+        Self::ones_index_to_end(min) & Self::ones_start_to_index((max + 1).min(64 * N))
+    }
+
+    #[inline(always)]
+    fn ones_in_range_include(start: usize, end: usize) -> Self {
+        if start < end {
+            Self::ones_in_sorted_closed_range(start, end)
+        } else {
+            let mask1 = Self::ones_index_to_end(start);
+            let mask2 = Self::ones_start_to_index(end + 1);
+            mask1 | mask2
+        }
+    }
+
+    #[inline(always)]
+    fn next_wrapped_index(&self, after: usize) -> usize {
+        debug_assert!(after < 64 * N);
+        let front = *self & Self::ones_index_to_end(after + 1);
+        if !front.is_zero() {
+            front.first_index()
+        } else {
+            self.first_index()
+        }
+    }
+
+    #[inline(always)]
+    fn prev_wrapped_index(&self, before: usize) -> usize {
+        debug_assert!(before < 64 * N);
+        let back = *self & Self::ones_start_to_index(before);
+        if !back.is_zero() {
+            back.last_index()
+        } else {
+            self.last_index()
+        }
+    }
+
+    #[inline(always)]
+    fn ones_count(&self) -> u32 {
+        self.0.iter().map(|word| word.count_ones()).sum()
+    }
+
+    #[inline(always)]
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|&word| word == 0)
+    }
+
+    #[inline(always)]
+    fn first_index(&self) -> usize {
+        for (w, word) in self.0.iter().enumerate() {
+            if *word != 0 {
+                return 64 * w + word.trailing_zeros() as usize;
+            }
+        }
+        64 * N
+    }
+
+    #[inline(always)]
+    fn set(&mut self, index: usize) {
+        self.0[index >> 6] |= 1 << (index & 63);
+    }
+
+    #[inline(always)]
+    fn unset(&mut self, index: usize) {
+        self.0[index >> 6] &= !(1 << (index & 63));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::earcut::earcut_64::Bit;
+    use crate::int::earcut::wide::WideBits;
+    use rand::RngExt;
+
+    type Bits = WideBits<2>;
+
+    #[test]
+    fn test_ones_start_to_index_0() {
+        assert_eq!(Bits::ones_start_to_index(1), WideBits([1, 0]));
+        assert_eq!(Bits::ones_start_to_index(64), WideBits([u64::MAX, 0]));
+        assert_eq!(Bits::ones_start_to_index(65), WideBits([u64::MAX, 1]));
+        assert_eq!(Bits::ones_start_to_index(128), WideBits([u64::MAX; 2]));
+    }
+
+    #[test]
+    fn test_ones_in_range_include_0() {
+        assert_eq!(Bits::ones_in_range_include(0, 127), WideBits([u64::MAX; 2]));
+        assert_eq!(Bits::ones_in_range_include(63, 64), WideBits([1 << 63, 1]));
+        assert_eq!(Bits::ones_in_range_include(127, 0), WideBits([1, 1 << 63]));
+    }
+
+    #[test]
+    fn test_wrapped_index_0() {
+        let bits = WideBits([1 << 3, 1 << 5]);
+        assert_eq!(bits.next_wrapped_index(3), 69);
+        assert_eq!(bits.next_wrapped_index(69), 3);
+        assert_eq!(bits.prev_wrapped_index(69), 3);
+        assert_eq!(bits.prev_wrapped_index(3), 69);
+    }
+
+    #[test]
+    fn test_random_0() {
+        // a single word must give the same answers as u64
+        let mut rng = rand::rng();
+        for _ in 0..10_000 {
+            let word: u64 = rng.random();
+            if word == 0 {
+                continue;
+            }
+            let bits = WideBits::<1>([word]);
+            let a = rng.random_range(0..64);
+            let b = rng.random_range(0..64);
+
+            assert_eq!(bits.next_wrapped_index(a), word.next_wrapped_index(a));
+            assert_eq!(bits.prev_wrapped_index(a), word.prev_wrapped_index(a));
+            assert_eq!(bits.ones_count(), word.count_ones());
+            assert_eq!(
+                WideBits::<1>::ones_in_range_include(a, b).0[0],
+                u64::ones_in_range_include(a, b)
+            );
+        }
+    }
+}
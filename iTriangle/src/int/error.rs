@@ -0,0 +1,311 @@
+use crate::int::triangulation::IndexType;
+use i_overlay::i_float::float::compatible::FloatPointCompatible;
+use i_overlay::i_float::float::number::FloatNumber;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_shape::flat::buffer::FlatContoursBuffer;
+use i_overlay::i_shape::int::shape::{IntContour, IntShape};
+
+/// The largest absolute coordinate accepted by the `try_*` methods.
+///
+/// Within this range the difference of any two coordinates still fits into `i32`.
+pub const MAX_COORDINATE: i32 = (1 << 30) - 1;
+
+/// The reason a `try_*` or attribute method refused to triangulate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriangulationError {
+    /// The result has more points than the index type can address.
+    IndexOverflow { points_count: usize, max: usize },
+    /// The input has no contour with at least three points.
+    EmptyInput,
+    /// The Steiner point at `index` lies outside the supported coordinate range.
+    InvalidSteinerPoint { index: usize },
+    /// An input point lies outside [`MAX_COORDINATE`] or is not finite.
+    CoordinateOutOfRange,
+    /// The number of attributes differs from the number of points they belong to.
+    AttributeCountMismatch { expected: usize, found: usize },
+}
+
+#[inline]
+pub(crate) fn check_attributes_count(
+    expected: usize,
+    found: usize,
+) -> Result<(), TriangulationError> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(TriangulationError::AttributeCountMismatch { expected, found })
+    }
+}
+
+#[inline]
+pub(crate) fn check_points_count<I: IndexType>(
+    points_count: usize,
+) -> Result<(), TriangulationError> {
+    if points_count > I::MAX {
+        Err(TriangulationError::IndexOverflow {
+            points_count,
+            max: I::MAX,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[inline]
+pub(crate) fn check_steiner_points(points: &[IntPoint]) -> Result<(), TriangulationError> {
+    match points.iter().position(|p| !p.is_in_range()) {
+        Some(index) => Err(TriangulationError::InvalidSteinerPoint { index }),
+        None => Ok(()),
+    }
+}
+
+#[inline]
+pub(crate) fn check_float_steiner_points<P: FloatPointCompatible>(
+    points: &[P],
+) -> Result<(), TriangulationError> {
+    match points.iter().position(|p| !p.is_finite()) {
+        Some(index) => Err(TriangulationError::InvalidSteinerPoint { index }),
+        None => Ok(()),
+    }
+}
+
+// checks the input before a `try_*` triangulation
+pub(crate) trait InputCheck {
+    fn check_input(&self) -> Result<(), TriangulationError>;
+}
+
+impl InputCheck for [IntPoint] {
+    #[inline]
+    fn check_input(&self) -> Result<(), TriangulationError> {
+        check_paths(core::iter::once(self))
+    }
+}
+
+impl InputCheck for [IntContour] {
+    #[inline]
+    fn check_input(&self) -> Result<(), TriangulationError> {
+        check_paths(self.iter().map(|c| c.as_slice()))
+    }
+}
+
+impl InputCheck for [IntShape] {
+    #[inline]
+    fn check_input(&self) -> Result<(), TriangulationError> {
+        check_paths(self.iter().flatten().map(|c| c.as_slice()))
+    }
+}
+
+impl InputCheck for FlatContoursBuffer {
+    #[inline]
+    fn check_input(&self) -> Result<(), TriangulationError> {
+        check_paths(self.ranges.iter().map(|r| &self.points[r.clone()]))
+    }
+}
+
+fn check_paths<'a, I: Iterator<Item = &'a [IntPoint]>>(paths: I) -> Result<(), TriangulationError> {
+    let mut is_empty = true;
+    for path in paths {
+        if path.iter().any(|p| !p.is_in_range()) {
+            return Err(TriangulationError::CoordinateOutOfRange);
+        }
+        is_empty &= path.len() < 3;
+    }
+
+    if is_empty {
+        Err(TriangulationError::EmptyInput)
+    } else {
+        Ok(())
+    }
+}
+
+pub(crate) fn check_float_paths<'a, P, I>(paths: I) -> Result<(), TriangulationError>
+where
+    P: FloatPointCompatible + 'a,
+    I: Iterator<Item = &'a [P]>,
+{
+    let mut is_empty = true;
+    for path in paths {
+        if path.iter().any(|p| !p.is_finite()) {
+            return Err(TriangulationError::CoordinateOutOfRange);
+        }
+        is_empty &= path.len() < 3;
+    }
+
+    if is_empty {
+        Err(TriangulationError::EmptyInput)
+    } else {
+        Ok(())
+    }
+}
+
+trait RangeCheck {
+    fn is_in_range(&self) -> bool;
+}
+
+impl RangeCheck for IntPoint {
+    #[inline]
+    fn is_in_range(&self) -> bool {
+        self.x.unsigned_abs() <= MAX_COORDINATE as u32
+            && self.y.unsigned_abs() <= MAX_COORDINATE as u32
+    }
+}
+
+trait FiniteCheck {
+    fn is_finite(&self) -> bool;
+}
+
+impl<P: FloatPointCompatible> FiniteCheck for P {
+    #[inline]
+    fn is_finite(&self) -> bool {
+        self.x().to_f64().is_finite() && self.y().to_f64().is_finite()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::error::{TriangulationError, MAX_COORDINATE};
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::int::triangulator::IntTriangulator;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::i_float::int::point::IntPoint;
+
+    #[test]
+    fn test_0() {
+        let mut triangulator = IntTriangulator::<u8>::default();
+
+        let empty: Vec<IntPoint> = Vec::new();
+        assert_eq!(
+            triangulator.try_triangulate_contour(&empty).err(),
+            Some(TriangulationError::EmptyInput)
+        );
+
+        let line = vec![IntPoint::new(0, 0), IntPoint::new(10, 0)];
+        assert_eq!(
+            triangulator.try_triangulate_shape(&vec![line]).err(),
+            Some(TriangulationError::EmptyInput)
+        );
+
+        let far = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(MAX_COORDINATE + 1, 0),
+            IntPoint::new(0, 10),
+        ];
+        assert_eq!(
+            triangulator.try_triangulate_shapes(&vec![vec![far]]).err(),
+            Some(TriangulationError::CoordinateOutOfRange)
+        );
+
+        let square = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+        let triangulation = triangulator.try_triangulate_contour(&square).unwrap();
+        assert_eq!(triangulation.indices.len(), 6);
+    }
+
+    #[test]
+    fn test_1() {
+        // a comb with 300 teeth does not fit into u8 indices
+        let n = 300;
+        let mut contour = Vec::with_capacity(2 * n + 2);
+        for i in 0..n as i32 {
+            contour.push(IntPoint::new(10 * i, 0));
+            contour.push(IntPoint::new(10 * i + 5, 10));
+        }
+        contour.push(IntPoint::new(10 * n as i32, 0));
+        contour.push(IntPoint::new(10 * n as i32, -10));
+        contour.push(IntPoint::new(0, -10));
+
+        let mut triangulator = IntTriangulator::<u8>::default();
+        let result = triangulator.try_triangulate_contour(&contour);
+        assert_eq!(
+            result.err(),
+            Some(TriangulationError::IndexOverflow {
+                points_count: contour.len(),
+                max: u8::MAX as usize,
+            })
+        );
+
+        let raw = contour.triangulate();
+        assert!(raw.try_to_triangulation::<u8>().is_err());
+        assert_eq!(
+            raw.try_to_triangulation::<u16>().unwrap().indices,
+            raw.to_triangulation::<u16>().indices
+        );
+
+        let mut triangulator = IntTriangulator::<u16>::default();
+        let triangulation = triangulator.try_triangulate_contour(&contour).unwrap();
+        assert_eq!(triangulation.points.len(), contour.len());
+    }
+
+    #[test]
+    fn test_2() {
+        let square = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+
+        let points = [IntPoint::new(5, 5), IntPoint::new(i32::MIN, 5)];
+        assert_eq!(
+            square.try_triangulate_with_steiner_points(&points).err(),
+            Some(TriangulationError::InvalidSteinerPoint { index: 1 })
+        );
+
+        let raw = square
+            .try_triangulate_with_steiner_points(&points[..1])
+            .unwrap();
+        assert_eq!(raw.points.len(), 5);
+    }
+
+    #[test]
+    fn test_3() {
+        // a rejected input keeps the results of the last triangulation
+        let bow_tie = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 0),
+            IntPoint::new(0, 10),
+        ];
+
+        let mut triangulator = IntTriangulator::<u8>::default();
+        triangulator.provenance = true;
+        triangulator.diagnostics = true;
+        let mut triangulation = triangulator.try_triangulate_contour(&bow_tie).unwrap();
+
+        let ranges = triangulator.shape_ranges().to_vec();
+        let origins = triangulator.vertex_origins().to_vec();
+        let issues = triangulator.validation_issues().to_vec();
+        assert!(!ranges.is_empty());
+        assert!(!origins.is_empty());
+        assert!(!issues.is_empty());
+
+        let mut comb = Vec::with_capacity(602);
+        for i in 0..300 {
+            comb.push(IntPoint::new(10 * i, 0));
+            comb.push(IntPoint::new(10 * i + 5, 10));
+        }
+        comb.push(IntPoint::new(3000, -10));
+        comb.push(IntPoint::new(0, -10));
+
+        let indices = triangulation.indices.clone();
+        assert!(triangulator
+            .try_triangulate_contour_into(&comb, &mut triangulation)
+            .is_err());
+        assert!(triangulator
+            .try_triangulate_shapes_into(&vec![vec![comb]], &mut triangulation)
+            .is_err());
+        assert!(triangulator
+            .try_triangulate_shape(&vec![vec![IntPoint::new(0, 0)]])
+            .is_err());
+
+        assert_eq!(triangulation.indices, indices);
+        assert_eq!(triangulator.shape_ranges(), ranges.as_slice());
+        assert_eq!(triangulator.vertex_origins(), origins.as_slice());
+        assert_eq!(triangulator.validation_issues(), issues.as_slice());
+    }
+}
@@ -4,8 +4,11 @@ use i_key_sort::sort::two_keys::TwoKeysSort;
 use i_overlay::i_float::int::rect::IntRect;
 use i_overlay::i_shape::int::IntPoint;
 
+use crate::advanced::delaunay::IntDelaunay;
+use crate::advanced::insert::{walk, MeshLocation};
+use crate::geom::triangle::IntTriangle;
 use crate::{
-    int::triangulation::{IndexType, IntTriangulation},
+    int::triangulation::{IndexType, IntTriangulation, RawIntTriangulation},
     location::{PointLocationInTriangulation, TriangleIndex},
 };
 
@@ -50,52 +53,62 @@ fn locate_points_in_triangles(
                 continue;
             }
 
-            match triangle.locate_point(point) {
-                PointLocationInTriangle::Outside => {}
-                PointLocationInTriangle::Inside => match &result[point_index] {
-                    PointLocationInTriangulation::Outside => {
-                        result[point_index] =
-                            PointLocationInTriangulation::InsideTriangle(triangle_index);
-                    }
-                    // Shouldn't happen.
-                    _ => {
-                        panic!("Expected outside triangle");
-                    }
-                },
-                PointLocationInTriangle::OnEdge => match &result[point_index] {
-                    PointLocationInTriangulation::Outside => {
-                        result[point_index] =
-                            PointLocationInTriangulation::OnExteriorEdge(triangle_index);
-                    }
-                    PointLocationInTriangulation::OnExteriorEdge(i) => {
-                        result[point_index] =
-                            PointLocationInTriangulation::OnInteriorEdge(*i, triangle_index);
-                    }
-                    // Shouldn't happen.
-                    _ => {
-                        panic!("More than 2 triangles for one edge");
-                    }
-                },
-                PointLocationInTriangle::OnVertex => match &mut result[point_index] {
-                    PointLocationInTriangulation::Outside => {
-                        result[point_index] =
-                            PointLocationInTriangulation::OnVertex(vec![triangle_index]);
-                    }
-                    PointLocationInTriangulation::OnVertex(hits) => {
-                        hits.push(triangle_index);
-                    }
-                    // Shouldn't happen.
-                    _ => {
-                        panic!("Point must be only on Vertex");
-                    }
-                },
-            }
+            add_location(
+                &mut result[point_index],
+                triangle_index,
+                triangle.locate_point(point),
+            );
         }
     }
 
     result
 }
 
+// merges the location in one triangle into the location in the triangulation,
+// triangles must come in ascending order
+pub(crate) fn add_location(
+    result: &mut PointLocationInTriangulation,
+    triangle_index: TriangleIndex,
+    location: PointLocationInTriangle,
+) {
+    match location {
+        PointLocationInTriangle::Outside => {}
+        PointLocationInTriangle::Inside => match result {
+            PointLocationInTriangulation::Outside => {
+                *result = PointLocationInTriangulation::InsideTriangle(triangle_index);
+            }
+            // Shouldn't happen.
+            _ => {
+                panic!("Expected outside triangle");
+            }
+        },
+        PointLocationInTriangle::OnEdge => match result {
+            PointLocationInTriangulation::Outside => {
+                *result = PointLocationInTriangulation::OnExteriorEdge(triangle_index);
+            }
+            PointLocationInTriangulation::OnExteriorEdge(i) => {
+                *result = PointLocationInTriangulation::OnInteriorEdge(*i, triangle_index);
+            }
+            // Shouldn't happen.
+            _ => {
+                panic!("More than 2 triangles for one edge");
+            }
+        },
+        PointLocationInTriangle::OnVertex => match result {
+            PointLocationInTriangulation::Outside => {
+                *result = PointLocationInTriangulation::OnVertex(vec![triangle_index]);
+            }
+            PointLocationInTriangulation::OnVertex(hits) => {
+                hits.push(triangle_index);
+            }
+            // Shouldn't happen.
+            _ => {
+                panic!("Point must be only on Vertex");
+            }
+        },
+    }
+}
+
 #[derive(Clone, Copy)]
 struct IndexedPoint {
     index: usize,
@@ -103,14 +116,14 @@ struct IndexedPoint {
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum PointLocationInTriangle {
+pub(crate) enum PointLocationInTriangle {
     Outside,
     Inside,
     OnEdge,
     OnVertex,
 }
 
-trait IntPointInTriangleLocator {
+pub(crate) trait IntPointInTriangleLocator {
     fn locate_point(&self, point: IntPoint) -> PointLocationInTriangle;
 
     fn boundary(&self) -> IntRect;
@@ -173,16 +186,126 @@ impl<I: IndexType> IntPointInTriangulationLocator for IntTriangulation<I> {
     }
 }
 
+impl RawIntTriangulation {
+    /// Locates a single point by walking the triangle neighbors, starting at the
+    /// triangle `hint`.
+    ///
+    /// Much faster than [`IntTriangulation::locate_points`] for a few queries
+    /// against a big mesh, especially if the queries are close to each other.
+    /// A walk that leaves a non-convex mesh follows the boundary loop it left through
+    /// to the place where it comes back in. A point the walk can not reach, e.g. in
+    /// a separate part of the mesh or on an island inside a hole, is found by a scan
+    /// of all triangles, so an outside point costs a full scan.
+    /// A point where several vertex fans touch reports the fan the walk arrived in.
+    ///
+    /// # Parameters
+    /// - `hint`: a triangle index to start from, any value is accepted. It is set to
+    ///   the triangle holding `p`, or to the last boundary triangle for an outside `p`,
+    ///   so the next query starts where this one ended.
+    /// - `p`: the point to locate
+    ///
+    /// Triangle indices are the same as in [`triangle_indices`](Self::triangle_indices).
+    #[inline]
+    pub fn locate_point_from(&self, hint: &mut usize, p: IntPoint) -> PointLocationInTriangulation {
+        locate_point_in_mesh(&self.triangles, hint, p)
+    }
+}
+
+impl IntDelaunay {
+    /// Locates a single point by walking the triangle neighbors, starting at the
+    /// triangle `hint`, see [`RawIntTriangulation::locate_point_from`].
+    #[inline]
+    pub fn locate_point_from(&self, hint: &mut usize, p: IntPoint) -> PointLocationInTriangulation {
+        locate_point_in_mesh(&self.triangles, hint, p)
+    }
+}
+
+fn locate_point_in_mesh(
+    triangles: &[IntTriangle],
+    hint: &mut usize,
+    p: IntPoint,
+) -> PointLocationInTriangulation {
+    let (index, location) = walk(triangles, *hint, p);
+    let result = match location {
+        MeshLocation::Outside if index == usize::MAX => {
+            return PointLocationInTriangulation::Outside
+        }
+        MeshLocation::Outside => PointLocationInTriangulation::Outside,
+        MeshLocation::Inside(index) => {
+            PointLocationInTriangulation::InsideTriangle(TriangleIndex::new(index))
+        }
+        MeshLocation::OnEdge(index, position) => {
+            let neighbor = triangles[index].neighbors[position];
+            if neighbor < triangles.len() {
+                PointLocationInTriangulation::OnInteriorEdge(
+                    TriangleIndex::new(index.min(neighbor)),
+                    TriangleIndex::new(index.max(neighbor)),
+                )
+            } else {
+                PointLocationInTriangulation::OnExteriorEdge(TriangleIndex::new(index))
+            }
+        }
+        MeshLocation::OnVertex(vertex_index) => PointLocationInTriangulation::OnVertex(
+            vertex_triangles(triangles, index, vertex_index)
+                .into_iter()
+                .map(TriangleIndex::new)
+                .collect(),
+        ),
+    };
+    *hint = index;
+    result
+}
+
+// the fan around the vertex, collected by rotating through the neighbors in both
+// directions, an open fan stops at the boundary on both sides
+fn vertex_triangles(triangles: &[IntTriangle], start: usize, vertex_index: usize) -> Vec<usize> {
+    let mut fan = vec![start];
+    let first = &triangles[start];
+    let position = first.position_of(vertex_index);
+    for side in [1, 2] {
+        // the edge shared with the next triangle is opposite this vertex
+        let mut other = first.vertices[(position + side) % 3].index;
+        let mut index = start;
+        loop {
+            let triangle = &triangles[index];
+            let next = triangle.neighbors[triangle.position_of(other)];
+            if next == start {
+                return sorted(fan);
+            }
+            if next == usize::MAX {
+                break;
+            }
+            other = triangle.vertices[triangle.other_vertex(vertex_index, other)].index;
+            index = next;
+            fan.push(index);
+        }
+    }
+
+    sorted(fan)
+}
+
+#[inline]
+fn sorted(mut fan: Vec<usize>) -> Vec<usize> {
+    fan.sort_unstable();
+    fan
+}
+
 #[cfg(test)]
 mod tests {
+    use crate::advanced::delaunay::IntDelaunay;
+    use crate::int::triangulatable::IntTriangulatable;
     use crate::int::triangulator::IntTriangulator;
     use crate::int::validation::Validation;
+    use crate::location::TriangleIndex;
     use crate::{int::triangulation::IntTriangulation, location::PointLocationInTriangulation};
     use alloc::vec;
     use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
     use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
     use i_overlay::i_shape::int::IntPoint;
     use i_overlay::i_shape::int_path;
+    use rand::RngExt;
 
     fn square_triangulation() -> IntTriangulation<u16> {
         IntTriangulation {
@@ -286,6 +409,149 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_locate_point_from() {
+        // a square with a square hole, the walk has to go around the hole
+        let shape = vec![
+            int_path![[0, 0], [12, 0], [12, 12], [0, 12]],
+            int_path![[4, 4], [4, 8], [8, 8], [8, 4]],
+        ];
+        let raw = shape.triangulate();
+        let triangulation = raw.to_triangulation::<u16>();
+        let delaunay = shape.triangulate().into_delaunay();
+
+        let points = int_path![
+            [1, 1],
+            [11, 11],
+            [6, 6],
+            [6, 2],
+            [0, 6],
+            [4, 4],
+            [13, 1],
+            [10, 10]
+        ];
+        let expected = triangulation.locate_points(&points);
+
+        let mut hint = 0;
+        for (p, location) in points.iter().zip(expected.iter()) {
+            assert_eq!(&raw.locate_point_from(&mut hint, *p), location);
+        }
+
+        let mut hint = delaunay.triangles.len() - 1;
+        let location = delaunay.locate_point_from(&mut hint, IntPoint::new(1, 6));
+        if let PointLocationInTriangulation::InsideTriangle(triangle) = location {
+            assert_eq!(hint, triangle.index());
+        } else {
+            panic!("not Inside");
+        }
+
+        // an outside point keeps the hint
+        let location = delaunay.locate_point_from(&mut hint, IntPoint::new(6, 6));
+        assert_eq!(location, PointLocationInTriangulation::Outside);
+        assert!(hint < delaunay.triangles.len());
+    }
+
+    #[test]
+    fn test_locate_point_from_parts() {
+        // two separate squares, the second one with an island inside its hole
+        let shapes = vec![
+            vec![int_path![[0, 0], [10, 0], [10, 10], [0, 10]]],
+            vec![
+                int_path![[20, 0], [50, 0], [50, 30], [20, 30]],
+                int_path![[30, 10], [30, 20], [40, 20], [40, 10]],
+            ],
+            vec![int_path![[33, 13], [37, 13], [37, 17], [33, 17]]],
+        ];
+        let raw = shapes.triangulate();
+        let triangulation = raw.to_triangulation::<u16>();
+        let delaunay = shapes.triangulate().into_delaunay();
+
+        let points = int_path![
+            [5, 5],
+            [25, 5],
+            [35, 15],
+            [15, 5],
+            [31, 15],
+            [2, 8],
+            [34, 16]
+        ];
+        let expected = triangulation.locate_points(&points);
+        assert!(expected[..3].iter().all(|l| l.triangle().is_some()));
+
+        for start in 0..raw.triangles.len() {
+            for (p, location) in points.iter().zip(expected.iter()) {
+                let mut hint = start;
+                assert_eq!(&raw.locate_point_from(&mut hint, *p), location);
+            }
+        }
+
+        let triangulation = IntTriangulation::<u32> {
+            indices: delaunay.triangle_indices(),
+            points: delaunay.points.clone(),
+        };
+        let expected = triangulation.locate_points(&points);
+        let mut hint = 0;
+        for (p, location) in points.iter().zip(expected.iter()) {
+            assert_eq!(&delaunay.locate_point_from(&mut hint, *p), location);
+        }
+    }
+
+    #[test]
+    fn test_random_locate_point_from() {
+        let mut rng = rand::rng();
+        for _ in 0..200 {
+            let path: Vec<_> = (0..8)
+                .map(|_| IntPoint::new(rng.random_range(-20..=20), rng.random_range(-20..=20)))
+                .collect();
+            let shapes = path.simplify(FillRule::NonZero, IntOverlayOptions::default());
+            let Some(first) = shapes.first() else {
+                continue;
+            };
+
+            let delaunay = first.triangulate().into_delaunay();
+            let triangulation = IntTriangulation::<u32> {
+                indices: delaunay.triangle_indices(),
+                points: delaunay.points.clone(),
+            };
+
+            let points: Vec<_> = (-22..=22)
+                .flat_map(|x| (-22..=22).map(move |y| IntPoint::new(x, y)))
+                .collect();
+            let expected = triangulation.locate_points(&points);
+
+            let mut hint = 0;
+            for (p, location) in points.iter().zip(expected.iter()) {
+                let result = delaunay.locate_point_from(&mut hint, *p);
+                match (&result, location) {
+                    // several fans touch here, the walk reports the one it arrived in
+                    (
+                        PointLocationInTriangulation::OnVertex(fan),
+                        PointLocationInTriangulation::OnVertex(all),
+                    ) if open_edges(&delaunay, all, *p) > 2 => {
+                        assert!(!fan.is_empty());
+                        assert!(fan.iter().all(|t| all.contains(t)));
+                    }
+                    _ => assert_eq!(&result, location),
+                }
+            }
+        }
+    }
+
+    // boundary edges that end at p, an open fan has two of them
+    fn open_edges(delaunay: &IntDelaunay, triangles: &[TriangleIndex], p: IntPoint) -> usize {
+        triangles
+            .iter()
+            .map(|t| {
+                let triangle = &delaunay.triangles[t.index()];
+                let i = triangle.vertices.iter().position(|v| v.point == p).unwrap();
+                [1, 2]
+                    .iter()
+                    .filter(|&&k| triangle.is_boundary((i + k) % 3))
+                    .count()
+            })
+            .sum()
+    }
+
     impl PointLocationInTriangulation {
         fn assert_on_vertex(&self, triangles: &[usize]) {
             if let PointLocationInTriangulation::OnVertex(vec) = self {
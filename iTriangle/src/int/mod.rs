@@ -1,10 +1,18 @@
+pub mod attribute;
+pub mod barycentric;
 mod binder;
+mod constraint;
 pub mod custom;
+pub mod diagnostics;
 pub mod earcut;
+pub mod error;
 pub mod locator;
 mod meta;
 pub(crate) mod monotone;
+pub mod prepared;
+pub mod provenance;
 mod solver;
+pub mod steiner;
 pub mod triangulatable;
 pub mod triangulation;
 pub mod triangulator;
@@ -0,0 +1,859 @@
+use crate::advanced::delaunay::IntDelaunay;
+use crate::geom::triangle::IntTriangle;
+use crate::int::locator::{add_location, IntPointInTriangleLocator, PointLocationInTriangle};
+use crate::int::triangulation::{IndexType, IntTriangulation, RawIntTriangulation};
+use crate::location::{PointLocationInTriangulation, TriangleIndex};
+use crate::tessellation::graded::sqr_distance_to_segment;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_float::int::rect::IntRect;
+use i_overlay::i_float::triangle::Triangle;
+
+/// A triangulation with a uniform grid index, built once for many queries.
+///
+/// Every grid cell keeps the triangles whose bounding box overlaps it, the grid
+/// has about as many cells as there are triangles. Point location, rectangle and
+/// radius queries only test the triangles of the cells they touch.
+///
+/// Triangle indices are the same as in the source [`IntTriangulation`].
+pub struct IntPreparedTriangulation {
+    pub(crate) triangles: Vec<[IntPoint; 3]>,
+    grid: Grid,
+    pub(crate) edges: Vec<BoundaryEdge>,
+    edge_grid: Grid,
+}
+
+/// The point of a mesh that is the closest to a query point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IntClosestPoint {
+    /// The triangle that holds `point`.
+    pub triangle: TriangleIndex,
+    /// The closest point, the query point itself if it is inside the mesh.
+    pub point: IntPoint,
+    /// Squared distance between the query point and `point`.
+    pub sqr_distance: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BoundaryEdge {
+    pub(crate) triangle: usize,
+    pub(crate) a: IntPoint,
+    pub(crate) b: IntPoint,
+}
+
+pub(crate) struct Grid {
+    rect: IntRect,
+    cell_size: i64,
+    columns: usize,
+    rows: usize,
+    // items of the cell i are items[offsets[i]..offsets[i + 1]]
+    offsets: Vec<usize>,
+    items: Vec<usize>,
+}
+
+impl<I: IndexType> IntTriangulation<I> {
+    /// Builds an [`IntPreparedTriangulation`] for repeated queries against this mesh.
+    #[inline]
+    pub fn prepare(&self) -> IntPreparedTriangulation {
+        let edges = boundary_edges(&self.indices, &self.points);
+        IntPreparedTriangulation::new(self.triangles().collect(), edges)
+    }
+}
+
+impl RawIntTriangulation {
+    /// Builds an [`IntPreparedTriangulation`] for repeated queries against this mesh.
+    #[inline]
+    pub fn prepare(&self) -> IntPreparedTriangulation {
+        IntPreparedTriangulation::with_mesh(&self.triangles)
+    }
+}
+
+impl IntDelaunay {
+    /// Builds an [`IntPreparedTriangulation`] for repeated queries against this mesh.
+    #[inline]
+    pub fn prepare(&self) -> IntPreparedTriangulation {
+        IntPreparedTriangulation::with_mesh(&self.triangles)
+    }
+}
+
+impl IntPreparedTriangulation {
+    pub(crate) fn new(triangles: Vec<[IntPoint; 3]>, edges: Vec<BoundaryEdge>) -> Self {
+        let boxes: Vec<_> = triangles.iter().map(|t| t.boundary()).collect();
+        let edge_boxes: Vec<_> = edges.iter().map(|e| IntRect::with_ab(e.a, e.b)).collect();
+        Self {
+            grid: Grid::new(&boxes),
+            edge_grid: Grid::new(&edge_boxes),
+            triangles,
+            edges,
+        }
+    }
+
+    // the boundary edges are the ones without a neighbor
+    pub(crate) fn with_mesh(mesh: &[IntTriangle]) -> Self {
+        let triangles = mesh.iter().map(|t| t.vertices.map(|v| v.point)).collect();
+        let mut edges = Vec::new();
+        for (index, t) in mesh.iter().enumerate() {
+            for i in 0..3 {
+                if t.is_boundary(i) {
+                    edges.push(BoundaryEdge {
+                        triangle: index,
+                        a: t.vertices[(i + 1) % 3].point,
+                        b: t.vertices[(i + 2) % 3].point,
+                    });
+                }
+            }
+        }
+        Self::new(triangles, edges)
+    }
+
+    /// Returns the number of triangles.
+    #[inline]
+    pub fn triangles_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Locates a point, the result is the same as in
+    /// [`IntTriangulation::locate_points`].
+    pub fn locate_point(&self, p: IntPoint) -> PointLocationInTriangulation {
+        let mut result = PointLocationInTriangulation::Outside;
+        if !self.grid.contains(p) {
+            return result;
+        }
+
+        let cell = self.grid.cell(p.x, p.y);
+        for &index in self.grid.cell_items(cell) {
+            let location = self.triangles[index].locate_point(p);
+            add_location(&mut result, TriangleIndex::new(index), location);
+        }
+
+        result
+    }
+
+    /// Returns the sorted indices of the triangles that intersect or touch `rect`.
+    pub fn triangles_in_rect(&self, rect: &IntRect) -> Vec<TriangleIndex> {
+        self.candidates(rect)
+            .into_iter()
+            .filter(|&index| is_triangle_overlap_rect(&self.triangles[index], rect))
+            .map(TriangleIndex::new)
+            .collect()
+    }
+
+    /// Returns the sorted indices of the triangles that are not farther than
+    /// `radius` from `center`.
+    pub fn triangles_in_radius(&self, center: IntPoint, radius: u32) -> Vec<TriangleIndex> {
+        let r = radius as i64;
+        let rect = IntRect::new(
+            clamp_i32(center.x as i64 - r),
+            clamp_i32(center.x as i64 + r),
+            clamp_i32(center.y as i64 - r),
+            clamp_i32(center.y as i64 + r),
+        );
+        let sqr_radius = r as u64 * r as u64;
+
+        self.candidates(&rect)
+            .into_iter()
+            .filter(|&index| sqr_distance_to_triangle(center, &self.triangles[index]) <= sqr_radius)
+            .map(TriangleIndex::new)
+            .collect()
+    }
+
+    /// Returns the point of the mesh that is the closest to `p`, `None` for an empty mesh.
+    ///
+    /// A point inside the mesh is returned as is, a point outside is moved to
+    /// the nearest boundary edge. The exact projection is rounded to a grid point
+    /// that still lies in the triangle of that edge.
+    pub fn closest_point(&self, p: IntPoint) -> Option<IntClosestPoint> {
+        if let Some(triangle) = self.locate_point(p).triangle() {
+            return Some(IntClosestPoint {
+                triangle,
+                point: p,
+                sqr_distance: 0,
+            });
+        }
+
+        let edge = &self.edges[self.nearest_edge(p.x as i64, p.y as i64)?];
+        let triangle = &self.triangles[edge.triangle];
+        let point = projection_candidates(p, edge.a, edge.b)
+            .into_iter()
+            .filter(|&q| triangle.locate_point(q) != PointLocationInTriangle::Outside)
+            .min_by_key(|&q| p.sqr_distance(q))
+            .unwrap_or(edge.a);
+
+        Some(IntClosestPoint {
+            triangle: TriangleIndex::new(edge.triangle),
+            point,
+            sqr_distance: p.sqr_distance(point) as u64,
+        })
+    }
+
+    // the boundary edge nearest to (x, y), (x, y) may lie anywhere in i64
+    pub(crate) fn nearest_edge(&self, x: i64, y: i64) -> Option<usize> {
+        if self.edges.is_empty() {
+            return None;
+        }
+        self.edge_grid.nearest(x, y, |index| {
+            let edge = &self.edges[index];
+            sqr_distance_to_segment_i128(x, y, edge.a, edge.b)
+        })
+    }
+
+    // triangles whose cells overlap the rect, sorted and unique
+    fn candidates(&self, rect: &IntRect) -> Vec<usize> {
+        if !self.grid.is_overlap(rect) {
+            return Vec::new();
+        }
+
+        let mut result = Vec::new();
+        for cell in self.grid.cells(rect) {
+            result.extend_from_slice(self.grid.cell_items(cell));
+        }
+        result.sort_unstable();
+        result.dedup();
+
+        result
+    }
+}
+
+impl Grid {
+    pub(crate) fn new(boxes: &[IntRect]) -> Self {
+        let Some(first) = boxes.first() else {
+            return Self {
+                rect: IntRect::new(0, -1, 0, -1),
+                cell_size: 1,
+                columns: 1,
+                rows: 1,
+                offsets: vec![0, 0],
+                items: Vec::new(),
+            };
+        };
+
+        let mut rect = first.clone();
+        for b in boxes.iter() {
+            rect = IntRect::with_rects(&rect, b);
+        }
+
+        // about one cell per item, a thin rect still gets at most n cells per side
+        let n = boxes.len() as u64;
+        let width = rect.max_x as i64 - rect.min_x as i64;
+        let height = rect.max_y as i64 - rect.min_y as i64;
+        let by_area = (width as u64 * height as u64 / n).isqrt() as i64;
+        let by_side = width.max(height) / n as i64;
+        let cell_size = by_area.max(by_side).max(1);
+
+        let columns = (width / cell_size) as usize + 1;
+        let rows = (height / cell_size) as usize + 1;
+
+        let mut grid = Self {
+            rect,
+            cell_size,
+            columns,
+            rows,
+            offsets: Vec::new(),
+            items: Vec::new(),
+        };
+
+        // count the items of every cell, then fill the cells in item order
+        let mut offsets = vec![0; columns * rows + 1];
+        for b in boxes.iter() {
+            for cell in grid.cells(b) {
+                offsets[cell + 1] += 1;
+            }
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        let mut ends = offsets.clone();
+        let mut items = vec![0; offsets[columns * rows]];
+        for (index, b) in boxes.iter().enumerate() {
+            for cell in grid.cells(b) {
+                items[ends[cell]] = index;
+                ends[cell] += 1;
+            }
+        }
+
+        grid.offsets = offsets;
+        grid.items = items;
+        grid
+    }
+
+    // the item nearest to (x, y) by `sqr_distance`, the cells are visited in growing
+    // rings around the start cell until no unseen item can be closer, (x, y) may lie
+    // anywhere in i64, a point off the grid starts from the nearest cell
+    pub(crate) fn nearest<F: FnMut(usize) -> u128>(
+        &self,
+        x: i64,
+        y: i64,
+        mut sqr_distance: F,
+    ) -> Option<usize> {
+        let (c0, r0) = self.column_row(x, y);
+        let (c0, r0) = (c0 as i64, r0 as i64);
+        let (columns, rows) = (self.columns as i64, self.rows as i64);
+
+        let mut best = None;
+        let mut best_distance = u128::MAX;
+        let mut radius = 0;
+        loop {
+            let mut visit = |column: i64, row: i64| {
+                let cell = row as usize * self.columns + column as usize;
+                for &index in self.cell_items(cell) {
+                    let distance = sqr_distance(index);
+                    // the smaller index wins a tie, whatever cell it is met in first
+                    if distance < best_distance
+                        || distance == best_distance && best.is_some_and(|b| index < b)
+                    {
+                        best_distance = distance;
+                        best = Some(index);
+                    }
+                }
+            };
+
+            // the cells of the ring around the start cell
+            let (min_column, max_column) = ((c0 - radius).max(0), (c0 + radius).min(columns - 1));
+            for row in (r0 - radius).max(0)..=(r0 + radius).min(rows - 1) {
+                if (row - r0).abs() == radius {
+                    for column in min_column..=max_column {
+                        visit(column, row);
+                    }
+                } else {
+                    for column in [c0 - radius, c0 + radius] {
+                        if (0..columns).contains(&column) {
+                            visit(column, row);
+                        }
+                    }
+                }
+            }
+
+            // the unseen edges are beyond the open sides of the visited block,
+            // in i128 for a point far off the grid
+            let (px, py) = (x as i128, y as i128);
+            let (min_x, min_y) = (self.rect.min_x as i128, self.rect.min_y as i128);
+            let size = self.cell_size as i128;
+            let (c_min, c_max) = ((c0 - radius) as i128, (c0 + radius) as i128);
+            let (r_min, r_max) = ((r0 - radius) as i128, (r0 + radius) as i128);
+            let mut bound = i128::MAX;
+            if c0 - radius > 0 {
+                bound = bound.min(px - (min_x + c_min * size));
+            }
+            if c0 + radius < columns - 1 {
+                bound = bound.min(min_x + (c_max + 1) * size - px);
+            }
+            if r0 - radius > 0 {
+                bound = bound.min(py - (min_y + r_min * size));
+            }
+            if r0 + radius < rows - 1 {
+                bound = bound.min(min_y + (r_max + 1) * size - py);
+            }
+
+            if bound == i128::MAX {
+                return best;
+            }
+            let bound = bound.max(0) as u128;
+            if best.is_some() && bound.saturating_mul(bound) > best_distance {
+                return best;
+            }
+            radius += 1;
+        }
+    }
+
+    // cells overlapping the rect, the rect must overlap the grid
+    #[inline]
+    fn cells(&self, rect: &IntRect) -> impl Iterator<Item = usize> + '_ {
+        let (x0, y0) = self.column_row(rect.min_x as i64, rect.min_y as i64);
+        let (x1, y1) = self.column_row(rect.max_x as i64, rect.max_y as i64);
+        (y0..=y1).flat_map(move |row| (x0..=x1).map(move |column| row * self.columns + column))
+    }
+
+    #[inline]
+    fn is_overlap(&self, rect: &IntRect) -> bool {
+        rect.min_x <= self.rect.max_x
+            && rect.max_x >= self.rect.min_x
+            && rect.min_y <= self.rect.max_y
+            && rect.max_y >= self.rect.min_y
+    }
+
+    #[inline]
+    fn contains(&self, p: IntPoint) -> bool {
+        self.rect.min_x <= p.x
+            && p.x <= self.rect.max_x
+            && self.rect.min_y <= p.y
+            && p.y <= self.rect.max_y
+    }
+
+    // the cell of (x, y) clamped to the grid, in i128 for a point far off the grid
+    #[inline]
+    fn column_row(&self, x: i64, y: i64) -> (usize, usize) {
+        let cell_size = self.cell_size as i128;
+        let column = (x as i128 - self.rect.min_x as i128).div_euclid(cell_size);
+        let row = (y as i128 - self.rect.min_y as i128).div_euclid(cell_size);
+        (
+            column.clamp(0, self.columns as i128 - 1) as usize,
+            row.clamp(0, self.rows as i128 - 1) as usize,
+        )
+    }
+
+    #[inline]
+    fn cell(&self, x: i32, y: i32) -> usize {
+        let (column, row) = self.column_row(x as i64, y as i64);
+        row * self.columns + column
+    }
+
+    #[inline]
+    fn cell_items(&self, cell: usize) -> &[usize] {
+        &self.items[self.offsets[cell]..self.offsets[cell + 1]]
+    }
+}
+
+#[inline]
+fn clamp_i32(value: i64) -> i32 {
+    value.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+// separating axis test: the boxes overlap and no triangle edge has the whole rect outside
+fn is_triangle_overlap_rect(triangle: &[IntPoint; 3], rect: &IntRect) -> bool {
+    let boundary = triangle.boundary();
+    if boundary.max_x < rect.min_x
+        || boundary.min_x > rect.max_x
+        || boundary.max_y < rect.min_y
+        || boundary.min_y > rect.max_y
+    {
+        return false;
+    }
+
+    let corners = [
+        IntPoint::new(rect.min_x, rect.min_y),
+        IntPoint::new(rect.max_x, rect.min_y),
+        IntPoint::new(rect.max_x, rect.max_y),
+        IntPoint::new(rect.min_x, rect.max_y),
+    ];
+
+    let [a, b, c] = *triangle;
+    let sign = Triangle::area_two_point(a, b, c).signum();
+    for [p0, p1] in [[a, b], [b, c], [c, a]] {
+        // outside the edge means the opposite side to the triangle
+        if corners
+            .iter()
+            .all(|&q| Triangle::area_two_point(p0, p1, q).signum() == -sign)
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn sqr_distance_to_triangle(p: IntPoint, triangle: &[IntPoint; 3]) -> u64 {
+    if triangle.locate_point(p) != PointLocationInTriangle::Outside {
+        return 0;
+    }
+
+    let [a, b, c] = *triangle;
+    sqr_distance_to_segment(p, [a, b])
+        .min(sqr_distance_to_segment(p, [b, c]))
+        .min(sqr_distance_to_segment(p, [c, a]))
+}
+
+// every edge used by a single triangle
+fn boundary_edges<I: IndexType>(indices: &[I], points: &[IntPoint]) -> Vec<BoundaryEdge> {
+    let mut edges = Vec::with_capacity(indices.len());
+    for (index, t) in indices.chunks_exact(3).enumerate() {
+        let t = [t[0].into_usize(), t[1].into_usize(), t[2].into_usize()];
+        for i in 0..3 {
+            let (a, b) = (t[i], t[(i + 1) % 3]);
+            edges.push((a.min(b), a.max(b), index, a, b));
+        }
+    }
+    edges.sort_unstable_by_key(|e| (e.0, e.1));
+
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < edges.len() {
+        let mut j = i + 1;
+        while j < edges.len() && edges[j].0 == edges[i].0 && edges[j].1 == edges[i].1 {
+            j += 1;
+        }
+        if j == i + 1 {
+            let (_, _, triangle, a, b) = edges[i];
+            result.push(BoundaryEdge {
+                triangle,
+                a: points[a],
+                b: points[b],
+            });
+        }
+        i = j;
+    }
+
+    result.sort_unstable_by_key(|e| e.triangle);
+    result
+}
+
+pub(crate) fn sqr_distance_to_segment_i128(x: i64, y: i64, a: IntPoint, b: IntPoint) -> u128 {
+    let (abx, aby) = (b.x as i128 - a.x as i128, b.y as i128 - a.y as i128);
+    let (apx, apy) = (x as i128 - a.x as i128, y as i128 - a.y as i128);
+    let t = abx * apx + aby * apy;
+    let sqr_ab = abx * abx + aby * aby;
+    // a square fits i128 for any i64 point, the sum of two may not
+    if t <= 0 || sqr_ab == 0 {
+        return (apx * apx) as u128 + (apy * apy) as u128;
+    }
+    if t >= sqr_ab {
+        let (bpx, bpy) = (apx - abx, apy - aby);
+        return (bpx * bpx) as u128 + (bpy * bpy) as u128;
+    }
+
+    // |ab x ap|^2 / |ab|^2, a far point is rounded to the length |ab x ap| / |ab|
+    let cross = (abx * apy - aby * apx).unsigned_abs();
+    let sqr_ab = sqr_ab as u128;
+    match cross.checked_mul(cross) {
+        Some(sqr_cross) => sqr_cross / sqr_ab,
+        None => {
+            let distance = cross / sqr_ab.isqrt();
+            distance.saturating_mul(distance)
+        }
+    }
+}
+
+// grid points around the exact projection of p on ab and the edge ends
+fn projection_candidates(p: IntPoint, a: IntPoint, b: IntPoint) -> [IntPoint; 6] {
+    let (abx, aby) = (b.x as i128 - a.x as i128, b.y as i128 - a.y as i128);
+    let (apx, apy) = (p.x as i128 - a.x as i128, p.y as i128 - a.y as i128);
+    let sqr_ab = (abx * abx + aby * aby).max(1);
+    let t = (abx * apx + aby * apy).clamp(0, sqr_ab);
+
+    let x = a.x as i128 * sqr_ab + abx * t;
+    let y = a.y as i128 * sqr_ab + aby * t;
+    let (x0, y0) = (x.div_euclid(sqr_ab) as i32, y.div_euclid(sqr_ab) as i32);
+    let (x1, y1) = (x0.saturating_add(1), y0.saturating_add(1));
+
+    [
+        IntPoint::new(x0, y0),
+        IntPoint::new(x1, y0),
+        IntPoint::new(x0, y1),
+        IntPoint::new(x1, y1),
+        a,
+        b,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::locator::{IntPointInTriangleLocator, PointLocationInTriangle};
+    use crate::int::prepared::{
+        is_triangle_overlap_rect, sqr_distance_to_segment_i128, sqr_distance_to_triangle,
+        IntClosestPoint,
+    };
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::int::triangulation::IntTriangulation;
+    use crate::location::{PointLocationInTriangulation, TriangleIndex};
+    use crate::tessellation::circumcenter::RefineOptions;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_float::int::rect::IntRect;
+    use rand::RngExt;
+
+    #[test]
+    fn test_0() {
+        let triangulation = IntTriangulation {
+            points: vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(4, 0),
+                IntPoint::new(4, 4),
+                IntPoint::new(0, 4),
+            ],
+            indices: vec![0_u16, 1, 2, 0, 2, 3],
+        };
+        let prepared = triangulation.prepare();
+        assert_eq!(prepared.triangles_count(), 2);
+
+        let points = vec![
+            IntPoint::new(3, 1),
+            IntPoint::new(1, 3),
+            IntPoint::new(2, 2),
+            IntPoint::new(2, 0),
+            IntPoint::new(0, 0),
+            IntPoint::new(5, 1),
+        ];
+        let expected = triangulation.locate_points(&points);
+        for (p, location) in points.iter().zip(expected.iter()) {
+            assert_eq!(&prepared.locate_point(*p), location);
+        }
+
+        let t0 = TriangleIndex::new(0);
+        let t1 = TriangleIndex::new(1);
+
+        // below the diagonal, touching it, outside
+        assert_eq!(
+            prepared.triangles_in_rect(&IntRect::new(3, 4, 0, 1)),
+            vec![t0]
+        );
+        assert_eq!(
+            prepared.triangles_in_rect(&IntRect::new(2, 3, 0, 2)),
+            vec![t0, t1]
+        );
+        assert!(prepared
+            .triangles_in_rect(&IntRect::new(5, 6, 0, 4))
+            .is_empty());
+
+        assert_eq!(
+            prepared.triangles_in_radius(IntPoint::new(4, -1), 1),
+            vec![t0]
+        );
+        assert_eq!(
+            prepared.triangles_in_radius(IntPoint::new(-1, 5), 2),
+            vec![t1]
+        );
+        assert_eq!(
+            prepared.triangles_in_radius(IntPoint::new(6, 6), 3),
+            vec![t0, t1]
+        );
+        assert!(prepared
+            .triangles_in_radius(IntPoint::new(6, 6), 2)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_1() {
+        let prepared = IntTriangulation::<u16>::default().prepare();
+        assert_eq!(
+            prepared.locate_point(IntPoint::new(0, 0)),
+            PointLocationInTriangulation::Outside
+        );
+        assert!(prepared
+            .triangles_in_rect(&IntRect::new(-5, 5, -5, 5))
+            .is_empty());
+        assert!(prepared
+            .triangles_in_radius(IntPoint::new(0, 0), 10)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_2() {
+        // a square with a square hole
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(12, 0),
+                IntPoint::new(12, 12),
+                IntPoint::new(0, 12),
+            ],
+            vec![
+                IntPoint::new(4, 4),
+                IntPoint::new(4, 8),
+                IntPoint::new(8, 8),
+                IntPoint::new(8, 4),
+            ],
+        ];
+        let raw = shape.triangulate();
+        let prepared = raw.prepare();
+        assert_eq!(prepared.edges.len(), 8);
+        assert_eq!(raw.to_triangulation::<u16>().prepare().edges.len(), 8);
+
+        let closest = |x, y| prepared.closest_point(IntPoint::new(x, y)).unwrap();
+
+        let inside = closest(2, 3);
+        assert_eq!(inside.point, IntPoint::new(2, 3));
+        assert_eq!(inside.sqr_distance, 0);
+
+        assert_eq!(closest(-3, 2).point, IntPoint::new(0, 2));
+        assert_eq!(closest(-3, 2).sqr_distance, 9);
+        assert_eq!(closest(-3, -4).point, IntPoint::new(0, 0));
+        assert_eq!(closest(-3, -4).sqr_distance, 25);
+        assert_eq!(closest(100, 6).point, IntPoint::new(12, 6));
+
+        // from the hole to its border
+        let hole = closest(6, 7);
+        assert_eq!(hole.point, IntPoint::new(6, 8));
+        assert_eq!(hole.sqr_distance, 1);
+
+        for c in [inside, hole, closest(-3, 2), closest(100, 6)] {
+            let triangle = prepared.triangles[c.triangle.index()];
+            assert_ne!(
+                triangle.locate_point(c.point),
+                PointLocationInTriangle::Outside
+            );
+        }
+
+        assert_eq!(
+            IntTriangulation::<u16>::default()
+                .prepare()
+                .closest_point(IntPoint::new(0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_3() {
+        // the projection is rounded to a grid point inside the triangle
+        let triangulation = IntTriangulation {
+            points: vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(7, 3),
+                IntPoint::new(0, 10),
+            ],
+            indices: vec![0_u16, 1, 2],
+        };
+        let prepared = triangulation.prepare();
+        let IntClosestPoint { point, .. } = prepared.closest_point(IntPoint::new(10, 10)).unwrap();
+        assert_ne!(
+            prepared.triangles[0].locate_point(point),
+            PointLocationInTriangle::Outside
+        );
+        assert!(point.sqr_distance(IntPoint::new(10, 10)) <= 60);
+    }
+
+    #[test]
+    fn test_4() {
+        // a fine mesh with a hole, the ring search visits many cells
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(1000, 0),
+                IntPoint::new(1000, 1000),
+                IntPoint::new(0, 1000),
+            ],
+            vec![
+                IntPoint::new(300, 300),
+                IntPoint::new(300, 700),
+                IntPoint::new(700, 700),
+                IntPoint::new(700, 300),
+            ],
+        ];
+        let delaunay = shape
+            .triangulate()
+            .into_delaunay()
+            .refine_with_sizing(|_| 50, &RefineOptions::with_max_points(usize::MAX))
+            .0;
+        let prepared = delaunay.prepare();
+        assert!(prepared.edges.len() > 100);
+
+        let mut rng = rand::rng();
+        for _ in 0..500 {
+            let p = IntPoint::new(
+                rng.random_range(-3000..=3000),
+                rng.random_range(-3000..=3000),
+            );
+            let (x, y) = (p.x as i64, p.y as i64);
+            let min = prepared
+                .edges
+                .iter()
+                .map(|e| sqr_distance_to_segment_i128(x, y, e.a, e.b))
+                .min()
+                .unwrap();
+            let edge = &prepared.edges[prepared.nearest_edge(x, y).unwrap()];
+            assert_eq!(sqr_distance_to_segment_i128(x, y, edge.a, edge.b), min);
+        }
+    }
+
+    #[test]
+    fn test_5() {
+        // a query far off the grid, up to the ends of i64
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+        let prepared = contour.triangulate().prepare();
+
+        let far = [i64::MIN, -1_000_000_000_000, 5, 1_000_000_000_000, i64::MAX];
+        for x in far {
+            for y in far {
+                let min = prepared
+                    .edges
+                    .iter()
+                    .map(|e| sqr_distance_to_segment_i128(x, y, e.a, e.b))
+                    .min()
+                    .unwrap();
+                let edge = &prepared.edges[prepared.nearest_edge(x, y).unwrap()];
+                assert_eq!(sqr_distance_to_segment_i128(x, y, edge.a, edge.b), min);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_0() {
+        let mut rng = rand::rng();
+        for _ in 0..200 {
+            let path: Vec<_> = (0..8)
+                .map(|_| IntPoint::new(rng.random_range(-20..=20), rng.random_range(-20..=20)))
+                .collect();
+            let shapes = path.simplify(FillRule::NonZero, IntOverlayOptions::default());
+            let Some(first) = shapes.first() else {
+                continue;
+            };
+
+            let triangulation = first
+                .triangulate()
+                .into_delaunay()
+                .into_triangulation::<u32>();
+            let prepared = triangulation.prepare();
+            let triangles: Vec<_> = triangulation.triangles().collect();
+
+            let points: Vec<_> = (-22..=22)
+                .flat_map(|x| (-22..=22).map(move |y| IntPoint::new(x, y)))
+                .collect();
+            let expected = triangulation.locate_points(&points);
+            for (p, location) in points.iter().zip(expected.iter()) {
+                assert_eq!(&prepared.locate_point(*p), location);
+            }
+
+            for _ in 0..20 {
+                let x = rng.random_range(-25..=25);
+                let y = rng.random_range(-25..=25);
+                let rect = IntRect::new(
+                    x,
+                    x + rng.random_range(0..10),
+                    y,
+                    y + rng.random_range(0..10),
+                );
+                let expected: Vec<_> = (0..triangles.len())
+                    .filter(|&i| is_triangle_overlap_rect(&triangles[i], &rect))
+                    .map(TriangleIndex::new)
+                    .collect();
+                assert_eq!(prepared.triangles_in_rect(&rect), expected);
+
+                let center = IntPoint::new(x, y);
+                let radius = rng.random_range(0..10);
+                let expected: Vec<_> = (0..triangles.len())
+                    .filter(|&i| {
+                        sqr_distance_to_triangle(center, &triangles[i]) <= (radius * radius) as u64
+                    })
+                    .map(TriangleIndex::new)
+                    .collect();
+                assert_eq!(prepared.triangles_in_radius(center, radius), expected);
+            }
+
+            for _ in 0..20 {
+                let p = IntPoint::new(rng.random_range(-60..=60), rng.random_range(-60..=60));
+                let closest = prepared.closest_point(p).unwrap();
+                let triangle = prepared.triangles[closest.triangle.index()];
+                assert_ne!(
+                    triangle.locate_point(closest.point),
+                    PointLocationInTriangle::Outside
+                );
+
+                if closest.sqr_distance == 0 {
+                    assert_eq!(closest.point, p);
+                    continue;
+                }
+                let min = prepared
+                    .edges
+                    .iter()
+                    .map(|e| sqr_distance_to_segment_i128(p.x as i64, p.y as i64, e.a, e.b))
+                    .min()
+                    .unwrap();
+                let edge = &prepared.edges[prepared.nearest_edge(p.x as i64, p.y as i64).unwrap()];
+                assert_eq!(
+                    sqr_distance_to_segment_i128(p.x as i64, p.y as i64, edge.a, edge.b),
+                    min
+                );
+                assert!(closest.sqr_distance as u128 >= min);
+            }
+        }
+    }
+}
@@ -0,0 +1,655 @@
+use crate::int::triangulation::ShapeRange;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_shape::flat::buffer::FlatContoursBuffer;
+use i_overlay::i_shape::int::shape::{IntContour, IntShape};
+
+/// The origin of an output vertex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexOrigin {
+    /// The vertex is an input point: `shapes[shape][contour][vertex]`.
+    ///
+    /// After validation a point is only matched with an input point whose input edges
+    /// still run along the contour there. Equal input points of different contours
+    /// are told apart this way, equal points within one output shape share a mesh vertex
+    /// and report the first of them.
+    Input {
+        shape: usize,
+        contour: usize,
+        vertex: usize,
+    },
+    /// The vertex is the Steiner point with the given index.
+    Steiner(usize),
+    /// The vertex was created while resolving self-intersections.
+    ///
+    /// This includes a crossing that lands on an input position but does not follow
+    /// the edges of that input point.
+    Intersection,
+    /// The vertex was inserted by mesh refinement (e.g. circumcenter tessellation).
+    Refinement,
+}
+
+/// Maps output vertices back to the input geometry.
+///
+/// The map is filled with the input before validation and then resolves output points
+/// by their exact coordinates. When several input points share the same position,
+/// the first one wins, and a new point that lands exactly on an input position is
+/// reported as that input point. Input points take precedence over Steiner points.
+///
+/// [`IntTriangulator`](crate::int::triangulator::IntTriangulator) does not rely on the
+/// position alone: it resolves every validated contour together with its edges and
+/// keeps the output shapes apart, see [`VertexOrigin::Input`].
+///
+/// # Example
+/// ```
+/// use i_triangle::int::provenance::{ProvenanceMap, VertexOrigin};
+/// use i_triangle::int::triangulatable::IntTriangulatable;
+/// use i_overlay::i_float::int::point::IntPoint;
+///
+/// let contour = vec![
+///     IntPoint::new(0, 0),
+///     IntPoint::new(10, 0),
+///     IntPoint::new(10, 10),
+///     IntPoint::new(0, 10),
+/// ];
+/// let steiner = [IntPoint::new(5, 5)];
+/// let raw = contour.triangulate_with_steiner_points(&steiner);
+///
+/// let mut map = ProvenanceMap::default();
+/// map.set_with_contour(&contour);
+/// map.add_steiner_points(&steiner);
+///
+/// let origins = map.resolve(raw.points());
+/// assert!(origins.contains(&VertexOrigin::Steiner(0)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProvenanceMap {
+    items: Vec<Item>,
+    is_sorted: bool,
+}
+
+// an input point together with its closest distinct neighbors in the contour
+#[derive(Debug, Clone, Copy)]
+struct Item {
+    point: IntPoint,
+    origin: VertexOrigin,
+    prev: IntPoint,
+    next: IntPoint,
+}
+
+impl ProvenanceMap {
+    /// Clears the map and fills it with the points of a single contour.
+    pub fn set_with_contour(&mut self, contour: &[IntPoint]) {
+        self.clear();
+        self.add_contour(0, 0, contour);
+    }
+
+    /// Clears the map and fills it with the contours of a single shape.
+    pub fn set_with_shape(&mut self, shape: &[IntContour]) {
+        self.clear();
+        for (contour_index, contour) in shape.iter().enumerate() {
+            self.add_contour(0, contour_index, contour);
+        }
+    }
+
+    /// Clears the map and fills it with a list of shapes.
+    pub fn set_with_shapes(&mut self, shapes: &[IntShape]) {
+        self.clear();
+        for (shape_index, shape) in shapes.iter().enumerate() {
+            for (contour_index, contour) in shape.iter().enumerate() {
+                self.add_contour(shape_index, contour_index, contour);
+            }
+        }
+    }
+
+    /// Clears the map and fills it with a flat buffer.
+    ///
+    /// A flat buffer has no shape grouping: `shape` is always `0` and `contour`
+    /// is the index of the range in the buffer.
+    pub fn set_with_flat(&mut self, flat: &FlatContoursBuffer) {
+        self.clear();
+        for (contour_index, range) in flat.ranges.iter().enumerate() {
+            self.add_contour(0, contour_index, &flat.points[range.clone()]);
+        }
+    }
+
+    /// Adds Steiner points, `Steiner(i)` refers to `points[i]`.
+    pub fn add_steiner_points(&mut self, points: &[IntPoint]) {
+        self.is_sorted = false;
+        self.items
+            .extend(points.iter().enumerate().map(|(i, &p)| Item {
+                point: p,
+                origin: VertexOrigin::Steiner(i),
+                prev: p,
+                next: p,
+            }));
+    }
+
+    /// Returns the origin of every point.
+    ///
+    /// Points that are not found in the map are reported as [`VertexOrigin::Intersection`].
+    pub fn resolve(&mut self, points: &[IntPoint]) -> Vec<VertexOrigin> {
+        let mut origins = Vec::with_capacity(points.len());
+        self.resolve_into(points, points.len(), &mut origins);
+        origins
+    }
+
+    /// Returns the origin of every point of a refined mesh.
+    ///
+    /// `count` is the number of vertices before refinement, all later vertices
+    /// are reported as [`VertexOrigin::Refinement`].
+    pub fn resolve_refined(&mut self, points: &[IntPoint], count: usize) -> Vec<VertexOrigin> {
+        let mut origins = Vec::with_capacity(points.len());
+        self.resolve_into(points, count, &mut origins);
+        origins
+    }
+
+    /// Writes the origin of every point into `origins`, see [`resolve_refined`](Self::resolve_refined).
+    pub fn resolve_into(
+        &mut self,
+        points: &[IntPoint],
+        count: usize,
+        origins: &mut Vec<VertexOrigin>,
+    ) {
+        self.sort();
+
+        origins.clear();
+        origins.extend(points.iter().enumerate().map(|(i, &p)| {
+            if i >= count {
+                return VertexOrigin::Refinement;
+            }
+            match self.items.get(self.first_index(p)) {
+                Some(item) if item.point == p => item.origin,
+                _ => VertexOrigin::Intersection,
+            }
+        }));
+    }
+
+    // resolves a closed validated path, an input point is only taken if one of its
+    // input edges runs along one of the path edges at that point, so repeated input
+    // positions and new points that land on an input position are told apart
+    pub(crate) fn resolve_path_into(&mut self, path: &[IntPoint], origins: &mut Vec<VertexOrigin>) {
+        self.sort();
+
+        origins.clear();
+        let n = path.len();
+        origins.extend(path.iter().enumerate().map(|(i, &p)| {
+            let a = path[(i + n - 1) % n];
+            let b = path[(i + 1) % n];
+            self.items[self.first_index(p)..]
+                .iter()
+                .take_while(|item| item.point == p)
+                .find(|item| {
+                    matches!(item.origin, VertexOrigin::Input { .. })
+                        && [item.prev, item.next]
+                            .iter()
+                            .any(|&q| is_same_direction(p, q, a) || is_same_direction(p, q, b))
+                })
+                .map_or(VertexOrigin::Intersection, |item| item.origin)
+        }));
+    }
+
+    // keeps only the hole contours of the input, sorted for `contours_at`
+    pub(crate) fn set_with_holes(&mut self, shapes: &[IntShape]) {
+        self.clear();
+        for (shape_index, shape) in shapes.iter().enumerate() {
+            for (contour_index, contour) in shape.iter().enumerate().skip(1) {
+                self.add_contour(shape_index, contour_index, contour);
+            }
+        }
+        self.sort();
+    }
+
+    // the input contours that pass through `p`, the map must be sorted
+    pub(crate) fn contours_at(&self, p: IntPoint) -> impl Iterator<Item = usize> + '_ {
+        debug_assert!(self.is_sorted || self.items.is_empty());
+        self.items[self.first_index(p)..]
+            .iter()
+            .take_while(move |item| item.point == p)
+            .filter_map(|item| match item.origin {
+                VertexOrigin::Input { contour, .. } => Some(contour),
+                _ => None,
+            })
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    #[inline]
+    fn first_index(&self, p: IntPoint) -> usize {
+        self.items.partition_point(|item| item.point < p)
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.items.clear();
+        self.is_sorted = false;
+    }
+
+    #[inline]
+    pub(crate) fn add_contour(&mut self, shape: usize, contour: usize, points: &[IntPoint]) {
+        self.is_sorted = false;
+        self.items
+            .extend(points.iter().enumerate().map(|(vertex, &p)| Item {
+                point: p,
+                origin: VertexOrigin::Input {
+                    shape,
+                    contour,
+                    vertex,
+                },
+                prev: distinct_neighbor(points, vertex, points.len() - 1),
+                next: distinct_neighbor(points, vertex, 1),
+            }));
+    }
+
+    // stable sort keeps the insertion order for equal points
+    #[inline]
+    fn sort(&mut self) {
+        if self.is_sorted {
+            return;
+        }
+        self.items.sort_by_key(|item| item.point);
+        self.is_sorted = true;
+    }
+}
+
+// the first point that differs from `points[index]`, walking by `step`
+#[inline]
+fn distinct_neighbor(points: &[IntPoint], index: usize, step: usize) -> IntPoint {
+    let n = points.len();
+    let p = points[index];
+    let mut i = index;
+    for _ in 1..n {
+        i = (i + step) % n;
+        if points[i] != p {
+            return points[i];
+        }
+    }
+    p
+}
+
+// true if `q` and `r` lie on the same ray leaving `p`
+#[inline]
+fn is_same_direction(p: IntPoint, q: IntPoint, r: IntPoint) -> bool {
+    let (ux, uy) = (q.x as i128 - p.x as i128, q.y as i128 - p.y as i128);
+    let (vx, vy) = (r.x as i128 - p.x as i128, r.y as i128 - p.y as i128);
+    ux * vy == uy * vx && ux * vx + uy * vy > 0
+}
+
+// the origins of the triangulated contours, grouped by output shape
+#[derive(Default)]
+pub(crate) struct ShapeOrigins {
+    items: Vec<(IntPoint, VertexOrigin)>,
+    // `items[offsets[k]..offsets[k + 1]]` belong to the output shape `k`
+    offsets: Vec<usize>,
+    path: Vec<VertexOrigin>,
+}
+
+impl ShapeOrigins {
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.items.clear();
+        self.offsets.clear();
+        self.offsets.push(0);
+    }
+
+    // the input reaches the triangulator unchanged, the origins are known by index
+    pub(crate) fn add_input_shape<'a, C>(&mut self, shape: usize, contours: C)
+    where
+        C: Iterator<Item = &'a [IntPoint]>,
+    {
+        for (contour, points) in contours.enumerate() {
+            self.items
+                .extend(points.iter().enumerate().map(|(vertex, &p)| {
+                    (
+                        p,
+                        VertexOrigin::Input {
+                            shape,
+                            contour,
+                            vertex,
+                        },
+                    )
+                }));
+        }
+        self.seal();
+    }
+
+    // the contours of a validated shape, resolved against the input
+    pub(crate) fn add_output_shape<'a, C>(&mut self, map: &mut ProvenanceMap, contours: C)
+    where
+        C: Iterator<Item = &'a [IntPoint]>,
+    {
+        for points in contours {
+            map.resolve_path_into(points, &mut self.path);
+            self.items
+                .extend(points.iter().copied().zip(self.path.iter().copied()));
+        }
+        self.seal();
+    }
+
+    // the triangulator merges equal points of a shape, the first occurrence is kept
+    #[inline]
+    fn seal(&mut self) {
+        if self.offsets.is_empty() {
+            self.offsets.push(0);
+        }
+        let start = self.offsets[self.offsets.len() - 1];
+        self.items[start..].sort_by_key(|item| item.0);
+        self.offsets.push(self.items.len());
+    }
+
+    // appends the origins of the mesh points of the output shape `shape`
+    pub(crate) fn resolve_into(
+        &self,
+        shape: usize,
+        points: &[IntPoint],
+        origins: &mut Vec<VertexOrigin>,
+    ) {
+        let items = match (self.offsets.get(shape), self.offsets.get(shape + 1)) {
+            (Some(&start), Some(&end)) => &self.items[start..end],
+            _ => &[],
+        };
+        origins.extend(points.iter().map(|&p| {
+            let index = items.partition_point(|item| item.0 < p);
+            match items.get(index) {
+                Some(item) if item.0 == p => item.1,
+                _ => VertexOrigin::Intersection,
+            }
+        }));
+    }
+}
+
+// collects the input shapes of every output shape from its vertex origins
+pub(crate) fn fill_sources(ranges: &mut [ShapeRange], origins: &[VertexOrigin]) {
+    for range in ranges.iter_mut() {
+        range.sources.clear();
+        for origin in origins[range.vertices.clone()].iter() {
+            if let VertexOrigin::Input { shape, .. } = *origin {
+                range.sources.push(shape);
+            }
+        }
+        range.sources.sort_unstable();
+        range.sources.dedup();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::diagnostics::ValidationIssue;
+    use crate::int::provenance::{ProvenanceMap, VertexOrigin};
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::int::triangulation::ShapeRange;
+    use crate::int::triangulator::IntTriangulator;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::path::IntPath;
+    use i_overlay::i_shape::int::shape::IntShape;
+    use rand::RngExt;
+
+    #[test]
+    fn test_0() {
+        let shapes = vec![
+            vec![vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(10, 0),
+                IntPoint::new(10, 10),
+            ]],
+            vec![vec![
+                IntPoint::new(20, 0),
+                IntPoint::new(30, 0),
+                IntPoint::new(10, 10),
+            ]],
+        ];
+
+        let mut map = ProvenanceMap::default();
+        map.set_with_shapes(&shapes);
+        map.add_steiner_points(&[IntPoint::new(5, 2), IntPoint::new(0, 0)]);
+
+        let origins = map.resolve_refined(
+            &[
+                IntPoint::new(10, 10),
+                IntPoint::new(30, 0),
+                IntPoint::new(5, 2),
+                IntPoint::new(0, 0),
+                IntPoint::new(1, 1),
+                IntPoint::new(2, 2),
+            ],
+            5,
+        );
+
+        assert_eq!(
+            origins,
+            vec![
+                VertexOrigin::Input {
+                    shape: 0,
+                    contour: 0,
+                    vertex: 2
+                },
+                VertexOrigin::Input {
+                    shape: 1,
+                    contour: 0,
+                    vertex: 1
+                },
+                VertexOrigin::Steiner(0),
+                VertexOrigin::Input {
+                    shape: 0,
+                    contour: 0,
+                    vertex: 0
+                },
+                VertexOrigin::Intersection,
+                VertexOrigin::Refinement,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_1() {
+        // a bow tie, the crossing point is new
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 0),
+            IntPoint::new(0, 10),
+        ];
+
+        let mut triangulator = IntTriangulator::<u16>::default();
+        let triangulation = triangulator.triangulate_contour(&contour);
+        assert!(triangulator.vertex_origins().is_empty());
+
+        triangulator.provenance = true;
+        let triangulation_with_origins = triangulator.triangulate_contour(&contour);
+        assert_eq!(triangulation.points, triangulation_with_origins.points);
+        assert_eq!(triangulation.indices, triangulation_with_origins.indices);
+
+        let origins = triangulator.vertex_origins();
+        assert_eq!(origins.len(), triangulation.points.len());
+        for (p, origin) in triangulation.points.iter().zip(origins.iter()) {
+            match *origin {
+                VertexOrigin::Input { vertex, .. } => assert_eq!(contour[vertex], *p),
+                VertexOrigin::Intersection => assert_eq!(*p, IntPoint::new(5, 5)),
+                _ => panic!("unexpected origin"),
+            }
+        }
+        assert!(origins.contains(&VertexOrigin::Intersection));
+    }
+
+    #[test]
+    fn test_2() {
+        // the squares share the corner (10, 10), each copy belongs to its own shape
+        let shapes = vec![
+            vec![vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(10, 0),
+                IntPoint::new(10, 10),
+                IntPoint::new(0, 10),
+            ]],
+            vec![vec![
+                IntPoint::new(10, 10),
+                IntPoint::new(20, 10),
+                IntPoint::new(20, 20),
+                IntPoint::new(10, 20),
+            ]],
+        ];
+
+        let mut triangulator = IntTriangulator::<u32>::default();
+        triangulator.provenance = true;
+        triangulator.diagnostics = true;
+
+        let triangulation = triangulator.triangulate_shapes(&shapes);
+        assert!(triangulator.validation_issues().is_empty());
+        assert_shape_sources(
+            &shapes,
+            &triangulation.points,
+            triangulator.vertex_origins(),
+            triangulator.shape_ranges(),
+        );
+
+        let triangulation = triangulator.uncheck_triangulate_shapes(&shapes);
+        assert_shape_sources(
+            &shapes,
+            &triangulation.points,
+            triangulator.vertex_origins(),
+            triangulator.shape_ranges(),
+        );
+
+        let (_, ranges) = shapes.triangulate_with_shape_ranges();
+        let sources: Vec<_> = ranges.iter().map(|r| r.sources.clone()).collect();
+        assert_eq!(sources, vec![vec![0], vec![1]]);
+    }
+
+    #[test]
+    fn test_3() {
+        // the bow tie crosses itself at (5, 5), where the triangle has a vertex
+        let shapes = vec![
+            vec![vec![
+                IntPoint::new(5, 5),
+                IntPoint::new(5, 9),
+                IntPoint::new(3, 9),
+            ]],
+            vec![vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(10, 10),
+                IntPoint::new(10, 0),
+                IntPoint::new(0, 10),
+            ]],
+        ];
+
+        let mut triangulator = IntTriangulator::<u32>::default();
+        triangulator.provenance = true;
+        triangulator.diagnostics = true;
+
+        let triangulation = triangulator.triangulate_shapes(&shapes);
+        assert_eq!(
+            triangulator.validation_issues(),
+            &[ValidationIssue::SelfIntersection {
+                point: IntPoint::new(5, 5)
+            }]
+        );
+
+        let origins = triangulator.vertex_origins();
+        assert_origins(&shapes, &triangulation.points, origins);
+        for range in triangulator.shape_ranges() {
+            let origin = triangulation.points[range.vertices.clone()]
+                .iter()
+                .zip(origins[range.vertices.clone()].iter())
+                .find(|(p, _)| **p == IntPoint::new(5, 5))
+                .map(|(_, o)| *o)
+                .unwrap();
+            if range.sources == [0] {
+                assert_eq!(
+                    origin,
+                    VertexOrigin::Input {
+                        shape: 0,
+                        contour: 0,
+                        vertex: 0
+                    }
+                );
+            } else {
+                assert_eq!(range.sources, [1]);
+                assert_eq!(origin, VertexOrigin::Intersection);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_0() {
+        let mut triangulator = IntTriangulator::<u32>::default();
+        triangulator.provenance = true;
+        for i in 0..10_000 {
+            triangulator.delaunay = i % 2 == 0;
+            let shapes = vec![vec![random(20, 8), random(10, 4)], vec![random(12, 6)]];
+
+            let triangulation = triangulator.triangulate_shapes(&shapes);
+            assert_origins(
+                &shapes,
+                &triangulation.points,
+                triangulator.vertex_origins(),
+            );
+
+            // unchanged input is resolved by index, every range has a single source
+            let simple = shapes.simplify(FillRule::NonZero, IntOverlayOptions::keep_all_points());
+            let triangulation = triangulator.uncheck_triangulate_shapes(&simple);
+            let origins = triangulator.vertex_origins();
+            assert_origins(&simple, &triangulation.points, origins);
+            for range in triangulator.shape_ranges() {
+                assert_eq!(range.sources.len(), 1);
+                for origin in origins[range.vertices.clone()].iter() {
+                    match *origin {
+                        VertexOrigin::Input { shape, .. } => assert_eq!(shape, range.sources[0]),
+                        _ => panic!("unexpected origin"),
+                    }
+                }
+            }
+        }
+    }
+
+    fn assert_origins(shapes: &[IntShape], points: &[IntPoint], origins: &[VertexOrigin]) {
+        assert_eq!(points.len(), origins.len());
+        for (p, origin) in points.iter().zip(origins.iter()) {
+            match *origin {
+                VertexOrigin::Input {
+                    shape,
+                    contour,
+                    vertex,
+                } => assert_eq!(shapes[shape][contour][vertex], *p),
+                VertexOrigin::Intersection => {}
+                _ => panic!("unexpected origin"),
+            }
+        }
+    }
+
+    fn assert_shape_sources(
+        shapes: &[IntShape],
+        points: &[IntPoint],
+        origins: &[VertexOrigin],
+        ranges: &[ShapeRange],
+    ) {
+        assert_origins(shapes, points, origins);
+        assert_eq!(ranges.len(), shapes.len());
+        for (index, range) in ranges.iter().enumerate() {
+            assert_eq!(range.sources, [index]);
+            for origin in origins[range.vertices.clone()].iter() {
+                assert!(matches!(*origin, VertexOrigin::Input { shape, .. } if shape == index));
+            }
+        }
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
@@ -1,12 +1,20 @@
+use crate::int::binder::SteinerInference;
+use crate::int::constraint::ConstraintInference;
 use crate::int::meta::MeshMetaProvider;
 use crate::int::monotone::triangulator::MonotoneTriangulator;
-use crate::int::triangulation::RawIntTriangulation;
+use crate::int::provenance::{fill_sources, ProvenanceMap, ShapeOrigins};
+use crate::int::steiner::{SteinerLayoutInference, SteinerOutcome};
+use crate::int::triangulation::{RawIntTriangulation, ShapeRange};
 use crate::int::unchecked::IntUncheckedTriangulatable;
 use crate::int::validation::Validation;
+use alloc::vec;
 use alloc::vec::Vec;
+use core::slice;
 use i_overlay::core::simplify::Simplify;
 use i_overlay::i_float::int::point::IntPoint;
 use i_overlay::i_shape::int::shape::{IntContour, IntShape, IntShapes};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 pub(super) struct ShapesSolver;
 pub(super) struct ShapeSolver;
@@ -15,17 +23,36 @@ pub(super) struct ContourSolver;
 impl ShapesSolver {
     #[inline]
     pub(super) fn triangulate(validation: Validation, shapes: &IntShapes) -> RawIntTriangulation {
-        let shapes = shapes.simplify(validation.fill_rule, validation.options);
-        Self::uncheck_triangulate(&shapes)
+        let simple = shapes.simplify(validation.fill_rule, validation.options);
+        with_holes(Self::uncheck_triangulate_parts(&simple).0, shapes)
     }
 
+    #[inline]
     pub(super) fn uncheck_triangulate(shapes: &IntShapes) -> RawIntTriangulation {
+        with_holes(Self::uncheck_triangulate_parts(shapes).0, shapes)
+    }
+
+    // also returns the input shape of every range, shapes without triangles get no range
+    fn uncheck_triangulate_parts(
+        shapes: &IntShapes,
+    ) -> (RawIntTriangulation, Vec<ShapeRange>, Vec<usize>) {
         if shapes.len() <= 1 {
-            return if let Some(first) = shapes.first() {
-                first.uncheck_triangulate()
+            let raw = if let Some(first) = shapes.first() {
+                ShapeSolver::uncheck_triangulate_with(&mut MonotoneTriangulator::default(), first)
             } else {
-                Default::default()
+                RawIntTriangulation::default()
             };
+            let ranges = if raw.triangles.is_empty() {
+                Vec::new()
+            } else {
+                vec![ShapeRange {
+                    vertices: 0..raw.points.len(),
+                    indices: 0..3 * raw.triangles.len(),
+                    sources: Vec::new(),
+                }]
+            };
+            let range_shapes = vec![0; ranges.len()];
+            return (raw, ranges, range_shapes);
         }
 
         let mut triangles_count = 0;
@@ -38,25 +65,83 @@ impl ShapesSolver {
 
         let mut triangles = Vec::with_capacity(triangles_count);
         let mut points = Vec::with_capacity(points_count);
+        let mut ranges = Vec::with_capacity(shapes.len());
+        let mut range_shapes = Vec::with_capacity(shapes.len());
 
-        let mut iter = shapes.iter();
-        if let Some(first) = iter.next() {
-            let mut raw_0 = first.uncheck_triangulate();
-            triangles.append(&mut raw_0.triangles);
-            points.append(&mut raw_0.points);
+        // every rayon job gets its own triangulator, the parts are joined in input order
+        #[cfg(feature = "rayon")]
+        let parts: Vec<RawIntTriangulation> = shapes
+            .par_iter()
+            .map_init(
+                MonotoneTriangulator::default,
+                ShapeSolver::uncheck_triangulate_with,
+            )
+            .collect();
 
-            for shape in iter {
-                let points_offset = points.len();
-                let triangle_offset = triangles.len();
-                let mut raw_i = shape.uncheck_triangulate();
-                raw_i.shift(points_offset, triangle_offset);
+        #[cfg(not(feature = "rayon"))]
+        let mut triangulator = MonotoneTriangulator::default();
+        #[cfg(not(feature = "rayon"))]
+        let parts = shapes
+            .iter()
+            .map(|shape| ShapeSolver::uncheck_triangulate_with(&mut triangulator, shape));
 
-                triangles.append(&mut raw_i.triangles);
-                points.append(&mut raw_i.points);
+        for (shape_index, mut raw_i) in parts.into_iter().enumerate() {
+            let points_offset = points.len();
+            let triangle_offset = triangles.len();
+            if raw_i.triangles.is_empty() {
+                continue;
             }
+            raw_i.shift(points_offset, triangle_offset);
+
+            triangles.append(&mut raw_i.triangles);
+            points.append(&mut raw_i.points);
+
+            ranges.push(ShapeRange {
+                vertices: points_offset..points.len(),
+                indices: 3 * triangle_offset..3 * triangles.len(),
+                sources: Vec::new(),
+            });
+            range_shapes.push(shape_index);
         }
 
-        RawIntTriangulation::new(triangles, points)
+        (
+            RawIntTriangulation::new(triangles, points),
+            ranges,
+            range_shapes,
+        )
+    }
+
+    #[inline]
+    pub(super) fn triangulate_with_shape_ranges(
+        validation: Validation,
+        shapes: &IntShapes,
+    ) -> (RawIntTriangulation, Vec<ShapeRange>) {
+        let mut map = ProvenanceMap::default();
+        map.set_with_shapes(shapes);
+        let simple = shapes.simplify(validation.fill_rule, validation.options);
+        let (raw, ranges) = Self::uncheck_triangulate_with_sources(&simple, &mut map);
+        (with_holes(raw, shapes), ranges)
+    }
+
+    // maps every output shape back to the input shapes
+    #[inline]
+    fn uncheck_triangulate_with_sources(
+        shapes: &IntShapes,
+        map: &mut ProvenanceMap,
+    ) -> (RawIntTriangulation, Vec<ShapeRange>) {
+        let (raw, mut ranges, range_shapes) = Self::uncheck_triangulate_parts(shapes);
+
+        let mut shape_origins = ShapeOrigins::default();
+        for shape in shapes.iter() {
+            shape_origins.add_output_shape(map, shape.iter().map(|c| c.as_slice()));
+        }
+        let mut origins = Vec::with_capacity(raw.points.len());
+        for (range, &shape) in ranges.iter().zip(range_shapes.iter()) {
+            shape_origins.resolve_into(shape, &raw.points[range.vertices.clone()], &mut origins);
+        }
+
+        fill_sources(&mut ranges, &origins);
+        (raw, ranges)
     }
 
     #[inline]
@@ -65,18 +150,62 @@ impl ShapesSolver {
         shapes: &IntShapes,
         points: &[IntPoint],
     ) -> RawIntTriangulation {
-        shapes
-            .simplify(validation.fill_rule, validation.options)
-            .uncheck_triangulate_with_steiner_points(points)
+        let simple = shapes.simplify(validation.fill_rule, validation.options);
+        with_holes(
+            Self::triangulate_with_steiner_layout(&simple, points),
+            shapes,
+        )
     }
 
+    // points on boundary edges split the edges, points outside are skipped
+    #[inline]
+    pub(super) fn triangulate_with_steiner_layout(
+        shapes: &IntShapes,
+        points: &[IntPoint],
+    ) -> RawIntTriangulation {
+        let layout = shapes.layout_steiner_points(points);
+        Self::uncheck_triangulate_with_groups(&layout.shapes, &layout.groups)
+    }
+
+    #[inline]
+    pub(super) fn triangulate_with_steiner_outcomes(
+        validation: Validation,
+        shapes: &IntShapes,
+        points: &[IntPoint],
+    ) -> (RawIntTriangulation, Vec<SteinerOutcome>) {
+        let simple = shapes.simplify(validation.fill_rule, validation.options);
+        let (raw, outcomes) = Self::uncheck_triangulate_with_steiner_outcomes(&simple, points);
+        (with_holes(raw, shapes), outcomes)
+    }
+
+    pub(super) fn uncheck_triangulate_with_steiner_outcomes(
+        shapes: &IntShapes,
+        points: &[IntPoint],
+    ) -> (RawIntTriangulation, Vec<SteinerOutcome>) {
+        let layout = shapes.layout_steiner_points(points);
+        let raw = Self::uncheck_triangulate_with_groups(&layout.shapes, &layout.groups);
+        let outcomes = layout.outcomes(points, &raw);
+        (raw, outcomes)
+    }
+
+    #[inline]
     pub(super) fn uncheck_triangulate_with_steiner_points(
         shapes: &IntShapes,
         groups: &[Vec<IntPoint>],
+    ) -> RawIntTriangulation {
+        with_holes(
+            Self::uncheck_triangulate_with_groups(shapes, groups),
+            shapes,
+        )
+    }
+
+    fn uncheck_triangulate_with_groups(
+        shapes: &IntShapes,
+        groups: &[Vec<IntPoint>],
     ) -> RawIntTriangulation {
         if shapes.len() <= 1 {
             return if let Some(first) = shapes.first() {
-                first.uncheck_triangulate_with_steiner_points(&groups[0])
+                ShapeSolver::uncheck_triangulate_with_points(first, &groups[0])
             } else {
                 Default::default()
             };
@@ -119,19 +248,70 @@ impl ShapesSolver {
 
         RawIntTriangulation::new(triangles, points)
     }
+
+    #[inline]
+    pub(super) fn triangulate_with_constraints(
+        validation: Validation,
+        shapes: &IntShapes,
+        segments: &[[IntPoint; 2]],
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        let simple = shapes.simplify(validation.fill_rule, validation.options);
+        let (raw, missed) = Self::uncheck_triangulate_with_constraints(&simple, segments);
+        (with_holes(raw, shapes), missed)
+    }
+
+    pub(super) fn uncheck_triangulate_with_constraints(
+        shapes: &IntShapes,
+        segments: &[[IntPoint; 2]],
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        if segments.is_empty() {
+            return (Self::uncheck_triangulate_parts(shapes).0, Vec::new());
+        }
+
+        let layout = shapes.layout_constraints(segments);
+        let groups = layout.shapes.group_by_shapes(&layout.steiner_points);
+
+        let mut raw = Self::uncheck_triangulate_with_groups(&layout.shapes, &groups);
+        let missed = raw.insert_constraints(&layout.segments);
+
+        (raw, missed)
+    }
 }
 
 impl ShapeSolver {
     #[inline]
     pub(super) fn triangulate(validation: Validation, shape: &IntShape) -> RawIntTriangulation {
         let shapes = shape.simplify(validation.fill_rule, validation.options);
-        ShapesSolver::uncheck_triangulate(&shapes)
+        let raw = ShapesSolver::uncheck_triangulate_parts(&shapes).0;
+        with_holes(raw, slice::from_ref(shape))
+    }
+
+    #[inline]
+    pub(super) fn triangulate_with_shape_ranges(
+        validation: Validation,
+        shape: &IntShape,
+    ) -> (RawIntTriangulation, Vec<ShapeRange>) {
+        let mut map = ProvenanceMap::default();
+        map.set_with_shape(shape);
+        let shapes = shape.simplify(validation.fill_rule, validation.options);
+        let (raw, ranges) = ShapesSolver::uncheck_triangulate_with_sources(&shapes, &mut map);
+        (with_holes(raw, slice::from_ref(shape)), ranges)
     }
 
     #[inline]
     pub(super) fn uncheck_triangulate(shape: &IntShape) -> RawIntTriangulation {
+        let raw = Self::uncheck_triangulate_with(&mut MonotoneTriangulator::default(), shape);
+        with_holes(raw, slice::from_ref(shape))
+    }
+
+    // shared by the sequential and the parallel path of `ShapesSolver`
+    #[inline]
+    fn uncheck_triangulate_with(
+        triangulator: &mut MonotoneTriangulator,
+        shape: &IntShape,
+    ) -> RawIntTriangulation {
         let mut raw = RawIntTriangulation::default();
-        MonotoneTriangulator::default().shape_into_net_triangulation(shape, None, &mut raw);
+        triangulator.shape_into_net_triangulation(shape, None, &mut raw);
         raw
     }
 
@@ -141,15 +321,36 @@ impl ShapeSolver {
         shape: &IntShape,
         points: &[IntPoint],
     ) -> RawIntTriangulation {
-        shape
-            .simplify(validation.fill_rule, validation.options)
-            .uncheck_triangulate_with_steiner_points(points)
+        let shapes = shape.simplify(validation.fill_rule, validation.options);
+        let raw = ShapesSolver::triangulate_with_steiner_layout(&shapes, points);
+        with_holes(raw, slice::from_ref(shape))
+    }
+
+    #[inline]
+    pub(super) fn triangulate_with_steiner_outcomes(
+        validation: Validation,
+        shape: &IntShape,
+        points: &[IntPoint],
+    ) -> (RawIntTriangulation, Vec<SteinerOutcome>) {
+        let shapes = shape.simplify(validation.fill_rule, validation.options);
+        let (raw, outcomes) =
+            ShapesSolver::uncheck_triangulate_with_steiner_outcomes(&shapes, points);
+        (with_holes(raw, slice::from_ref(shape)), outcomes)
     }
 
     #[inline]
     pub(super) fn uncheck_triangulate_with_steiner_points(
         shape: &IntShape,
         points: &[IntPoint],
+    ) -> RawIntTriangulation {
+        let raw = Self::uncheck_triangulate_with_points(shape, points);
+        with_holes(raw, slice::from_ref(shape))
+    }
+
+    #[inline]
+    fn uncheck_triangulate_with_points(
+        shape: &IntShape,
+        points: &[IntPoint],
     ) -> RawIntTriangulation {
         if shape.len() <= 1 {
             return if let Some(first) = shape.first() {
@@ -162,6 +363,17 @@ impl ShapeSolver {
         MonotoneTriangulator::default().shape_into_net_triangulation(shape, Some(points), &mut raw);
         raw
     }
+
+    #[inline]
+    pub(super) fn triangulate_with_constraints(
+        validation: Validation,
+        shape: &IntShape,
+        segments: &[[IntPoint; 2]],
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        let shapes = shape.simplify(validation.fill_rule, validation.options);
+        let (raw, missed) = ShapesSolver::uncheck_triangulate_with_constraints(&shapes, segments);
+        (with_holes(raw, slice::from_ref(shape)), missed)
+    }
 }
 
 impl ContourSolver {
@@ -172,6 +384,17 @@ impl ContourSolver {
             .uncheck_triangulate()
     }
 
+    #[inline]
+    pub(super) fn triangulate_with_shape_ranges(
+        validation: Validation,
+        contour: &IntContour,
+    ) -> (RawIntTriangulation, Vec<ShapeRange>) {
+        let mut map = ProvenanceMap::default();
+        map.set_with_contour(contour);
+        let shapes = contour.simplify(validation.fill_rule, validation.options);
+        ShapesSolver::uncheck_triangulate_with_sources(&shapes, &mut map)
+    }
+
     #[inline]
     pub(super) fn uncheck_triangulate(contour: &IntContour) -> RawIntTriangulation {
         if contour.len() < 3 {
@@ -189,9 +412,18 @@ impl ContourSolver {
         contour: &IntContour,
         points: &[IntPoint],
     ) -> RawIntTriangulation {
-        contour
-            .simplify(validation.fill_rule, validation.options)
-            .uncheck_triangulate_with_steiner_points(points)
+        let shapes = contour.simplify(validation.fill_rule, validation.options);
+        ShapesSolver::triangulate_with_steiner_layout(&shapes, points)
+    }
+
+    #[inline]
+    pub(super) fn triangulate_with_steiner_outcomes(
+        validation: Validation,
+        contour: &IntContour,
+        points: &[IntPoint],
+    ) -> (RawIntTriangulation, Vec<SteinerOutcome>) {
+        let shapes = contour.simplify(validation.fill_rule, validation.options);
+        ShapesSolver::uncheck_triangulate_with_steiner_outcomes(&shapes, points)
     }
 
     #[inline]
@@ -211,4 +443,23 @@ impl ContourSolver {
             raw
         }
     }
+
+    #[inline]
+    pub(super) fn triangulate_with_constraints(
+        validation: Validation,
+        contour: &IntContour,
+        segments: &[[IntPoint; 2]],
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        let shapes = contour.simplify(validation.fill_rule, validation.options);
+        ShapesSolver::uncheck_triangulate_with_constraints(&shapes, segments)
+    }
+}
+
+// keeps the input holes in the mesh, `edge_kinds` names the hole loops with them
+#[inline]
+fn with_holes(mut raw: RawIntTriangulation, shapes: &[IntShape]) -> RawIntTriangulation {
+    if shapes.iter().any(|shape| shape.len() > 1) {
+        raw.hole_origins.set_with_holes(shapes);
+    }
+    raw
 }
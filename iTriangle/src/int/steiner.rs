@@ -0,0 +1,276 @@
+use crate::int::binder::{BoundPoint, PointBinding, SteinerInference};
+use crate::int::constraint::EdgePointInsertion;
+use crate::int::triangulation::RawIntTriangulation;
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_shape::int::shape::IntShapes;
+
+/// What happened to a Steiner point during triangulation.
+///
+/// `vertex_index` refers to `RawIntTriangulation::points`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SteinerOutcome {
+    /// The point is inside a shape or on its boundary edge and became a new vertex.
+    Inserted { vertex_index: usize },
+    /// The point coincides with a contour vertex.
+    OnExistingVertex { vertex_index: usize },
+    /// The point is outside all shapes or inside a hole.
+    Outside,
+    /// The point coincides with a previous input point and shares its vertex.
+    Merged { vertex_index: usize },
+}
+
+pub(super) struct SteinerLayout<'a> {
+    pub(super) shapes: Cow<'a, IntShapes>,
+    pub(super) groups: Vec<Vec<IntPoint>>,
+    bound: Vec<BoundPoint>,
+}
+
+pub(super) trait SteinerLayoutInference {
+    fn layout_steiner_points(&self, points: &[IntPoint]) -> SteinerLayout<'_>;
+}
+
+impl SteinerLayoutInference for IntShapes {
+    // inner points are grouped by shapes, points on edges are inserted into contours
+    fn layout_steiner_points(&self, points: &[IntPoint]) -> SteinerLayout<'_> {
+        let bound = self.bind_points(points);
+
+        let mut groups = vec![Vec::new(); self.len()];
+        let mut edge_points = Vec::new();
+        for b in bound.iter() {
+            match b.binding {
+                PointBinding::Inner(shape_index) => groups[shape_index].push(b.point),
+                PointBinding::Edge(e) => edge_points.push(e),
+                PointBinding::Vertex | PointBinding::Outside => {}
+            }
+        }
+
+        // exact points on edges do not move the edges, the contours stay valid
+        let shapes = if edge_points.is_empty() {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(self.insert_edge_points(edge_points))
+        };
+
+        SteinerLayout {
+            shapes,
+            groups,
+            bound,
+        }
+    }
+}
+
+impl SteinerLayout<'_> {
+    pub(super) fn outcomes(
+        &self,
+        points: &[IntPoint],
+        raw: &RawIntTriangulation,
+    ) -> Vec<SteinerOutcome> {
+        let mut vertices: Vec<_> = raw
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (p, i))
+            .collect();
+        vertices.sort_unstable_by_key(|v| v.0);
+
+        let vertex_index = |p: IntPoint| -> Option<usize> {
+            vertices
+                .binary_search_by_key(&p, |v| v.0)
+                .ok()
+                .map(|i| vertices[i].1)
+        };
+
+        let mut is_used = vec![false; self.bound.len()];
+        let mut outcomes = Vec::with_capacity(points.len());
+        for &p in points.iter() {
+            let Ok(i) = self.bound.binary_search_by_key(&p, |b| b.point) else {
+                outcomes.push(SteinerOutcome::Outside);
+                continue;
+            };
+
+            let outcome = match (self.bound[i].binding, vertex_index(p)) {
+                (PointBinding::Outside, _) | (_, None) => SteinerOutcome::Outside,
+                (PointBinding::Vertex, Some(vertex_index)) => {
+                    SteinerOutcome::OnExistingVertex { vertex_index }
+                }
+                (_, Some(vertex_index)) => {
+                    if is_used[i] {
+                        SteinerOutcome::Merged { vertex_index }
+                    } else {
+                        is_used[i] = true;
+                        SteinerOutcome::Inserted { vertex_index }
+                    }
+                }
+            };
+            outcomes.push(outcome);
+        }
+
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::steiner::SteinerOutcome;
+    use crate::int::triangulatable::IntTriangulatable;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::area::Area;
+    use i_overlay::i_shape::int::path::IntPath;
+    use rand::RngExt;
+
+    #[test]
+    fn test_0() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(0, 10),
+        ];
+
+        let points = [
+            IntPoint::new(5, 5),
+            IntPoint::new(5, 0),
+            IntPoint::new(10, 5),
+            IntPoint::new(10, 10),
+            IntPoint::new(20, 5),
+            IntPoint::new(5, 5),
+        ];
+
+        let (raw, outcomes) = contour.triangulate_with_steiner_outcomes(&points);
+        raw.validate();
+        raw.to_triangulation::<u16>().validate(-200);
+
+        assert_eq!(raw.points.len(), 7);
+        assert_eq!(raw.triangles.len(), 6);
+
+        for (i, outcome) in outcomes.iter().enumerate() {
+            match *outcome {
+                SteinerOutcome::Inserted { vertex_index }
+                | SteinerOutcome::OnExistingVertex { vertex_index }
+                | SteinerOutcome::Merged { vertex_index } => {
+                    assert_eq!(raw.points[vertex_index], points[i]);
+                }
+                SteinerOutcome::Outside => {}
+            }
+        }
+
+        assert!(matches!(outcomes[0], SteinerOutcome::Inserted { .. }));
+        assert!(matches!(outcomes[1], SteinerOutcome::Inserted { .. }));
+        assert!(matches!(outcomes[2], SteinerOutcome::Inserted { .. }));
+        assert!(matches!(
+            outcomes[3],
+            SteinerOutcome::OnExistingVertex { .. }
+        ));
+        assert_eq!(outcomes[4], SteinerOutcome::Outside);
+        assert!(matches!(outcomes[5], SteinerOutcome::Merged { .. }));
+    }
+
+    #[test]
+    fn test_1() {
+        // points on hole edges and inside the hole
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(20, 0),
+                IntPoint::new(20, 20),
+                IntPoint::new(0, 20),
+            ],
+            vec![
+                IntPoint::new(5, 5),
+                IntPoint::new(5, 15),
+                IntPoint::new(15, 15),
+                IntPoint::new(15, 5),
+            ],
+        ];
+
+        let points = [
+            IntPoint::new(10, 10),
+            IntPoint::new(5, 10),
+            IntPoint::new(10, 15),
+            IntPoint::new(2, 2),
+        ];
+
+        let (raw, outcomes) = shape.triangulate_with_steiner_outcomes(&points);
+        raw.validate();
+        raw.to_triangulation::<u16>().validate(-600);
+
+        assert_eq!(outcomes[0], SteinerOutcome::Outside);
+        assert!(matches!(outcomes[1], SteinerOutcome::Inserted { .. }));
+        assert!(matches!(outcomes[2], SteinerOutcome::Inserted { .. }));
+        assert!(matches!(outcomes[3], SteinerOutcome::Inserted { .. }));
+        assert_eq!(raw.points.len(), 11);
+    }
+
+    #[test]
+    fn test_random_0() {
+        let mut rng = rand::rng();
+        for _ in 0..20_000 {
+            let shapes = [vec![random(20, 8), random(10, 4)]]
+                .simplify(FillRule::NonZero, IntOverlayOptions::keep_all_points());
+            let area: i64 = shapes.iter().map(|s| s.area_two()).sum();
+
+            let mut points = Vec::new();
+            let mut on_boundary = Vec::new();
+            for shape in shapes.iter() {
+                for path in shape.iter() {
+                    let mut a = *path.last().unwrap();
+                    for &b in path.iter() {
+                        // an exact middle point lies on the edge
+                        if (a.x + b.x) % 2 == 0 && (a.y + b.y) % 2 == 0 {
+                            on_boundary.push(points.len());
+                            points.push(IntPoint::new((a.x + b.x) / 2, (a.y + b.y) / 2));
+                        }
+                        if rng.random_bool(0.2) {
+                            on_boundary.push(points.len());
+                            points.push(b);
+                        }
+                        a = b;
+                    }
+                }
+            }
+            for _ in 0..8 {
+                points.push(IntPoint::new(
+                    rng.random_range(-12..=12),
+                    rng.random_range(-12..=12),
+                ));
+            }
+
+            let (raw, outcomes) = shapes.triangulate_with_steiner_outcomes(&points);
+            raw.validate();
+            assert_eq!(raw.area_two(), area);
+            assert_eq!(outcomes.len(), points.len());
+
+            for (i, outcome) in outcomes.iter().enumerate() {
+                match *outcome {
+                    SteinerOutcome::Inserted { vertex_index }
+                    | SteinerOutcome::OnExistingVertex { vertex_index }
+                    | SteinerOutcome::Merged { vertex_index } => {
+                        assert_eq!(raw.points[vertex_index], points[i]);
+                    }
+                    SteinerOutcome::Outside => assert!(!on_boundary.contains(&i)),
+                }
+            }
+        }
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
@@ -1,12 +1,16 @@
+use crate::int::attribute::{AttributeSource, Lerp};
+use crate::int::error::{check_steiner_points, InputCheck, TriangulationError};
 use crate::int::solver::{ContourSolver, ShapeSolver, ShapesSolver};
-use crate::int::triangulation::RawIntTriangulation;
+use crate::int::steiner::SteinerOutcome;
+use crate::int::triangulation::{RawIntTriangulation, ShapeRange};
+use alloc::vec::Vec;
 use i_overlay::i_float::int::point::IntPoint;
 use i_overlay::i_shape::int::shape::{IntContour, IntShape, IntShapes};
 /// A trait for performing triangulation with default validation settings.
 ///
 /// Provides a simplified interface for converting shapes or contours into triangle meshes.
-/// Internally applies the default [`DisposableTriangulator`] settings:
-/// - [`FillRule::NonZero`]
+/// Internally applies the default [`Validation`](crate::int::validation::Validation) settings:
+/// - [`FillRule::NonZero`](i_overlay::core::fill_rule::FillRule::NonZero)
 /// - Minimum area = `0`
 /// - Orientation = counter-clockwise for outer contours, clockwise for holes
 ///
@@ -19,17 +23,80 @@ use i_overlay::i_shape::int::shape::{IntContour, IntShape, IntShapes};
 /// Returns an [`RawIntTriangulation`] containing vertex indices and point data.
 ///
 /// # Steiner Points
-/// Use [`triangulate_with_steiner_points`] to inject additional internal points during triangulation.
+/// Use [`triangulate_with_steiner_points`](Self::triangulate_with_steiner_points) to inject additional internal points during triangulation.
+///
+/// # Constraints
+/// Use [`triangulate_with_constraints`](Self::triangulate_with_constraints) to force segments to appear as edges of the mesh.
+///
+/// # Attributes
+/// Use [`triangulate_with_attributes`](Self::triangulate_with_attributes) to carry per-vertex values (z, uv, color) into the mesh.
 pub trait IntTriangulatable {
     /// Triangulates the shape(s) with automatic validation and cleanup.
     ///
-    /// Uses the default [`DisposableTriangulator`] (non-zero fill rule, zero area threshold).
+    /// Uses the default [`Validation`](crate::int::validation::Validation) (non-zero fill rule, zero area threshold).
     fn triangulate(&self) -> RawIntTriangulation;
 
     /// Triangulates the shape(s) with inserted Steiner points.
     ///
-    /// Points must lie within the shape's valid interior area (not on edges).
+    /// Points inside the shape become vertices, points lying exactly on a boundary
+    /// edge split the edge. Points outside the shape are skipped.
     fn triangulate_with_steiner_points(&self, points: &[IntPoint]) -> RawIntTriangulation;
+
+    /// Works like [`triangulate_with_steiner_points`](Self::triangulate_with_steiner_points)
+    /// but reports bad input instead of panicking.
+    ///
+    /// # Errors
+    /// Returns [`TriangulationError::EmptyInput`] or [`TriangulationError::CoordinateOutOfRange`]
+    /// for bad shapes, and [`TriangulationError::InvalidSteinerPoint`] for the first Steiner
+    /// point outside [`MAX_COORDINATE`](crate::int::error::MAX_COORDINATE).
+    fn try_triangulate_with_steiner_points(
+        &self,
+        points: &[IntPoint],
+    ) -> Result<RawIntTriangulation, TriangulationError>;
+
+    /// Triangulates the shape(s) with inserted Steiner points and reports what
+    /// happened to each of them.
+    ///
+    /// Works like [`triangulate_with_steiner_points`](Self::triangulate_with_steiner_points);
+    /// the returned outcomes follow the order of `points`.
+    fn triangulate_with_steiner_outcomes(
+        &self,
+        points: &[IntPoint],
+    ) -> (RawIntTriangulation, Vec<SteinerOutcome>);
+
+    /// Triangulates the shape(s) so that every constraint segment becomes a mesh edge.
+    ///
+    /// Segments are clipped by the shape and split at mutual intersections and
+    /// at boundary crossings. The resulting edges are kept by
+    /// [`into_delaunay`](RawIntTriangulation::into_delaunay).
+    ///
+    /// Also returns the segment parts inside the shape that could not be forced into
+    /// the mesh, the mesh around them is left as it was. Parts outside the shape
+    /// are clipped away and not reported.
+    fn triangulate_with_constraints(
+        &self,
+        segments: &[[IntPoint; 2]],
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>);
+
+    /// Triangulates the shape(s) and returns an attribute for every output vertex.
+    ///
+    /// `attributes` hold one value per input point, contour by contour in input order.
+    /// Input vertices keep their value, points created while resolving self-intersections
+    /// get a value interpolated along the input edge they lie on.
+    ///
+    /// # Errors
+    /// Returns [`TriangulationError::AttributeCountMismatch`] if the number of attributes
+    /// differs from the number of input points.
+    fn triangulate_with_attributes<A: Lerp>(
+        &self,
+        attributes: &[A],
+    ) -> Result<(RawIntTriangulation, Vec<A>), TriangulationError>;
+
+    /// Triangulates the shape(s) and returns the range of every output shape.
+    ///
+    /// Validation may merge or split shapes, each [`ShapeRange`] lists the input shapes
+    /// it came from.
+    fn triangulate_with_shape_ranges(&self) -> (RawIntTriangulation, Vec<ShapeRange>);
 }
 
 impl IntTriangulatable for IntContour {
@@ -42,6 +109,48 @@ impl IntTriangulatable for IntContour {
     fn triangulate_with_steiner_points(&self, points: &[IntPoint]) -> RawIntTriangulation {
         ContourSolver::triangulate_with_steiner_points(Default::default(), self, points)
     }
+
+    #[inline]
+    fn try_triangulate_with_steiner_points(
+        &self,
+        points: &[IntPoint],
+    ) -> Result<RawIntTriangulation, TriangulationError> {
+        self.check_input()?;
+        check_steiner_points(points)?;
+        Ok(self.triangulate_with_steiner_points(points))
+    }
+
+    #[inline]
+    fn triangulate_with_steiner_outcomes(
+        &self,
+        points: &[IntPoint],
+    ) -> (RawIntTriangulation, Vec<SteinerOutcome>) {
+        ContourSolver::triangulate_with_steiner_outcomes(Default::default(), self, points)
+    }
+
+    #[inline]
+    fn triangulate_with_constraints(
+        &self,
+        segments: &[[IntPoint; 2]],
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        ContourSolver::triangulate_with_constraints(Default::default(), self, segments)
+    }
+
+    #[inline]
+    fn triangulate_with_attributes<A: Lerp>(
+        &self,
+        attributes: &[A],
+    ) -> Result<(RawIntTriangulation, Vec<A>), TriangulationError> {
+        let mut source = AttributeSource::with_contour(self, attributes)?;
+        let raw = self.triangulate();
+        let attributes = source.interpolate(&raw.points);
+        Ok((raw, attributes))
+    }
+
+    #[inline]
+    fn triangulate_with_shape_ranges(&self) -> (RawIntTriangulation, Vec<ShapeRange>) {
+        ContourSolver::triangulate_with_shape_ranges(Default::default(), self)
+    }
 }
 
 impl IntTriangulatable for IntShape {
@@ -54,6 +163,48 @@ impl IntTriangulatable for IntShape {
     fn triangulate_with_steiner_points(&self, points: &[IntPoint]) -> RawIntTriangulation {
         ShapeSolver::triangulate_with_steiner_points(Default::default(), self, points)
     }
+
+    #[inline]
+    fn try_triangulate_with_steiner_points(
+        &self,
+        points: &[IntPoint],
+    ) -> Result<RawIntTriangulation, TriangulationError> {
+        self.check_input()?;
+        check_steiner_points(points)?;
+        Ok(self.triangulate_with_steiner_points(points))
+    }
+
+    #[inline]
+    fn triangulate_with_steiner_outcomes(
+        &self,
+        points: &[IntPoint],
+    ) -> (RawIntTriangulation, Vec<SteinerOutcome>) {
+        ShapeSolver::triangulate_with_steiner_outcomes(Default::default(), self, points)
+    }
+
+    #[inline]
+    fn triangulate_with_constraints(
+        &self,
+        segments: &[[IntPoint; 2]],
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        ShapeSolver::triangulate_with_constraints(Default::default(), self, segments)
+    }
+
+    #[inline]
+    fn triangulate_with_attributes<A: Lerp>(
+        &self,
+        attributes: &[A],
+    ) -> Result<(RawIntTriangulation, Vec<A>), TriangulationError> {
+        let mut source = AttributeSource::with_shape(self, attributes)?;
+        let raw = self.triangulate();
+        let attributes = source.interpolate(&raw.points);
+        Ok((raw, attributes))
+    }
+
+    #[inline]
+    fn triangulate_with_shape_ranges(&self) -> (RawIntTriangulation, Vec<ShapeRange>) {
+        ShapeSolver::triangulate_with_shape_ranges(Default::default(), self)
+    }
 }
 
 impl IntTriangulatable for IntShapes {
@@ -66,4 +217,46 @@ impl IntTriangulatable for IntShapes {
     fn triangulate_with_steiner_points(&self, points: &[IntPoint]) -> RawIntTriangulation {
         ShapesSolver::triangulate_with_steiner_points(Default::default(), self, points)
     }
+
+    #[inline]
+    fn try_triangulate_with_steiner_points(
+        &self,
+        points: &[IntPoint],
+    ) -> Result<RawIntTriangulation, TriangulationError> {
+        self.check_input()?;
+        check_steiner_points(points)?;
+        Ok(self.triangulate_with_steiner_points(points))
+    }
+
+    #[inline]
+    fn triangulate_with_steiner_outcomes(
+        &self,
+        points: &[IntPoint],
+    ) -> (RawIntTriangulation, Vec<SteinerOutcome>) {
+        ShapesSolver::triangulate_with_steiner_outcomes(Default::default(), self, points)
+    }
+
+    #[inline]
+    fn triangulate_with_constraints(
+        &self,
+        segments: &[[IntPoint; 2]],
+    ) -> (RawIntTriangulation, Vec<[IntPoint; 2]>) {
+        ShapesSolver::triangulate_with_constraints(Default::default(), self, segments)
+    }
+
+    #[inline]
+    fn triangulate_with_attributes<A: Lerp>(
+        &self,
+        attributes: &[A],
+    ) -> Result<(RawIntTriangulation, Vec<A>), TriangulationError> {
+        let mut source = AttributeSource::with_shapes(self, attributes)?;
+        let raw = self.triangulate();
+        let attributes = source.interpolate(&raw.points);
+        Ok((raw, attributes))
+    }
+
+    #[inline]
+    fn triangulate_with_shape_ranges(&self) -> (RawIntTriangulation, Vec<ShapeRange>) {
+        ShapesSolver::triangulate_with_shape_ranges(Default::default(), self)
+    }
 }
@@ -1,11 +1,38 @@
+use crate::advanced::constraint::ConstraintEdges;
 use crate::advanced::delaunay::IntDelaunay;
+use crate::advanced::edge::{EdgeClassification, EdgeKind};
 use crate::geom::triangle::IntTriangle;
+use crate::int::error::{check_points_count, TriangulationError};
+use crate::int::provenance::ProvenanceMap;
 use alloc::vec::Vec;
 use core::iter::FusedIterator;
+use core::ops::Range;
 use i_overlay::i_float::int::point::IntPoint;
 use i_overlay::i_float::triangle::Triangle;
 use i_overlay::i_shape::util::reserve::Reserve;
 
+/// The part of a multi-shape triangulation produced by one validated shape.
+///
+/// Validation may merge or split the input shapes, so one range describes one output
+/// shape, [`sources`](Self::sources) maps it back to the input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShapeRange {
+    /// The shape vertices in `points`.
+    pub vertices: Range<usize>,
+    /// The shape triangles in the flat index list, three indices per triangle.
+    pub indices: Range<usize>,
+    /// The input shapes that contributed vertices to this shape, in ascending order.
+    pub sources: Vec<usize>,
+}
+
+impl ShapeRange {
+    /// Returns the shape triangles as a range of triangle indices.
+    #[inline]
+    pub fn triangles(&self) -> Range<usize> {
+        self.indices.start / 3..self.indices.end / 3
+    }
+}
+
 pub trait IndexType: Copy + Clone + TryFrom<usize> + Default {
     const MAX: usize;
     const ZERO: Self;
@@ -121,7 +148,7 @@ impl<I: IndexType> FusedIterator for IntTriangleIterator<'_, I> {}
 ///
 /// This is the low-level output containing full triangle and vertex data,
 /// including adjacency and vertex indices. It can be converted into a higher-level
-/// `Triangulation` (index buffer + point list) using [`into_triangulation`].
+/// `Triangulation` (index buffer + point list) using [`into_triangulation`](Self::into_triangulation).
 ///
 /// Use this when you need detailed control over topology, neighbor tracking, or
 /// advanced mesh manipulation.
@@ -129,12 +156,20 @@ impl<I: IndexType> FusedIterator for IntTriangleIterator<'_, I> {}
 pub struct RawIntTriangulation {
     pub(crate) triangles: Vec<IntTriangle>,
     pub(crate) points: Vec<IntPoint>,
+    pub(crate) constraints: ConstraintEdges,
+    // the input hole vertices, names the hole loops in `edge_kinds`
+    pub(crate) hole_origins: ProvenanceMap,
 }
 
 impl RawIntTriangulation {
     #[inline]
     pub(super) fn new(triangles: Vec<IntTriangle>, points: Vec<IntPoint>) -> Self {
-        Self { triangles, points }
+        Self {
+            triangles,
+            points,
+            constraints: ConstraintEdges::new(),
+            hole_origins: ProvenanceMap::default(),
+        }
     }
 
     /// Returns true if the triangulation contains no triangles.
@@ -151,6 +186,14 @@ impl RawIntTriangulation {
         &self.points
     }
 
+    /// Returns the edges that were forced into the mesh by constraint segments.
+    ///
+    /// Each edge is a pair of indices into the `points` buffer.
+    #[inline]
+    pub fn constraints(&self) -> &ConstraintEdges {
+        &self.constraints
+    }
+
     /// Returns a flat list of triangle vertex indices (ABC ordering).
     ///
     /// Each triangle contributes 3 indices into the `points` buffer.
@@ -170,6 +213,16 @@ impl RawIntTriangulation {
             .collect()
     }
 
+    /// Returns the [`EdgeKind`] of every triangle edge.
+    ///
+    /// `edge_kinds()[t][i]` describes the edge of triangle `t` opposite its vertex `i`,
+    /// so boundary edges tell outer outlines from holes.
+    #[inline]
+    pub fn edge_kinds(&self) -> Vec<[EdgeKind; 3]> {
+        self.triangles
+            .edge_kinds(&self.constraints, &self.hole_origins)
+    }
+
     /// Converts the int triangulation into a simpler index-based mesh.
     ///
     /// Returns a [`IntTriangulation`] with separate index buffer and point list.
@@ -192,6 +245,26 @@ impl RawIntTriangulation {
         }
     }
 
+    /// Converts the int triangulation into an index-based mesh, or returns
+    /// [`TriangulationError::IndexOverflow`] if `I` cannot address all points.
+    #[inline]
+    pub fn try_into_triangulation<I: IndexType>(
+        self,
+    ) -> Result<IntTriangulation<I>, TriangulationError> {
+        check_points_count::<I>(self.points.len())?;
+        Ok(self.into_triangulation())
+    }
+
+    /// Converts the int triangulation into an index-based mesh, or returns
+    /// [`TriangulationError::IndexOverflow`] if `I` cannot address all points.
+    #[inline]
+    pub fn try_to_triangulation<I: IndexType>(
+        &self,
+    ) -> Result<IntTriangulation<I>, TriangulationError> {
+        check_points_count::<I>(self.points.len())?;
+        Ok(self.to_triangulation())
+    }
+
     #[inline]
     pub(crate) fn shift(&mut self, points_offset: usize, triangle_offset: usize) {
         for t in self.triangles.iter_mut() {
@@ -202,6 +275,7 @@ impl RawIntTriangulation {
             t.neighbors[1] = t.neighbors[1].saturating_add(triangle_offset);
             t.neighbors[2] = t.neighbors[2].saturating_add(triangle_offset);
         }
+        self.constraints.shift(points_offset);
     }
 }
 impl<I: IndexType> IntTriangulation<I> {
@@ -230,6 +304,18 @@ impl<I: IndexType> IntTriangulation<I> {
         self.points.extend_from_slice(&other.points)
     }
 
+    // joins a part built with another index type
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub(crate) fn join_converted<J: IndexType>(&mut self, other: &IntTriangulation<J>) {
+        let points_offset = self.points.len();
+        for &i in other.indices.iter() {
+            self.indices
+                .push(I::try_from(i.into_usize() + points_offset).unwrap_or(I::ZERO));
+        }
+        self.points.extend_from_slice(&other.points)
+    }
+
     /// Iterates over resolved triangle points.
     ///
     /// The iterator walks `indices` in exact triples and yields the matching
@@ -273,9 +359,17 @@ impl<I: IndexType> IntTriangulation<I> {
 
 #[cfg(test)]
 mod tests {
-    use super::IntTriangulation;
+    use super::{IntTriangulation, ShapeRange};
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::int::triangulator::IntTriangulator;
+    use crate::int::unchecked::IntUncheckedTriangulatable;
     use alloc::{vec, vec::Vec};
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::simplify::Simplify;
     use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::path::IntPath;
+    use i_overlay::i_shape::int::shape::IntShapes;
+    use rand::RngExt;
 
     #[test]
     fn triangles_iterates_resolved_points() {
@@ -307,6 +401,227 @@ mod tests {
             ]
         );
     }
+
+    fn square(x: i32, y: i32, size: i32) -> IntPath {
+        vec![
+            IntPoint::new(x, y),
+            IntPoint::new(x + size, y),
+            IntPoint::new(x + size, y + size),
+            IntPoint::new(x, y + size),
+        ]
+    }
+
+    fn assert_ranges(points_count: usize, indices: &[usize], ranges: &[ShapeRange]) {
+        let mut vertices_end = 0;
+        let mut indices_end = 0;
+        for range in ranges.iter() {
+            assert_eq!(range.vertices.start, vertices_end);
+            assert_eq!(range.indices.start, indices_end);
+            vertices_end = range.vertices.end;
+            indices_end = range.indices.end;
+            for &i in indices[range.indices.clone()].iter() {
+                assert!(range.vertices.contains(&i));
+            }
+        }
+        assert_eq!(vertices_end, points_count);
+        assert_eq!(indices_end, indices.len());
+    }
+
+    #[test]
+    fn shape_ranges_merge_shapes() {
+        // the third square overlaps the first one and is merged with it
+        let shapes = vec![
+            vec![square(0, 0, 10)],
+            vec![square(20, 0, 10)],
+            vec![square(5, 5, 10)],
+        ];
+
+        let (raw, ranges) = shapes.triangulate_with_shape_ranges();
+        let indices: Vec<usize> = raw.triangle_indices();
+        assert_ranges(raw.points.len(), &indices, &ranges);
+
+        let mut sources: Vec<_> = ranges.iter().map(|r| r.sources.clone()).collect();
+        sources.sort();
+        assert_eq!(sources, vec![vec![0, 2], vec![1]]);
+        assert_eq!(
+            ranges.iter().map(|r| r.triangles().len()).sum::<usize>(),
+            raw.triangles.len()
+        );
+
+        let mut triangulator = IntTriangulator::<usize>::default();
+        let triangulation = triangulator.triangulate_shapes(&shapes);
+        assert_ranges(
+            triangulation.points.len(),
+            &triangulation.indices,
+            triangulator.shape_ranges(),
+        );
+        assert_eq!(triangulator.shape_ranges().len(), 2);
+        assert!(triangulator
+            .shape_ranges()
+            .iter()
+            .all(|r| r.sources.is_empty()));
+
+        triangulator.provenance = true;
+        triangulator.triangulate_shapes(&shapes);
+        let mut sources: Vec<_> = triangulator
+            .shape_ranges()
+            .iter()
+            .map(|r| r.sources.clone())
+            .collect();
+        sources.sort();
+        assert_eq!(sources, vec![vec![0, 2], vec![1]]);
+    }
+
+    #[test]
+    fn shape_ranges_split_contour() {
+        // a bow tie is split into two shapes
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10, 10),
+            IntPoint::new(10, 0),
+            IntPoint::new(0, 10),
+        ];
+
+        let (raw, ranges) = contour.triangulate_with_shape_ranges();
+        let indices: Vec<usize> = raw.triangle_indices();
+        assert_ranges(raw.points.len(), &indices, &ranges);
+        assert_eq!(ranges.len(), 2);
+        assert!(ranges.iter().all(|r| r.sources == vec![0]));
+
+        let mut triangulator = IntTriangulator::<usize>::default();
+        let triangulation = triangulator.triangulate_contour(&contour);
+        assert_ranges(
+            triangulation.points.len(),
+            &triangulation.indices,
+            triangulator.shape_ranges(),
+        );
+
+        let triangulation = triangulator.triangulate_contour(&square(0, 0, 10));
+        assert_ranges(
+            triangulation.points.len(),
+            &triangulation.indices,
+            triangulator.shape_ranges(),
+        );
+        assert_eq!(triangulator.shape_ranges().len(), 1);
+    }
+
+    #[test]
+    fn shape_ranges_random() {
+        let mut triangulator = IntTriangulator::<usize>::default();
+        triangulator.provenance = true;
+        let mut rng = rand::rng();
+        for _ in 0..2_000 {
+            let shapes: IntShapes = (0..4)
+                .map(|_| {
+                    let x = rng.random_range(-20..=20);
+                    let y = rng.random_range(-20..=20);
+                    vec![square(x, y, rng.random_range(1..=10))]
+                })
+                .collect();
+
+            let (raw, ranges) = shapes.triangulate_with_shape_ranges();
+            let indices: Vec<usize> = raw.triangle_indices();
+            assert_ranges(raw.points.len(), &indices, &ranges);
+            assert!(ranges.iter().all(|r| !r.sources.is_empty()));
+
+            let triangulation = triangulator.triangulate_shapes(&shapes);
+            assert_ranges(
+                triangulation.points.len(),
+                &triangulation.indices,
+                triangulator.shape_ranges(),
+            );
+            assert_eq!(triangulator.shape_ranges(), ranges.as_slice());
+        }
+    }
+
+    #[test]
+    fn shapes_match_joined_shapes() {
+        // the shapes are independent, triangulating them together (in parallel with
+        // the `rayon` feature) gives the same bytes as joining them one by one
+        let mut rng = rand::rng();
+        let mut triangulator = IntTriangulator::<u32>::default();
+        let mut single = IntTriangulator::<u32>::default();
+        for _ in 0..200 {
+            let shapes: IntShapes = (0..rng.random_range(2..12))
+                .map(|_| {
+                    let x = rng.random_range(-40..=40);
+                    let y = rng.random_range(-40..=40);
+                    vec![square(x, y, rng.random_range(1..=10))]
+                })
+                .collect();
+            let shapes = shapes.simplify(FillRule::NonZero, Default::default());
+
+            for (earcut, delaunay) in [(false, false), (true, false), (false, true), (true, true)] {
+                triangulator.earcut = earcut;
+                triangulator.delaunay = delaunay;
+                single.earcut = earcut;
+                single.delaunay = delaunay;
+
+                let triangulation = triangulator.uncheck_triangulate_shapes(&shapes);
+
+                let mut joined = IntTriangulation::<u32>::default();
+                for shape in shapes.iter() {
+                    joined.join(&single.uncheck_triangulate_shape(shape));
+                }
+                assert_eq!(triangulation.points, joined.points);
+                assert_eq!(triangulation.indices, joined.indices);
+            }
+
+            let raw = shapes.uncheck_triangulate();
+            let mut points = Vec::new();
+            let mut triangles = Vec::new();
+            for shape in shapes.iter() {
+                let mut part = shape.uncheck_triangulate();
+                part.shift(points.len(), triangles.len());
+                points.append(&mut part.points);
+                triangles.append(&mut part.triangles);
+            }
+            assert_eq!(raw.points, points);
+            assert_eq!(raw.triangles.len(), triangles.len());
+            for (a, b) in raw.triangles.iter().zip(triangles.iter()) {
+                for (va, vb) in a.vertices.iter().zip(b.vertices.iter()) {
+                    assert_eq!(va.index, vb.index);
+                    assert_eq!(va.point, vb.point);
+                }
+                assert_eq!(a.neighbors, b.neighbors);
+            }
+        }
+    }
+
+    #[test]
+    fn index_type_does_not_need_send() {
+        // a custom index type that can not be sent between threads
+        #[derive(Clone, Copy, Default, Debug, PartialEq)]
+        struct LocalIndex(u32, core::marker::PhantomData<*const ()>);
+
+        impl TryFrom<usize> for LocalIndex {
+            type Error = ();
+            fn try_from(value: usize) -> Result<Self, Self::Error> {
+                u32::try_from(value)
+                    .map(|i| LocalIndex(i, Default::default()))
+                    .map_err(|_| ())
+            }
+        }
+
+        impl super::IndexType for LocalIndex {
+            const MAX: usize = u32::MAX as usize;
+            const ZERO: Self = LocalIndex(0, core::marker::PhantomData);
+            fn add(self, other: Self) -> Self {
+                LocalIndex(self.0 + other.0, Default::default())
+            }
+            fn into_usize(self) -> usize {
+                self.0 as usize
+            }
+        }
+
+        let shapes: IntShapes = vec![vec![square(0, 0, 4)], vec![square(10, 0, 4)]];
+        let triangulation = IntTriangulator::<LocalIndex>::default().triangulate_shapes(&shapes);
+        let expected = IntTriangulator::<u32>::default().triangulate_shapes(&shapes);
+
+        assert_eq!(triangulation.points, expected.points);
+        let indices: Vec<u32> = triangulation.indices.iter().map(|i| i.0).collect();
+        assert_eq!(indices, expected.indices);
+    }
 }
 
 pub(crate) trait IndicesBuilder {
@@ -1,24 +1,42 @@
 use crate::advanced::buffer::DelaunayBuffer;
-use crate::int::earcut::earcut_64::Earcut64;
+use crate::int::diagnostics::{ValidationDiagnostics, ValidationIssue};
+use crate::int::earcut::earcut_wide::{AdaptiveEarcut, EARCUT_MAX_POINTS};
+use crate::int::earcut::holes::EarcutShape;
+use crate::int::error::{check_points_count, InputCheck, TriangulationError};
 use crate::int::monotone::triangulator::MonotoneTriangulator;
-use crate::int::triangulation::{IndexType, IntTriangulation, RawIntTriangulation};
+use crate::int::provenance::{fill_sources, ProvenanceMap, ShapeOrigins, VertexOrigin};
+use crate::int::triangulation::{IndexType, IntTriangulation, RawIntTriangulation, ShapeRange};
 use crate::int::validation::Validation;
+use alloc::vec::Vec;
 use i_overlay::core::fill_rule::FillRule;
 use i_overlay::core::overlay::Overlay;
 use i_overlay::core::solver::Solver;
 use i_overlay::i_float::int::point::IntPoint;
 use i_overlay::i_shape::flat::buffer::FlatContoursBuffer;
+use i_overlay::i_shape::int::count::PointsCount;
 use i_overlay::i_shape::int::shape::{IntContour, IntShape, IntShapes};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 pub struct IntTriangulator<I> {
     pub overlay: Overlay,
     pub fill_rule: FillRule,
     pub earcut: bool,
     pub delaunay: bool,
-    triangulator: MonotoneTriangulator,
+    /// Records the origin of every output vertex, see [`IntTriangulator::vertex_origins`].
+    pub provenance: bool,
+    /// Records what validation changed, see [`IntTriangulator::validation_issues`].
+    pub diagnostics: bool,
+    worker: ShapeWorker,
+    // the parallel path joins the parts directly
+    #[cfg_attr(feature = "rayon", allow(dead_code))]
     shapes_buffer: Option<IntTriangulation<I>>,
-    raw_buffer: Option<RawIntTriangulation>,
-    delaunay_buffer: DelaunayBuffer,
+    provenance_map: ProvenanceMap,
+    shape_origins: ShapeOrigins,
+    origins: Vec<VertexOrigin>,
+    shape_ranges: Vec<ShapeRange>,
+    validation_diagnostics: ValidationDiagnostics,
+    issues: Vec<ValidationIssue>,
 }
 
 impl<I: IndexType> IntTriangulator<I> {
@@ -29,10 +47,16 @@ impl<I: IndexType> IntTriangulator<I> {
             fill_rule: validation.fill_rule,
             earcut: false,
             delaunay: false,
-            triangulator: MonotoneTriangulator::default(),
-            raw_buffer: None,
+            provenance: false,
+            diagnostics: false,
+            worker: ShapeWorker::default(),
             shapes_buffer: None,
-            delaunay_buffer: DelaunayBuffer::default(),
+            provenance_map: ProvenanceMap::default(),
+            shape_origins: ShapeOrigins::default(),
+            origins: Vec::new(),
+            shape_ranges: Vec::new(),
+            validation_diagnostics: ValidationDiagnostics::default(),
+            issues: Vec::new(),
         }
     }
 }
@@ -45,12 +69,38 @@ impl<I: IndexType> Default for IntTriangulator<I> {
 }
 
 impl<I: IndexType> IntTriangulator<I> {
+    /// Returns the range of every output shape of the last triangulation.
+    ///
+    /// Shape sources are only filled if [`provenance`](Self::provenance) is enabled.
+    /// A flat buffer is triangulated at once and always gives a single range.
+    #[inline]
+    pub fn shape_ranges(&self) -> &[ShapeRange] {
+        &self.shape_ranges
+    }
+
+    /// Returns what validation changed in the input of the last triangulation.
+    ///
+    /// The list is empty unless [`diagnostics`](Self::diagnostics) is enabled,
+    /// `uncheck_*` methods skip validation and never report issues.
+    #[inline]
+    pub fn validation_issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    /// Returns the origin of every vertex of the last triangulation.
+    ///
+    /// `vertex_origins()[i]` describes `triangulation.points[i]`. The list is empty
+    /// unless [`provenance`](Self::provenance) is enabled.
+    #[inline]
+    pub fn vertex_origins(&self) -> &[VertexOrigin] {
+        &self.origins
+    }
+
     #[inline]
     pub fn triangulate_contour(&mut self, contour: &IntContour) -> IntTriangulation<I> {
-        match self.overlay.simplify_contour(contour, self.fill_rule) {
-            None => self.uncheck_triangulate_contour(contour),
-            Some(shapes) => self.uncheck_triangulate_shapes(&shapes),
-        }
+        let mut triangulation = Default::default();
+        self.checked_contour_into(contour, &mut triangulation);
+        triangulation
     }
 
     #[inline]
@@ -59,18 +109,14 @@ impl<I: IndexType> IntTriangulator<I> {
         contour: IntContour,
         triangulation: &mut IntTriangulation<I>,
     ) {
-        match self.overlay.simplify_contour(&contour, self.fill_rule) {
-            None => self.uncheck_triangulate_contour_into(&contour, triangulation),
-            Some(shapes) => self.uncheck_triangulate_shapes_into(&shapes, triangulation),
-        }
+        self.checked_contour_into(&contour, triangulation);
     }
 
     #[inline]
     pub fn triangulate_shape(&mut self, shape: &IntShape) -> IntTriangulation<I> {
-        match self.overlay.simplify_shape(shape, self.fill_rule) {
-            None => self.uncheck_triangulate_shape(shape),
-            Some(shapes) => self.uncheck_triangulate_shapes(&shapes),
-        }
+        let mut triangulation = Default::default();
+        self.triangulate_shape_into(shape, &mut triangulation);
+        triangulation
     }
 
     #[inline]
@@ -79,16 +125,22 @@ impl<I: IndexType> IntTriangulator<I> {
         shape: &IntShape,
         triangulation: &mut IntTriangulation<I>,
     ) {
+        self.start(TrackedInput::Shape(shape), true);
         match self.overlay.simplify_shape(shape, self.fill_rule) {
-            None => self.uncheck_triangulate_shape_into(shape, triangulation),
-            Some(shapes) => self.uncheck_triangulate_shapes_into(&shapes, triangulation),
+            None => self.shape_into(shape, triangulation),
+            Some(shapes) => {
+                self.report_shapes(&shapes);
+                self.shapes_into(&shapes, triangulation);
+            }
         }
+        self.finish(triangulation);
     }
 
     #[inline]
     pub fn triangulate_shapes(&mut self, shapes: &IntShapes) -> IntTriangulation<I> {
-        let simple = self.overlay.simplify_shapes(shapes, self.fill_rule);
-        self.uncheck_triangulate_shapes(&simple)
+        let mut triangulation = Default::default();
+        self.triangulate_shapes_into(shapes, &mut triangulation);
+        triangulation
     }
 
     #[inline]
@@ -97,14 +149,18 @@ impl<I: IndexType> IntTriangulator<I> {
         shapes: &IntShapes,
         triangulation: &mut IntTriangulation<I>,
     ) {
+        self.start(TrackedInput::Shapes(shapes), true);
         let simple = self.overlay.simplify_shapes(shapes, self.fill_rule);
-        self.uncheck_triangulate_shapes_into(&simple, triangulation);
+        self.report_shapes(&simple);
+        self.shapes_into(&simple, triangulation);
+        self.finish(triangulation);
     }
 
     #[inline]
     pub fn triangulate_flat(&mut self, flat: &mut FlatContoursBuffer) -> IntTriangulation<I> {
-        self.overlay.simplify_flat_buffer(flat, self.fill_rule);
-        self.uncheck_triangulate_flat(flat)
+        let mut triangulation = Default::default();
+        self.triangulate_flat_into(flat, &mut triangulation);
+        triangulation
     }
 
     #[inline]
@@ -113,8 +169,287 @@ impl<I: IndexType> IntTriangulator<I> {
         flat: &mut FlatContoursBuffer,
         triangulation: &mut IntTriangulation<I>,
     ) {
+        // the buffer is simplified in place, the map must see the original input
+        self.start(TrackedInput::Flat(flat), true);
         self.overlay.simplify_flat_buffer(flat, self.fill_rule);
-        self.uncheck_triangulate_flat_into(flat, triangulation);
+        self.report_flat(flat);
+        self.flat_into(flat, triangulation);
+        self.finish(triangulation);
+    }
+
+    #[inline]
+    fn checked_contour_into(
+        &mut self,
+        contour: &IntContour,
+        triangulation: &mut IntTriangulation<I>,
+    ) {
+        self.start(TrackedInput::Contour(contour), true);
+        match self.overlay.simplify_contour(contour, self.fill_rule) {
+            None => self.contour_into(contour, triangulation),
+            Some(shapes) => {
+                self.report_shapes(&shapes);
+                self.shapes_into(&shapes, triangulation);
+            }
+        }
+        self.finish(triangulation);
+    }
+
+    #[inline]
+    fn mode(&self) -> Mode {
+        Mode {
+            earcut: self.earcut,
+            delaunay: self.delaunay,
+        }
+    }
+
+    #[inline]
+    fn start(&mut self, input: TrackedInput<'_>, validate: bool) {
+        self.clear_results();
+        self.track(input, validate);
+    }
+
+    // the results of the last triangulation, kept until the next one succeeds
+    #[inline]
+    fn clear_results(&mut self) {
+        self.shape_ranges.clear();
+        self.origins.clear();
+        self.issues.clear();
+    }
+
+    #[inline]
+    fn track(&mut self, input: TrackedInput<'_>, validate: bool) {
+        if self.provenance {
+            // the origins of unchanged input, validation replaces them in `report_*`
+            let target = &mut self.shape_origins;
+            target.clear();
+            match input {
+                TrackedInput::Contour(contour) => target.add_input_shape(0, [contour].into_iter()),
+                TrackedInput::Shape(shape) => {
+                    target.add_input_shape(0, shape.iter().map(|c| c.as_slice()))
+                }
+                TrackedInput::Shapes(shapes) => {
+                    for (i, shape) in shapes.iter().enumerate() {
+                        target.add_input_shape(i, shape.iter().map(|c| c.as_slice()));
+                    }
+                }
+                TrackedInput::Flat(flat) => {
+                    target.add_input_shape(0, flat.ranges.iter().map(|r| &flat.points[r.clone()]))
+                }
+            }
+        }
+        if !validate {
+            return;
+        }
+        if self.provenance || self.diagnostics {
+            match input {
+                TrackedInput::Contour(contour) => self.provenance_map.set_with_contour(contour),
+                TrackedInput::Shape(shape) => self.provenance_map.set_with_shape(shape),
+                TrackedInput::Shapes(shapes) => self.provenance_map.set_with_shapes(shapes),
+                TrackedInput::Flat(flat) => self.provenance_map.set_with_flat(flat),
+            }
+        }
+        if self.diagnostics {
+            let target = &mut self.validation_diagnostics;
+            match input {
+                TrackedInput::Contour(contour) => target.set_with_contour(contour),
+                TrackedInput::Shape(shape) => target.set_with_shape(shape),
+                TrackedInput::Shapes(shapes) => target.set_with_shapes(shapes),
+                TrackedInput::Flat(flat) => target.set_with_flat(flat),
+            }
+        }
+    }
+
+    #[inline]
+    fn report_shapes(&mut self, shapes: &IntShapes) {
+        if self.provenance {
+            self.shape_origins.clear();
+            for shape in shapes.iter() {
+                self.shape_origins
+                    .add_output_shape(&mut self.provenance_map, shape.iter().map(|c| c.as_slice()));
+            }
+        }
+        if self.diagnostics {
+            let paths = shapes
+                .iter()
+                .enumerate()
+                .flat_map(|(i, shape)| shape.iter().map(move |c| (i, c.as_slice())));
+            self.validation_diagnostics
+                .collect(&mut self.provenance_map, paths, &mut self.issues);
+        }
+    }
+
+    #[inline]
+    fn report_flat(&mut self, flat: &FlatContoursBuffer) {
+        if self.provenance {
+            self.shape_origins.clear();
+            self.shape_origins.add_output_shape(
+                &mut self.provenance_map,
+                flat.ranges.iter().map(|r| &flat.points[r.clone()]),
+            );
+        }
+        if self.diagnostics {
+            let paths = flat.ranges.iter().map(|r| (0, &flat.points[r.clone()]));
+            self.validation_diagnostics
+                .collect(&mut self.provenance_map, paths, &mut self.issues);
+        }
+    }
+
+    #[inline]
+    fn finish(&mut self, triangulation: &IntTriangulation<I>) {
+        // a single shape is not split by `shapes_into`
+        if self.shape_ranges.is_empty() && !triangulation.indices.is_empty() {
+            self.shape_ranges.push(ShapeRange {
+                vertices: 0..triangulation.points.len(),
+                indices: 0..triangulation.indices.len(),
+                sources: Vec::new(),
+            });
+        }
+
+        if self.provenance {
+            // `shapes_into` resolves every joined shape on its own
+            if self.origins.is_empty() {
+                self.shape_origins
+                    .resolve_into(0, &triangulation.points, &mut self.origins);
+            }
+            fill_sources(&mut self.shape_ranges, &self.origins);
+        }
+    }
+}
+
+impl<I: IndexType> IntTriangulator<I> {
+    /// Works like [`triangulate_contour`](Self::triangulate_contour) but reports
+    /// bad input instead of panicking.
+    ///
+    /// # Errors
+    /// Returns a [`TriangulationError`] if the contour is empty, has a coordinate outside
+    /// [`MAX_COORDINATE`](crate::int::error::MAX_COORDINATE), or the result has more points
+    /// than `I` can address. The same applies to all `try_*` methods below.
+    ///
+    /// On error `triangulation` and the results of the last successful call, such as
+    /// [`shape_ranges`](Self::shape_ranges), are left unchanged.
+    #[inline]
+    pub fn try_triangulate_contour(
+        &mut self,
+        contour: &IntContour,
+    ) -> Result<IntTriangulation<I>, TriangulationError> {
+        let mut triangulation = Default::default();
+        self.try_triangulate_contour_into(contour, &mut triangulation)?;
+        Ok(triangulation)
+    }
+
+    #[inline]
+    pub fn try_triangulate_contour_into(
+        &mut self,
+        contour: &IntContour,
+        triangulation: &mut IntTriangulation<I>,
+    ) -> Result<(), TriangulationError> {
+        contour.check_input()?;
+        match self.overlay.simplify_contour(contour, self.fill_rule) {
+            None => {
+                check_points_count::<I>(contour.len())?;
+                self.start(TrackedInput::Contour(contour), true);
+                self.contour_into(contour, triangulation);
+            }
+            Some(shapes) => {
+                check_points_count::<I>(shapes.points_count())?;
+                self.start(TrackedInput::Contour(contour), true);
+                self.report_shapes(&shapes);
+                self.shapes_into(&shapes, triangulation);
+            }
+        }
+        self.finish(triangulation);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn try_triangulate_shape(
+        &mut self,
+        shape: &IntShape,
+    ) -> Result<IntTriangulation<I>, TriangulationError> {
+        let mut triangulation = Default::default();
+        self.try_triangulate_shape_into(shape, &mut triangulation)?;
+        Ok(triangulation)
+    }
+
+    #[inline]
+    pub fn try_triangulate_shape_into(
+        &mut self,
+        shape: &IntShape,
+        triangulation: &mut IntTriangulation<I>,
+    ) -> Result<(), TriangulationError> {
+        shape.check_input()?;
+        match self.overlay.simplify_shape(shape, self.fill_rule) {
+            None => {
+                check_points_count::<I>(shape.points_count())?;
+                self.start(TrackedInput::Shape(shape), true);
+                self.shape_into(shape, triangulation);
+            }
+            Some(shapes) => {
+                check_points_count::<I>(shapes.points_count())?;
+                self.start(TrackedInput::Shape(shape), true);
+                self.report_shapes(&shapes);
+                self.shapes_into(&shapes, triangulation);
+            }
+        }
+        self.finish(triangulation);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn try_triangulate_shapes(
+        &mut self,
+        shapes: &IntShapes,
+    ) -> Result<IntTriangulation<I>, TriangulationError> {
+        let mut triangulation = Default::default();
+        self.try_triangulate_shapes_into(shapes, &mut triangulation)?;
+        Ok(triangulation)
+    }
+
+    #[inline]
+    pub fn try_triangulate_shapes_into(
+        &mut self,
+        shapes: &IntShapes,
+        triangulation: &mut IntTriangulation<I>,
+    ) -> Result<(), TriangulationError> {
+        shapes.check_input()?;
+        let simple = self.overlay.simplify_shapes(shapes, self.fill_rule);
+        check_points_count::<I>(simple.points_count())?;
+        self.start(TrackedInput::Shapes(shapes), true);
+        self.report_shapes(&simple);
+        self.shapes_into(&simple, triangulation);
+        self.finish(triangulation);
+        Ok(())
+    }
+
+    /// Works like [`triangulate_flat`](Self::triangulate_flat) but reports bad input
+    /// instead of panicking. The buffer is left untouched if the input is rejected
+    /// before validation.
+    #[inline]
+    pub fn try_triangulate_flat(
+        &mut self,
+        flat: &mut FlatContoursBuffer,
+    ) -> Result<IntTriangulation<I>, TriangulationError> {
+        let mut triangulation = Default::default();
+        self.try_triangulate_flat_into(flat, &mut triangulation)?;
+        Ok(triangulation)
+    }
+
+    #[inline]
+    pub fn try_triangulate_flat_into(
+        &mut self,
+        flat: &mut FlatContoursBuffer,
+        triangulation: &mut IntTriangulation<I>,
+    ) -> Result<(), TriangulationError> {
+        flat.check_input()?;
+        // the buffer is simplified in place, the map must see the original input
+        self.track(TrackedInput::Flat(flat), true);
+        self.overlay.simplify_flat_buffer(flat, self.fill_rule);
+        check_points_count::<I>(flat.points.len())?;
+        self.clear_results();
+        self.report_flat(flat);
+        self.flat_into(flat, triangulation);
+        self.finish(triangulation);
+        Ok(())
     }
 }
 
@@ -132,23 +467,15 @@ impl<I: IndexType> IntTriangulator<I> {
         contour: &IntContour,
         triangulation: &mut IntTriangulation<I>,
     ) {
-        if self.delaunay {
-            let mut raw = self.raw_buffer.take().unwrap_or_default();
-            if self.earcut && contour.is_earcut_compatible() {
-                contour.earcut_net_triangulate_into(&mut raw);
-            } else {
-                self.triangulator
-                    .contour_into_net_triangulation(contour, None, &mut raw);
-            }
-            let delaunay = raw.into_delaunay_with_buffer(&mut self.delaunay_buffer);
-            triangulation.fill_with_delaunay(&delaunay);
-            self.raw_buffer = Some(delaunay.into_raw());
-        } else if self.earcut && contour.is_earcut_compatible() {
-            contour.earcut_flat_triangulate_into(triangulation);
-        } else {
-            self.triangulator
-                .contour_into_flat_triangulation(contour, triangulation);
-        }
+        self.start(TrackedInput::Contour(contour), false);
+        self.contour_into(contour, triangulation);
+        self.finish(triangulation);
+    }
+
+    #[inline]
+    fn contour_into(&mut self, contour: &IntContour, triangulation: &mut IntTriangulation<I>) {
+        let mode = self.mode();
+        self.worker.contour_into(mode, contour, triangulation);
     }
 
     #[inline]
@@ -164,22 +491,15 @@ impl<I: IndexType> IntTriangulator<I> {
         shape: &IntShape,
         triangulation: &mut IntTriangulation<I>,
     ) {
-        if shape.len() == 1 {
-            self.uncheck_triangulate_contour_into(&shape[0], triangulation);
-            return;
-        }
+        self.start(TrackedInput::Shape(shape), false);
+        self.shape_into(shape, triangulation);
+        self.finish(triangulation);
+    }
 
-        if self.delaunay {
-            let mut raw = self.raw_buffer.take().unwrap_or_default();
-            self.triangulator
-                .shape_into_net_triangulation(shape, None, &mut raw);
-            let delaunay = raw.into_delaunay_with_buffer(&mut self.delaunay_buffer);
-            triangulation.fill_with_delaunay(&delaunay);
-            self.raw_buffer = Some(delaunay.into_raw());
-        } else {
-            self.triangulator
-                .shape_into_flat_triangulation(shape, triangulation);
-        }
+    #[inline]
+    fn shape_into(&mut self, shape: &IntShape, triangulation: &mut IntTriangulation<I>) {
+        let mode = self.mode();
+        self.worker.shape_into(mode, shape, triangulation);
     }
 
     #[inline]
@@ -195,20 +515,83 @@ impl<I: IndexType> IntTriangulator<I> {
         shapes: &IntShapes,
         triangulation: &mut IntTriangulation<I>,
     ) {
+        self.start(TrackedInput::Shapes(shapes), false);
+        self.shapes_into(shapes, triangulation);
+        self.finish(triangulation);
+    }
+
+    #[inline]
+    fn shapes_into(&mut self, shapes: &IntShapes, triangulation: &mut IntTriangulation<I>) {
         if shapes.len() == 1 {
-            self.uncheck_triangulate_shape_into(&shapes[0], triangulation);
+            self.shape_into(&shapes[0], triangulation);
             return;
         }
 
         triangulation.points.clear();
         triangulation.indices.clear();
 
-        let mut buffer = self.shapes_buffer.take().unwrap_or_default();
-        for shape in shapes.iter() {
-            self.uncheck_triangulate_shape_into(shape, &mut buffer);
-            triangulation.join(&buffer);
+        #[cfg(feature = "rayon")]
+        {
+            // every rayon job gets its own buffers, the parts are joined in input order,
+            // they use `usize` indices so `I` does not have to be `Send`
+            let mode = self.mode();
+            let parts: Vec<IntTriangulation<usize>> = shapes
+                .par_iter()
+                .map_init(ShapeWorker::default, |worker, shape| {
+                    let mut part = IntTriangulation::default();
+                    worker.shape_into(mode, shape, &mut part);
+                    part
+                })
+                .collect();
+            for (shape, part) in parts.iter().enumerate() {
+                self.push_range(shape, triangulation, |t| t.join_converted(part));
+            }
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        {
+            let mut buffer = self.shapes_buffer.take().unwrap_or_default();
+            for (index, shape) in shapes.iter().enumerate() {
+                self.shape_into(shape, &mut buffer);
+                self.join_shape(index, &buffer, triangulation);
+            }
+            self.shapes_buffer = Some(buffer)
+        }
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    #[inline]
+    fn join_shape(
+        &mut self,
+        shape: usize,
+        part: &IntTriangulation<I>,
+        triangulation: &mut IntTriangulation<I>,
+    ) {
+        self.push_range(shape, triangulation, |t| t.join(part));
+    }
+
+    #[inline]
+    fn push_range<F: FnOnce(&mut IntTriangulation<I>)>(
+        &mut self,
+        shape: usize,
+        triangulation: &mut IntTriangulation<I>,
+        join: F,
+    ) {
+        let vertices_start = triangulation.points.len();
+        let indices_start = triangulation.indices.len();
+        join(triangulation);
+        if self.provenance {
+            let points = &triangulation.points[vertices_start..];
+            self.shape_origins
+                .resolve_into(shape, points, &mut self.origins);
+        }
+        if indices_start < triangulation.indices.len() {
+            self.shape_ranges.push(ShapeRange {
+                vertices: vertices_start..triangulation.points.len(),
+                indices: indices_start..triangulation.indices.len(),
+                sources: Vec::new(),
+            });
         }
-        self.shapes_buffer = Some(buffer)
     }
 
     #[inline]
@@ -226,17 +609,106 @@ impl<I: IndexType> IntTriangulator<I> {
         &mut self,
         flat: &FlatContoursBuffer,
         triangulation: &mut IntTriangulation<I>,
+    ) {
+        self.start(TrackedInput::Flat(flat), false);
+        self.flat_into(flat, triangulation);
+        self.finish(triangulation);
+    }
+
+    #[inline]
+    fn flat_into(&mut self, flat: &FlatContoursBuffer, triangulation: &mut IntTriangulation<I>) {
+        let mode = self.mode();
+        self.worker.flat_into(mode, flat, triangulation);
+    }
+}
+
+// the switches of a single shape triangulation
+#[derive(Clone, Copy)]
+struct Mode {
+    earcut: bool,
+    delaunay: bool,
+}
+
+// the buffers reused between shapes
+#[derive(Default)]
+struct ShapeWorker {
+    triangulator: MonotoneTriangulator,
+    raw_buffer: Option<RawIntTriangulation>,
+    delaunay_buffer: DelaunayBuffer,
+}
+
+impl ShapeWorker {
+    #[inline]
+    fn contour_into<I: IndexType>(
+        &mut self,
+        mode: Mode,
+        contour: &IntContour,
+        triangulation: &mut IntTriangulation<I>,
+    ) {
+        if mode.delaunay {
+            let mut raw = self.raw_buffer.take().unwrap_or_default();
+            if mode.earcut && contour.is_earcut_compatible() {
+                contour.adaptive_earcut_net_into(&mut raw);
+            } else {
+                self.triangulator
+                    .contour_into_net_triangulation(contour, None, &mut raw);
+            }
+            let delaunay = raw.into_delaunay_with_buffer(&mut self.delaunay_buffer);
+            triangulation.fill_with_delaunay(&delaunay);
+            self.raw_buffer = Some(delaunay.into_raw());
+        } else if mode.earcut && contour.is_earcut_compatible() {
+            contour.adaptive_earcut_flat_into(triangulation);
+        } else {
+            self.triangulator
+                .contour_into_flat_triangulation(contour, triangulation);
+        }
+    }
+
+    #[inline]
+    fn shape_into<I: IndexType>(
+        &mut self,
+        mode: Mode,
+        shape: &IntShape,
+        triangulation: &mut IntTriangulation<I>,
+    ) {
+        if shape.len() == 1 {
+            self.contour_into(mode, &shape[0], triangulation);
+            return;
+        }
+
+        let is_earcut = mode.earcut && shape.bridged_count() <= EARCUT_MAX_POINTS;
+
+        if mode.delaunay {
+            let mut raw = self.raw_buffer.take().unwrap_or_default();
+            if !(is_earcut && shape.earcut_shape_net_into(&mut raw)) {
+                self.triangulator
+                    .shape_into_net_triangulation(shape, None, &mut raw);
+            }
+            let delaunay = raw.into_delaunay_with_buffer(&mut self.delaunay_buffer);
+            triangulation.fill_with_delaunay(&delaunay);
+            self.raw_buffer = Some(delaunay.into_raw());
+        } else if !(is_earcut && shape.earcut_shape_flat_into(triangulation)) {
+            self.triangulator
+                .shape_into_flat_triangulation(shape, triangulation);
+        }
+    }
+
+    #[inline]
+    fn flat_into<I: IndexType>(
+        &mut self,
+        mode: Mode,
+        flat: &FlatContoursBuffer,
+        triangulation: &mut IntTriangulation<I>,
     ) {
         if flat.is_empty() {
             triangulation.reserve_and_clear(0);
             return;
         }
 
-        if self.delaunay {
+        if mode.delaunay {
             let mut raw = self.raw_buffer.take().unwrap_or_default();
-            if self.earcut && flat.is_earcut_compatible() {
-                flat.as_first_contour()
-                    .earcut_net_triangulate_into(&mut raw);
+            if mode.earcut && flat.is_earcut_compatible() {
+                flat.as_first_contour().adaptive_earcut_net_into(&mut raw);
             } else {
                 self.triangulator
                     .flat_into_net_triangulation(flat, &mut raw);
@@ -244,9 +716,9 @@ impl<I: IndexType> IntTriangulator<I> {
             let delaunay = raw.into_delaunay_with_buffer(&mut self.delaunay_buffer);
             triangulation.fill_with_delaunay(&delaunay);
             self.raw_buffer = Some(delaunay.into_raw());
-        } else if self.earcut && flat.is_earcut_compatible() {
+        } else if mode.earcut && flat.is_earcut_compatible() {
             flat.as_first_contour()
-                .earcut_flat_triangulate_into(triangulation);
+                .adaptive_earcut_flat_into(triangulation);
         } else {
             self.triangulator
                 .flat_into_flat_triangulation(flat, triangulation);
@@ -254,20 +726,29 @@ impl<I: IndexType> IntTriangulator<I> {
     }
 }
 
-trait Earcut64Compatible {
+// the input of a triangulation call, before validation
+#[derive(Clone, Copy)]
+enum TrackedInput<'a> {
+    Contour(&'a [IntPoint]),
+    Shape(&'a [IntContour]),
+    Shapes(&'a [IntShape]),
+    Flat(&'a FlatContoursBuffer),
+}
+
+trait EarcutCompatible {
     fn is_earcut_compatible(&self) -> bool;
 }
 
-impl Earcut64Compatible for FlatContoursBuffer {
+impl EarcutCompatible for FlatContoursBuffer {
     #[inline(always)]
     fn is_earcut_compatible(&self) -> bool {
-        self.is_single_contour() && self.points.len() <= 64
+        self.is_single_contour() && self.points.len() <= EARCUT_MAX_POINTS
     }
 }
 
-impl Earcut64Compatible for [IntPoint] {
+impl EarcutCompatible for [IntPoint] {
     #[inline(always)]
     fn is_earcut_compatible(&self) -> bool {
-        self.len() <= 64
+        self.len() <= EARCUT_MAX_POINTS
     }
 }
@@ -1,4 +1,19 @@
 #![no_std]
+// the upstream tests predate these lints
+#![cfg_attr(
+    test,
+    allow(
+        clippy::assertions_on_constants,
+        clippy::assign_op_pattern,
+        clippy::bool_assert_comparison,
+        clippy::inconsistent_digit_grouping,
+        clippy::items_after_test_module,
+        clippy::manual_range_contains,
+        clippy::map_identity,
+        clippy::needless_borrow,
+        clippy::useless_vec
+    )
+)]
 extern crate alloc;
 
 pub mod advanced;
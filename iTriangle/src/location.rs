@@ -26,3 +26,18 @@ pub enum PointLocationInTriangulation {
     OnInteriorEdge(TriangleIndex, TriangleIndex),
     OnVertex(Vec<TriangleIndex>),
 }
+
+impl PointLocationInTriangulation {
+    /// Returns a triangle that holds the point, the first one if there are several,
+    /// or `None` for [`Outside`](Self::Outside).
+    #[inline]
+    pub fn triangle(&self) -> Option<TriangleIndex> {
+        match self {
+            Self::Outside => None,
+            Self::InsideTriangle(t) | Self::OnExteriorEdge(t) | Self::OnInteriorEdge(t, _) => {
+                Some(*t)
+            }
+            Self::OnVertex(triangles) => triangles.first().copied(),
+        }
+    }
+}
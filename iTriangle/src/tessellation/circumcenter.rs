@@ -1,12 +1,67 @@
 use crate::advanced::bitset::IndexBitSet;
-use crate::advanced::delaunay::{DelaunayRefine, IntDelaunay};
+use crate::advanced::delaunay::IntDelaunay;
 use crate::geom::point::IndexPoint;
 use crate::geom::triangle::{Abc, IntTriangle};
+use crate::int::attribute::{interpolate_refined, Lerp};
+use crate::int::error::{check_attributes_count, TriangulationError};
+use crate::tessellation::split::{extract_on_segment, segment_split_point};
 use alloc::vec::Vec;
 use i_overlay::i_float::float::number::FloatNumber;
 use i_overlay::i_float::int::point::IntPoint;
 use i_overlay::i_float::triangle::Triangle;
 
+/// Stopping criteria of the circumcenter refinement passes.
+///
+/// The default repeats the classic behavior: at most 8 passes, no point limit,
+/// stop once a pass splits 25% of the triangles or fewer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefineOptions {
+    /// The maximum number of passes over the triangles.
+    pub max_passes: usize,
+    /// The maximum number of inserted points.
+    pub max_points: usize,
+    /// Stop once a pass splits this share of the triangles or fewer, `0.0` runs
+    /// until nothing is split.
+    pub convergence_ratio: f64,
+}
+
+impl Default for RefineOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_passes: 8,
+            max_points: usize::MAX,
+            convergence_ratio: 0.25,
+        }
+    }
+}
+
+impl RefineOptions {
+    /// Options without a pass limit: refine until nothing is left to split
+    /// or `max_points` points were inserted.
+    #[inline]
+    pub fn with_max_points(max_points: usize) -> Self {
+        Self {
+            max_passes: usize::MAX,
+            max_points,
+            convergence_ratio: 0.0,
+        }
+    }
+}
+
+/// What a refinement did to the mesh.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RefineReport {
+    /// Number of points added to the mesh.
+    pub inserted_points: usize,
+    /// Number of passes over the triangles.
+    pub passes: usize,
+    /// Number of edge flips made to restore the Delaunay condition.
+    pub flips: usize,
+    /// `true` if no triangle is left that the criterion wants to split.
+    pub is_target_met: bool,
+}
+
 impl IntDelaunay {
     #[inline]
     pub fn refine_with_circumcenters(mut self, min_area: u64) -> Self {
@@ -16,7 +71,11 @@ impl IntDelaunay {
 
     #[inline]
     pub fn refine_with_circumcenters_mut(&mut self, min_area: u64) {
-        self.refine_with_circumcenters_and_selector::<SelectBiggerAngle>(min_area);
+        self.refine_with_circumcenters_and_selector(
+            &MinArea::new(min_area, SelectBiggerAngle),
+            &RefineOptions::default(),
+            None,
+        );
     }
 
     #[inline]
@@ -27,170 +86,377 @@ impl IntDelaunay {
 
     #[inline]
     pub fn refine_with_circumcenters_by_obtuse_angle_mut(&mut self, min_area: u64) {
-        self.refine_with_circumcenters_and_selector::<SelectObtuseAngle>(min_area)
+        self.refine_with_circumcenters_and_selector(
+            &MinArea::new(min_area, SelectObtuseAngle),
+            &RefineOptions::default(),
+            None,
+        );
+    }
+
+    /// Consuming variant of [`refine_with_circumcenters_and_attributes_mut`](Self::refine_with_circumcenters_and_attributes_mut).
+    #[inline]
+    pub fn refine_with_circumcenters_and_attributes<A: Lerp>(
+        mut self,
+        min_area: u64,
+        attributes: &mut Vec<A>,
+    ) -> Result<Self, TriangulationError> {
+        self.refine_with_circumcenters_and_attributes_mut(min_area, attributes)?;
+        Ok(self)
     }
 
-    fn refine_with_circumcenters_and_selector<S: EdgeSelector>(&mut self, min_area: u64) {
-        let two_area = min_area << 1;
+    /// Refines the mesh like [`refine_with_circumcenters_mut`](Self::refine_with_circumcenters_mut)
+    /// and extends `attributes` for every inserted point.
+    ///
+    /// A new attribute is interpolated with barycentric weights from the triangle
+    /// that contains the inserted point.
+    ///
+    /// # Errors
+    /// Returns [`TriangulationError::AttributeCountMismatch`] and leaves the mesh unchanged
+    /// if `attributes` and `points` have different lengths.
+    #[inline]
+    pub fn refine_with_circumcenters_and_attributes_mut<A: Lerp>(
+        &mut self,
+        min_area: u64,
+        attributes: &mut Vec<A>,
+    ) -> Result<(), TriangulationError> {
+        check_attributes_count(self.points.len(), attributes.len())?;
+        let mut parents = Vec::new();
+        self.refine_with_circumcenters_and_selector(
+            &MinArea::new(min_area, SelectBiggerAngle),
+            &RefineOptions::default(),
+            Some(&mut parents),
+        );
+        interpolate_refined(&self.points, &parents, attributes);
+        Ok(())
+    }
 
+    /// Consuming variant of [`refine_with_selector_mut`](Self::refine_with_selector_mut).
+    #[inline]
+    pub fn refine_with_selector<S: EdgeSelector>(mut self, selector: &S) -> Self {
+        self.refine_with_selector_mut(selector);
+        self
+    }
+
+    /// Refines the mesh with circumcenters, the edges to split are picked by `selector`.
+    ///
+    /// Runs the same passes as [`refine_with_circumcenters_mut`](Self::refine_with_circumcenters_mut),
+    /// which is this method with [`SelectBiggerAngle`] limited by a min area.
+    #[inline]
+    pub fn refine_with_selector_mut<S: EdgeSelector>(&mut self, selector: &S) {
+        self.refine_with_circumcenters_and_selector(selector, &RefineOptions::default(), None);
+    }
+
+    /// Refines the mesh like [`refine_with_circumcenters_mut`](Self::refine_with_circumcenters_mut)
+    /// with custom stopping criteria.
+    ///
+    /// # Returns
+    /// A [`RefineReport`] with the mesh statistics.
+    #[inline]
+    pub fn refine_with_options_mut(
+        &mut self,
+        min_area: u64,
+        options: &RefineOptions,
+    ) -> RefineReport {
+        self.refine_with_selector_and_options_mut(
+            &MinArea::new(min_area, SelectBiggerAngle),
+            options,
+        )
+    }
+
+    /// Refines the mesh like [`refine_with_selector_mut`](Self::refine_with_selector_mut)
+    /// with custom stopping criteria.
+    ///
+    /// # Returns
+    /// A [`RefineReport`] with the mesh statistics.
+    #[inline]
+    pub fn refine_with_selector_and_options_mut<S: EdgeSelector>(
+        &mut self,
+        selector: &S,
+        options: &RefineOptions,
+    ) -> RefineReport {
+        self.refine_with_circumcenters_and_selector(selector, options, None)
+    }
+
+    /// Consuming variant of [`refine_to_max_area_mut`](Self::refine_to_max_area_mut).
+    #[inline]
+    pub fn refine_to_max_area(
+        mut self,
+        max_area: u64,
+        max_points: Option<usize>,
+    ) -> (Self, RefineReport) {
+        let report = self.refine_to_max_area_mut(max_area, max_points);
+        (self, report)
+    }
+
+    /// Refines the mesh until every triangle has an area of at most `max_area`.
+    ///
+    /// Unlike [`refine_with_circumcenters_mut`](Self::refine_with_circumcenters_mut)
+    /// there is no pass limit, the loop goes on until the target is met, nothing
+    /// can be split any more, or `max_points` points were inserted.
+    ///
+    /// # Returns
+    /// A [`RefineReport`] telling whether the target was met.
+    #[inline]
+    pub fn refine_to_max_area_mut(
+        &mut self,
+        max_area: u64,
+        max_points: Option<usize>,
+    ) -> RefineReport {
+        let options = RefineOptions::with_max_points(max_points.unwrap_or(usize::MAX));
+        self.refine_with_options_mut(max_area, &options)
+    }
+
+    /// Consuming variant of [`refine_with_sizing_mut`](Self::refine_with_sizing_mut).
+    #[inline]
+    pub fn refine_with_sizing<F: Fn(IntPoint) -> u32>(
+        mut self,
+        sizing: F,
+        options: &RefineOptions,
+    ) -> (Self, RefineReport) {
+        let report = self.refine_with_sizing_mut(sizing, options);
+        (self, report)
+    }
+
+    /// Refines the mesh until every edge is shorter than the local size.
+    ///
+    /// `sizing` returns the max edge length at a point, so the mesh can be dense
+    /// near features and coarse elsewhere. Boundary edges are first sliced at their
+    /// midpoint size like [`SliceContour`](crate::tessellation::split::SliceContour)
+    /// does, then every triangle whose longest edge exceeds the size at its centroid
+    /// is split like in [`refine_with_circumcenters_mut`](Self::refine_with_circumcenters_mut).
+    ///
+    /// The boundary is kept as it is: a boundary edge is only split at grid points that
+    /// lie exactly on it. An edge without such points, e.g. from `(0, 0)` to `(7, 3)`,
+    /// stays whole and its triangle may keep a longer edge than `sizing` asks for.
+    ///
+    /// Sizes below 2 are treated as 2. `options` limits the passes and the inserted
+    /// points, boundary slices included, use [`RefineOptions::with_max_points`] to run
+    /// until nothing is left to split.
+    ///
+    /// # Returns
+    /// A [`RefineReport`] telling whether every triangle fits its size.
+    pub fn refine_with_sizing_mut<F: Fn(IntPoint) -> u32>(
+        &mut self,
+        sizing: F,
+        options: &RefineOptions,
+    ) -> RefineReport {
         let mut bitset = IndexBitSet::with_size(self.triangles.len());
         let mut buffer = Vec::with_capacity(self.triangles.len().max(16));
 
-        let mut iter_counter = 0;
-        let mut split_counter = self.triangles.len();
+        let mut report = RefineReport {
+            inserted_points: self.slice_boundary(&sizing, options.max_points, &mut buffer),
+            ..Default::default()
+        };
+        if !buffer.is_empty() {
+            report.flips += self.fix_triangles(&mut buffer, &mut bitset);
+        }
 
-        // do 8 times or leave if last time we found only 25%
-        while iter_counter < 8 && 4 * split_counter > self.triangles.len() {
+        while report.passes < options.max_passes && report.inserted_points < options.max_points {
             let mut abc_index = 0;
-            split_counter = 0;
-            while abc_index < self.triangles.len() {
+            let mut split_counter = 0;
+            while abc_index < self.triangles.len() && report.inserted_points < options.max_points {
                 let abc = &self.triangles[abc_index];
-                if let Some(t) = self.select_edge_for_refinement::<S>(two_area, abc) {
-                    self.split_triangle(abc_index, t, &mut buffer);
-                    self.triangles.fix_triangles(&mut buffer, &mut bitset);
-                    debug_assert!(buffer.is_empty());
-                    split_counter += 1;
+                if let Some(t) = select_edge_by_size(abc, &sizing) {
+                    if self.split_triangle_inside_boundary(abc_index, t, &mut buffer) {
+                        report.flips += self.fix_triangles(&mut buffer, &mut bitset);
+                        split_counter += 1;
+                        report.inserted_points += 1;
+                    }
                 }
                 abc_index += 1;
             }
 
-            iter_counter += 1;
+            report.passes += 1;
+            if is_converged(split_counter, self.triangles.len(), options) {
+                break;
+            }
         }
-    }
-
-    #[inline]
-    fn select_edge_for_refinement<S: EdgeSelector>(
-        &self,
-        min_area: u64,
-        abc: &IntTriangle,
-    ) -> Option<Abc> {
-        let a = abc.vertices[0].point;
-        let b = abc.vertices[1].point;
-        let c = abc.vertices[2].point;
 
-        let area = Triangle::area_two_point(a, b, c).unsigned_abs();
-        if area <= min_area {
-            return None;
-        }
+        report.is_target_met = self
+            .triangles
+            .iter()
+            .all(|abc| select_edge_by_size(abc, &sizing).is_none());
 
-        S::select(abc)
+        report
     }
 
-    #[inline]
-    fn split_triangle(&mut self, abc_index: usize, abc: Abc, buffer: &mut Vec<usize>) {
-        let pcb_index = abc.v0.neighbor;
-        if pcb_index < self.triangles.len() {
-            buffer.extend_from_slice(&self.split_triangle_with_neighbor(abc_index, abc, pcb_index));
-        } else {
-            buffer.extend_from_slice(&self.split_alone_triangle(abc_index, abc));
+    // returns the number of inserted points, at most `max_points`
+    pub(crate) fn slice_boundary<F: Fn(IntPoint) -> u32>(
+        &mut self,
+        sizing: &F,
+        max_points: usize,
+        buffer: &mut Vec<usize>,
+    ) -> usize {
+        // new triangles are visited too, they may hold the other boundary edges
+        let mut points = Vec::new();
+        let mut count = 0;
+        let mut abc_index = 0;
+        while abc_index < self.triangles.len() {
+            for position in 0..3 {
+                if self.triangles[abc_index].neighbors[position] < self.triangles.len() {
+                    continue;
+                }
+
+                let mut abc = self.triangles[abc_index].abc_by_position(position);
+                let b = abc.v1.vertex.point;
+                let c = abc.v2.vertex.point;
+                let radius = local_size(sizing, middle(b, c)) as u64;
+
+                points.clear();
+                extract_on_segment(b, c, radius, radius * radius, &mut points);
+
+                // every split leaves the rest of the edge in the new triangle amc
+                let mut index = abc_index;
+                for &m in points.iter() {
+                    if count >= max_points || !abc.is_split_valid(m) {
+                        break;
+                    }
+                    let vm = IndexPoint::new(self.points.len(), m);
+                    self.points.push(m);
+                    self.split_edge(index, abc, vm, buffer);
+                    count += 1;
+                    index = self.triangles.len() - 1;
+                    abc = self.triangles[index].abc_by_a();
+                }
+            }
+            abc_index += 1;
         }
+        count
     }
 
-    fn split_triangle_with_neighbor(
+    // like `split_triangle`, but a boundary edge is only split at a grid point on it
+    #[inline]
+    pub(crate) fn split_triangle_inside_boundary(
         &mut self,
         abc_index: usize,
         abc: Abc,
-        pcb_index: usize,
-    ) -> [usize; 4] {
-        let p = abc.circumscribed_center();
-        let pcb = &self.triangles[pcb_index].abc_by_neighbor(abc_index);
+        buffer: &mut Vec<usize>,
+    ) -> bool {
+        if abc.v0.neighbor < self.triangles.len() {
+            return self.split_triangle(abc_index, abc, buffer, None);
+        }
 
-        let m = if pcb.is_contain(p) {
-            p
-        } else {
-            abc.edge_mid_point()
+        let Some(m) = segment_split_point(abc.v1.vertex.point, abc.v2.vertex.point) else {
+            return false;
         };
+        if !abc.is_split_valid(m) {
+            return false;
+        }
 
-        let m_index = self.points.len();
+        let vm = IndexPoint::new(self.points.len(), m);
         self.points.push(m);
-        let vm = IndexPoint {
-            index: m_index,
-            point: m,
-        };
+        self.split_edge(abc_index, abc, vm, buffer);
 
-        // abc -> abm
-        // pcb -> pcm
-        let abm_index = abc_index;
-        let pcm_index = pcb_index;
+        true
+    }
 
-        let amc_index = self.triangles.len();
-        let pmb_index = amc_index + 1;
+    fn refine_with_circumcenters_and_selector<S: EdgeSelector>(
+        &mut self,
+        selector: &S,
+        options: &RefineOptions,
+        mut parents: Option<&mut Vec<[usize; 3]>>,
+    ) -> RefineReport {
+        let mut bitset = IndexBitSet::with_size(self.triangles.len());
+        let mut buffer = Vec::with_capacity(self.triangles.len().max(16));
 
-        let abm = IntTriangle {
-            vertices: [abc.v0.vertex, abc.v1.vertex, vm],
-            neighbors: [pmb_index, amc_index, abc.v2.neighbor],
-        };
+        let mut report = RefineReport::default();
 
-        let amc = IntTriangle {
-            vertices: [abc.v0.vertex, vm, abc.v2.vertex],
-            neighbors: [pcm_index, abc.v1.neighbor, abm_index],
-        };
+        while report.passes < options.max_passes && report.inserted_points < options.max_points {
+            let mut abc_index = 0;
+            let mut split_counter = 0;
+            while abc_index < self.triangles.len() && report.inserted_points < options.max_points {
+                if let Some(position) = selector.select(self, abc_index) {
+                    let t = self.triangles[abc_index].abc_by_position(position);
+                    if self.split_triangle(abc_index, t, &mut buffer, parents.as_deref_mut()) {
+                        report.flips += self.fix_triangles(&mut buffer, &mut bitset);
+                        debug_assert!(buffer.is_empty());
+                        split_counter += 1;
+                        report.inserted_points += 1;
+                    }
+                }
+                abc_index += 1;
+            }
 
-        let pmb = IntTriangle {
-            vertices: [pcb.v0.vertex, vm, pcb.v2.vertex],
-            neighbors: [abm_index, pcb.v1.neighbor, pcm_index],
-        };
+            report.passes += 1;
+            if is_converged(split_counter, self.triangles.len(), options) {
+                break;
+            }
+        }
 
-        let pcm = IntTriangle {
-            vertices: [pcb.v0.vertex, pcb.v1.vertex, vm],
-            neighbors: [amc_index, pmb_index, pcb.v2.neighbor],
-        };
+        report.is_target_met =
+            (0..self.triangles.len()).all(|index| selector.select(self, index).is_none());
 
-        self.triangles
-            .update_neighbor(abc.v1.neighbor, abc_index, amc_index);
-        self.triangles
-            .update_neighbor(abc.v2.neighbor, abc_index, abm_index);
+        report
+    }
 
-        self.triangles
-            .update_neighbor(pcb.v1.neighbor, pcb_index, pmb_index);
-        self.triangles
-            .update_neighbor(pcb.v2.neighbor, pcb_index, pcm_index);
+    // returns false if the split point is rounded so that a new triangle is degenerate
+    #[inline]
+    pub(crate) fn split_triangle(
+        &mut self,
+        abc_index: usize,
+        abc: Abc,
+        buffer: &mut Vec<usize>,
+        parents: Option<&mut Vec<[usize; 3]>>,
+    ) -> bool {
+        let (m, parent) = if let Some(split) = self.split_point(abc_index, &abc) {
+            split
+        } else {
+            return false;
+        };
 
-        self.triangles[abm_index] = abm;
-        self.triangles[pcm_index] = pcm;
-        self.triangles.push(amc);
-        self.triangles.push(pmb);
+        if let Some(parents) = parents {
+            parents.push(parent);
+        }
+
+        let vm = IndexPoint::new(self.points.len(), m);
+        self.points.push(m);
+        self.split_edge(abc_index, abc, vm, buffer);
 
-        [abm_index, pcm_index, amc_index, pmb_index]
+        true
     }
 
-    fn split_alone_triangle(&mut self, abc_index: usize, abc: Abc) -> [usize; 2] {
-        let m = abc.edge_mid_point();
-        let m_index = self.points.len();
-        self.points.push(m);
-        let vm = IndexPoint {
-            index: m_index,
-            point: m,
-        };
+    // returns the split point and the vertices of the triangle that contains it
+    fn split_point(&self, abc_index: usize, abc: &Abc) -> Option<(IntPoint, [usize; 3])> {
+        let abc_vertices = [
+            abc.v0.vertex.index,
+            abc.v1.vertex.index,
+            abc.v2.vertex.index,
+        ];
+        let pcb_index = abc.v0.neighbor;
+        if pcb_index >= self.triangles.len() {
+            let m = abc.edge_mid_point();
+            return if abc.is_split_valid(m) {
+                Some((m, abc_vertices))
+            } else {
+                None
+            };
+        }
 
-        let abm_index = abc_index;
-        let amc_index = self.triangles.len();
+        let p = abc.circumscribed_center();
+        let pcb = &self.triangles[pcb_index].abc_by_neighbor(abc_index);
 
-        let abm = IntTriangle {
-            vertices: [abc.v0.vertex, abc.v1.vertex, vm],
-            neighbors: [usize::MAX, amc_index, abc.v2.neighbor],
-        };
+        let b_index = abc.v1.vertex.index;
+        let c_index = abc.v2.vertex.index;
+        let is_constrained = self.constraints.contains(b_index, c_index);
 
-        let amc = IntTriangle {
-            vertices: [abc.v0.vertex, vm, abc.v2.vertex],
-            neighbors: [usize::MAX, abc.v1.neighbor, abm_index],
+        // a constrained edge can only be split in the middle
+        let (m, parent) = if !is_constrained && pcb.is_contain(p) && abc.is_split_valid(p) {
+            (p, [pcb.v0.vertex.index, b_index, c_index])
+        } else {
+            (abc.edge_mid_point(), abc_vertices)
         };
 
-        self.triangles
-            .update_neighbor(abc.v1.neighbor, abc_index, amc_index);
-        self.triangles
-            .update_neighbor(abc.v2.neighbor, abc_index, abm_index);
-
-        self.triangles[abm_index] = abm;
-        self.triangles.push(amc);
+        if !abc.is_split_valid(m) || !pcb.is_split_valid(m) {
+            return None;
+        }
 
-        [abm_index, amc_index]
+        Some((m, parent))
     }
 }
 
 impl Abc {
     #[inline]
-    fn circumscribed_center(&self) -> IntPoint {
+    pub(crate) fn circumscribed_center(&self) -> IntPoint {
         let a = self.v0.vertex.point;
         let b = self.v1.vertex.point;
         let c = self.v2.vertex.point;
@@ -217,7 +483,7 @@ impl Abc {
     }
 
     #[inline]
-    fn is_contain(&self, p: IntPoint) -> bool {
+    pub(crate) fn is_contain(&self, p: IntPoint) -> bool {
         let a = self.v0.vertex.point;
         let b = self.v1.vertex.point;
         let c = self.v2.vertex.point;
@@ -225,8 +491,18 @@ impl Abc {
         Triangle::is_contain_point_exclude_borders(p, a, b, c)
     }
 
+    // m splits abc into abm and amc, both must keep the orientation
+    #[inline]
+    pub(crate) fn is_split_valid(&self, m: IntPoint) -> bool {
+        let a = self.v0.vertex.point;
+        let b = self.v1.vertex.point;
+        let c = self.v2.vertex.point;
+
+        Triangle::area_two_point(a, b, m) < 0 && Triangle::area_two_point(a, m, c) < 0
+    }
+
     #[inline]
-    fn edge_mid_point(&self) -> IntPoint {
+    pub(crate) fn edge_mid_point(&self) -> IntPoint {
         let b = self.v1.vertex.point;
         let c = self.v2.vertex.point;
 
@@ -237,16 +513,102 @@ impl Abc {
     }
 }
 
-trait EdgeSelector {
-    fn select(abc: &IntTriangle) -> Option<Abc>;
+// the longest edge if it exceeds the size at the centroid
+#[inline]
+pub(crate) fn select_edge_by_size<F: Fn(IntPoint) -> u32>(
+    abc: &IntTriangle,
+    sizing: &F,
+) -> Option<Abc> {
+    let a = abc.vertices[0].point;
+    let b = abc.vertices[1].point;
+    let c = abc.vertices[2].point;
+
+    let sqr_c = a.sqr_distance(b);
+    let sqr_a = b.sqr_distance(c);
+    let sqr_b = c.sqr_distance(a);
+    let sqr_max = sqr_a.max(sqr_b).max(sqr_c) as u64;
+
+    let centroid = IntPoint::new(
+        ((a.x as i64 + b.x as i64 + c.x as i64) / 3) as i32,
+        ((a.y as i64 + b.y as i64 + c.y as i64) / 3) as i32,
+    );
+    let size = local_size(sizing, centroid) as u64;
+    if sqr_max <= size * size {
+        return None;
+    }
+
+    Some(abc.abc_by_position(longest_edge_position(abc)))
+}
+
+// leave if this time we split only a small share
+#[inline]
+pub(crate) fn is_converged(split_counter: usize, count: usize, options: &RefineOptions) -> bool {
+    split_counter == 0 || split_counter as f64 / count as f64 <= options.convergence_ratio
+}
+
+#[inline]
+fn local_size<F: Fn(IntPoint) -> u32>(sizing: &F, p: IntPoint) -> u32 {
+    sizing(p).max(2)
+}
+
+#[inline]
+fn middle(a: IntPoint, b: IntPoint) -> IntPoint {
+    IntPoint::new(
+        ((a.x as i64 + b.x as i64) >> 1) as i32,
+        ((a.y as i64 + b.y as i64) >> 1) as i32,
+    )
+}
+
+/// Picks the edge of a triangle to split during circumcenter refinement.
+///
+/// The split point is the circumcenter if it falls into the neighbor across the
+/// edge and the edge middle otherwise.
+pub trait EdgeSelector {
+    /// Returns the position of the vertex opposite the edge to split, `None` keeps the triangle.
+    ///
+    /// `index` points into [`IntDelaunay::triangles`], neighbors, points and
+    /// constraints are reachable through `delaunay`.
+    fn select(&self, delaunay: &IntDelaunay, index: usize) -> Option<usize>;
+}
+
+/// Splits the longest edge, the one opposite the biggest angle.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SelectBiggerAngle;
+
+/// Splits the longest edge of an obtuse triangle, other triangles are kept.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SelectObtuseAngle;
+
+// skips triangles not bigger than the area
+pub(crate) struct MinArea<S> {
+    two_area: u64,
+    selector: S,
+}
+
+impl<S> MinArea<S> {
+    #[inline]
+    pub(crate) fn new(min_area: u64, selector: S) -> Self {
+        Self {
+            two_area: min_area.saturating_mul(2),
+            selector,
+        }
+    }
 }
 
-struct SelectBiggerAngle {}
-struct SelectObtuseAngle {}
+impl<S: EdgeSelector> EdgeSelector for MinArea<S> {
+    #[inline]
+    fn select(&self, delaunay: &IntDelaunay, index: usize) -> Option<usize> {
+        if delaunay.triangles[index].area_two() <= self.two_area {
+            return None;
+        }
+        self.selector.select(delaunay, index)
+    }
+}
 
 impl EdgeSelector for SelectObtuseAngle {
     #[inline]
-    fn select(abc: &IntTriangle) -> Option<Abc> {
+    fn select(&self, delaunay: &IntDelaunay, index: usize) -> Option<usize> {
+        let abc = &delaunay.triangles[index];
         let a = abc.vertices[0].point;
         let b = abc.vertices[1].point;
         let c = abc.vertices[2].point;
@@ -256,11 +618,11 @@ impl EdgeSelector for SelectObtuseAngle {
         let sqr_b = c.sqr_distance(a);
 
         if sqr_c > sqr_a + sqr_b {
-            Some(abc.abc_by_c())
+            Some(2)
         } else if sqr_b > sqr_a + sqr_c {
-            Some(abc.abc_by_b())
+            Some(1)
         } else if sqr_a > sqr_b + sqr_c {
-            Some(abc.abc_by_a())
+            Some(0)
         } else {
             None
         }
@@ -269,21 +631,385 @@ impl EdgeSelector for SelectObtuseAngle {
 
 impl EdgeSelector for SelectBiggerAngle {
     #[inline]
-    fn select(abc: &IntTriangle) -> Option<Abc> {
-        let a = abc.vertices[0].point;
-        let b = abc.vertices[1].point;
-        let c = abc.vertices[2].point;
+    fn select(&self, delaunay: &IntDelaunay, index: usize) -> Option<usize> {
+        Some(longest_edge_position(&delaunay.triangles[index]))
+    }
+}
 
-        let sqr_c = a.sqr_distance(b);
-        let sqr_a = b.sqr_distance(c);
-        let sqr_b = c.sqr_distance(a);
+#[inline]
+fn longest_edge_position(abc: &IntTriangle) -> usize {
+    let a = abc.vertices[0].point;
+    let b = abc.vertices[1].point;
+    let c = abc.vertices[2].point;
+
+    let sqr_c = a.sqr_distance(b);
+    let sqr_a = b.sqr_distance(c);
+    let sqr_b = c.sqr_distance(a);
+
+    if sqr_c >= sqr_a && sqr_c >= sqr_b {
+        2
+    } else if sqr_b >= sqr_a && sqr_b >= sqr_c {
+        1
+    } else {
+        0
+    }
+}
 
-        if sqr_c >= sqr_a && sqr_c >= sqr_b {
-            Some(abc.abc_by_c())
-        } else if sqr_b >= sqr_a && sqr_b >= sqr_c {
-            Some(abc.abc_by_b())
-        } else {
-            Some(abc.abc_by_a())
+#[cfg(test)]
+mod tests {
+    use crate::advanced::delaunay::IntDelaunay;
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::tessellation::circumcenter::{
+        EdgeSelector, MinArea, RefineOptions, SelectBiggerAngle, SelectObtuseAngle,
+    };
+    use crate::tessellation::split::SliceContour;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_float::triangle::Triangle;
+    use i_overlay::i_shape::int::area::Area;
+    use i_overlay::i_shape::int::path::IntPath;
+    use rand::RngExt;
+
+    #[test]
+    fn test_0() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 1000),
+            IntPoint::new(0, 1000),
+        ];
+
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_sizing(|_| 100, &RefineOptions::with_max_points(usize::MAX))
+            .0;
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert!(delaunay.is_delaunay());
+        assert_sizing(&delaunay, |_| 100);
+
+        // the boundary is sliced like SliceContour does
+        for p in contour.slice_contour(100) {
+            assert!(delaunay.points.contains(&p));
+        }
+    }
+
+    #[test]
+    fn test_1() {
+        // dense near the origin, coarse far away
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(2000, 0),
+            IntPoint::new(2000, 2000),
+            IntPoint::new(0, 2000),
+        ];
+        let sizing = |p: IntPoint| 20 + (p.x + p.y) as u32 / 10;
+
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_sizing(sizing, &RefineOptions::with_max_points(usize::MAX))
+            .0;
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert_sizing(&delaunay, sizing);
+
+        let near = delaunay.points.iter().filter(|p| p.x + p.y < 500).count();
+        let far = delaunay.points.iter().filter(|p| p.x + p.y > 3500).count();
+        assert!(near > 4 * far);
+    }
+
+    #[test]
+    fn test_2() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 1000),
+            IntPoint::new(0, 1000),
+        ];
+
+        let mut delaunay = contour.triangulate().into_delaunay();
+        let report = delaunay.refine_to_max_area_mut(5000, None);
+
+        delaunay.validate();
+        assert!(report.is_target_met);
+        assert_eq!(delaunay.points.len(), 4 + report.inserted_points);
+        assert_eq!(delaunay.area(), contour.area_two());
+        for t in delaunay.triangles.iter() {
+            let [a, b, c] = t.vertices.map(|v| v.point);
+            assert!(Triangle::area_two_point(a, b, c).unsigned_abs() <= 10_000);
+        }
+    }
+
+    #[test]
+    fn test_3() {
+        // the point cap stops the refinement early
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 1000),
+            IntPoint::new(0, 1000),
+        ];
+
+        let mut delaunay = contour.triangulate().into_delaunay();
+        let report = delaunay.refine_to_max_area_mut(5000, Some(10));
+
+        delaunay.validate();
+        assert!(!report.is_target_met);
+        assert_eq!(report.inserted_points, 10);
+        assert_eq!(delaunay.points.len(), 14);
+
+        let (delaunay, report) = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_to_max_area(5000, Some(10));
+        assert!(!report.is_target_met);
+        assert_eq!(delaunay.points.len(), 4 + report.inserted_points);
+    }
+
+    #[test]
+    fn test_4() {
+        // a region rule: refine the left half only
+        struct LeftHalf;
+
+        impl EdgeSelector for LeftHalf {
+            fn select(&self, delaunay: &IntDelaunay, index: usize) -> Option<usize> {
+                let t = &delaunay.triangles[index];
+                let x = t.vertices.iter().map(|v| v.point.x).max().unwrap();
+                if x > 500 || t.area_two() <= 2 * 1000 {
+                    return None;
+                }
+                SelectBiggerAngle.select(delaunay, index)
+            }
+        }
+
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 1000),
+            IntPoint::new(0, 1000),
+        ];
+
+        let mut delaunay = contour.triangulate().into_delaunay();
+        delaunay.refine_with_circumcenters_mut(1000);
+        let base = delaunay.points.len();
+
+        let delaunay = delaunay.refine_with_selector(&LeftHalf);
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert!(delaunay.points.len() > base);
+
+        let left = delaunay.points[base..]
+            .iter()
+            .filter(|p| p.x <= 500)
+            .count();
+        assert!(left > delaunay.points.len() - base - left);
+    }
+
+    #[test]
+    fn test_5() {
+        // the built-in selectors through the public entry point
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 100),
+            IntPoint::new(0, 100),
+        ];
+
+        let obtuse = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_selector(&SelectObtuseAngle);
+        obtuse.validate();
+        assert_eq!(obtuse.area(), contour.area_two());
+
+        let bigger = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_selector(&SelectBiggerAngle);
+        bigger.validate();
+        assert_eq!(bigger.area(), contour.area_two());
+        assert!(bigger.points.len() > 4);
+    }
+
+    #[test]
+    fn test_6() {
+        // the default options repeat the classic refinement
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 1000),
+            IntPoint::new(0, 1000),
+        ];
+
+        let classic = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_circumcenters(500);
+
+        let mut delaunay = contour.triangulate().into_delaunay();
+        let report = delaunay.refine_with_options_mut(500, &RefineOptions::default());
+
+        delaunay.validate();
+        assert_eq!(delaunay.points, classic.points);
+        assert_eq!(delaunay.points.len(), 4 + report.inserted_points);
+        assert!(report.passes >= 1 && report.passes <= 8);
+        assert!(report.flips > 0);
+    }
+
+    #[test]
+    fn test_7() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 1000),
+            IntPoint::new(0, 1000),
+        ];
+
+        // a single pass
+        let mut delaunay = contour.triangulate().into_delaunay();
+        let options = RefineOptions {
+            max_passes: 1,
+            ..Default::default()
+        };
+        let report = delaunay.refine_with_options_mut(500, &options);
+        delaunay.validate();
+        assert_eq!(report.passes, 1);
+        assert!(!report.is_target_met);
+
+        // the point cap
+        let mut delaunay = contour.triangulate().into_delaunay();
+        let options = RefineOptions {
+            max_points: 25,
+            convergence_ratio: 0.0,
+            ..Default::default()
+        };
+        let report = delaunay.refine_with_options_mut(500, &options);
+        delaunay.validate();
+        assert_eq!(report.inserted_points, 25);
+        assert_eq!(delaunay.points.len(), 29);
+
+        // run until nothing is left to split
+        let mut delaunay = contour.triangulate().into_delaunay();
+        let options = RefineOptions {
+            max_passes: usize::MAX,
+            convergence_ratio: 0.0,
+            ..Default::default()
+        };
+        let report = delaunay
+            .refine_with_selector_and_options_mut(&MinArea::new(500, SelectBiggerAngle), &options);
+        delaunay.validate();
+        assert!(report.is_target_met);
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert!(delaunay.triangles.iter().all(|t| t.area_two() <= 1000));
+    }
+
+    #[test]
+    fn test_8() {
+        // the middle of the long edge rounds onto the opposite vertex
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(500, 0),
+            IntPoint::new(1001, 1),
+        ];
+
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_circumcenters(0);
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert!(delaunay.triangles.iter().all(|t| t.area_two() > 0));
+    }
+
+    #[test]
+    fn test_9() {
+        // the edges are not axis-aligned, the bottom one has no grid points between its ends
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10007, 1),
+            IntPoint::new(10003, 997),
+            IntPoint::new(1, 1003),
+        ];
+        let area = contour.area_two();
+
+        let (delaunay, report) = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_sizing(|_| 100, &RefineOptions::with_max_points(usize::MAX));
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), area);
+        assert!(delaunay.is_delaunay());
+        assert!(!report.is_target_met);
+        assert_eq!(delaunay.points.len(), 4 + report.inserted_points);
+
+        // a tiny size is stopped by the point limit
+        let options = RefineOptions::with_max_points(1000);
+        let (delaunay, report) = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_sizing(|_| 2, &options);
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), area);
+        assert_eq!(report.inserted_points, 1000);
+        assert_eq!(delaunay.points.len(), 4 + 1000);
+        assert!(!report.is_target_met);
+    }
+
+    #[test]
+    fn test_random_0() {
+        for _ in 0..500 {
+            let path = random(1000, 8);
+            let shapes = path.simplify(FillRule::NonZero, IntOverlayOptions::keep_all_points());
+            let Some(first) = shapes.first() else {
+                continue;
+            };
+
+            let delaunay = first
+                .triangulate()
+                .into_delaunay()
+                .refine_with_sizing(|_| 100, &RefineOptions::with_max_points(usize::MAX))
+                .0;
+
+            delaunay.validate();
+            assert_eq!(delaunay.area(), first.area_two());
+        }
+    }
+
+    fn assert_sizing<F: Fn(IntPoint) -> u32>(delaunay: &IntDelaunay, sizing: F) {
+        for t in delaunay.triangles.iter() {
+            let [a, b, c] = t.vertices.map(|v| v.point);
+            let centroid = IntPoint::new((a.x + b.x + c.x) / 3, (a.y + b.y + c.y) / 3);
+            let size = sizing(centroid) as i64;
+            let sqr_max = a
+                .sqr_distance(b)
+                .max(b.sqr_distance(c))
+                .max(c.sqr_distance(a));
+            assert!(sqr_max <= size * size);
+        }
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
         }
+
+        points
     }
 }
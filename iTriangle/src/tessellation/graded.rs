@@ -0,0 +1,349 @@
+use crate::advanced::bitset::IndexBitSet;
+use crate::advanced::delaunay::IntDelaunay;
+use crate::tessellation::circumcenter::{
+    is_converged, select_edge_by_size, RefineOptions, RefineReport,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+
+impl IntDelaunay {
+    /// Consuming variant of [`refine_graded_mut`](Self::refine_graded_mut).
+    #[inline]
+    pub fn refine_graded(
+        mut self,
+        min_size: u32,
+        growth: f64,
+        options: &RefineOptions,
+    ) -> (Self, RefineReport) {
+        let report = self.refine_graded_mut(min_size, growth, options);
+        (self, report)
+    }
+
+    /// Refines the mesh with small triangles near the boundary and larger ones inside.
+    ///
+    /// The local max edge length is `min_size + growth * distance`, where `distance`
+    /// is measured from a triangle centroid to the nearest boundary edge. Boundary
+    /// edges are sliced to `min_size` first, then the nearest edge of every triangle
+    /// is spread over the mesh through its neighbors and triangles are split like in
+    /// [`refine_with_sizing_mut`](Self::refine_with_sizing_mut), which also describes
+    /// how the boundary is kept and how `options` apply.
+    ///
+    /// # Parameters
+    /// - `min_size`: the edge length at the boundary, values below 2 are treated as 2
+    /// - `growth`: how fast the edge length grows with the distance, `0.2..0.5` gives
+    ///   a smooth transition, negative values are treated as 0
+    /// - `options`: limits the passes and the inserted points
+    ///
+    /// # Returns
+    /// A [`RefineReport`] telling whether every triangle fits its size.
+    pub fn refine_graded_mut(
+        &mut self,
+        min_size: u32,
+        growth: f64,
+        options: &RefineOptions,
+    ) -> RefineReport {
+        let growth = growth.max(0.0);
+
+        let mut bitset = IndexBitSet::with_size(self.triangles.len());
+        let mut buffer = Vec::with_capacity(self.triangles.len().max(16));
+
+        let mut report = RefineReport {
+            inserted_points: self.slice_boundary(&|_| min_size, options.max_points, &mut buffer),
+            ..Default::default()
+        };
+        if !buffer.is_empty() {
+            report.flips += self.fix_triangles(&mut buffer, &mut bitset);
+        }
+
+        // the nearest edges are found once per pass,
+        // triangles created during a pass wait for the next one
+        while report.passes < options.max_passes && report.inserted_points < options.max_points {
+            let mut split_counter = 0;
+            let field = BoundaryField::new(self);
+            for abc_index in 0..field.nearest.len() {
+                if report.inserted_points >= options.max_points {
+                    break;
+                }
+                let segment = field.nearest[abc_index];
+                let sizing = |p: IntPoint| field.size(p, segment, min_size, growth);
+                if let Some(t) = select_edge_by_size(&self.triangles[abc_index], &sizing) {
+                    if self.split_triangle_inside_boundary(abc_index, t, &mut buffer) {
+                        report.flips += self.fix_triangles(&mut buffer, &mut bitset);
+                        split_counter += 1;
+                        report.inserted_points += 1;
+                    }
+                }
+            }
+
+            report.passes += 1;
+            if is_converged(split_counter, self.triangles.len(), options) {
+                break;
+            }
+        }
+
+        let field = BoundaryField::new(self);
+        report.is_target_met = self.triangles.iter().enumerate().all(|(index, abc)| {
+            let segment = field.nearest[index];
+            select_edge_by_size(abc, &|p: IntPoint| field.size(p, segment, min_size, growth))
+                .is_none()
+        });
+
+        report
+    }
+}
+
+// the nearest boundary edge of every triangle
+struct BoundaryField {
+    segments: Vec<[IntPoint; 2]>,
+    nearest: Vec<usize>,
+}
+
+impl BoundaryField {
+    fn new(delaunay: &IntDelaunay) -> Self {
+        let triangles = &delaunay.triangles;
+        let mut segments = Vec::new();
+        let mut nearest = vec![usize::MAX; triangles.len()];
+        let mut sqr_distances = vec![u64::MAX; triangles.len()];
+        let mut queue = Vec::new();
+
+        // seed with the own boundary edges
+        for (index, t) in triangles.iter().enumerate() {
+            let centroid = centroid(t.vertices.map(|v| v.point));
+            for position in 0..3 {
+                if t.neighbors[position] < triangles.len() {
+                    continue;
+                }
+                let segment = [
+                    t.vertices[(position + 1) % 3].point,
+                    t.vertices[(position + 2) % 3].point,
+                ];
+                let sqr_distance = sqr_distance_to_segment(centroid, segment);
+                if sqr_distance < sqr_distances[index] {
+                    sqr_distances[index] = sqr_distance;
+                    nearest[index] = segments.len();
+                }
+                segments.push(segment);
+            }
+            if nearest[index] != usize::MAX {
+                queue.push(index);
+            }
+        }
+
+        // a neighbor takes the edge if it is closer than its own, distances only
+        // decrease so the spread stops
+        while let Some(index) = queue.pop() {
+            let segment = nearest[index];
+            for &neighbor in triangles[index].neighbors.iter() {
+                if neighbor >= triangles.len() {
+                    continue;
+                }
+                let centroid = centroid(triangles[neighbor].vertices.map(|v| v.point));
+                let sqr_distance = sqr_distance_to_segment(centroid, segments[segment]);
+                if sqr_distance < sqr_distances[neighbor] {
+                    sqr_distances[neighbor] = sqr_distance;
+                    nearest[neighbor] = segment;
+                    queue.push(neighbor);
+                }
+            }
+        }
+
+        Self { segments, nearest }
+    }
+
+    #[inline]
+    fn size(&self, p: IntPoint, segment: usize, min_size: u32, growth: f64) -> u32 {
+        let Some(segment) = self.segments.get(segment) else {
+            return min_size;
+        };
+        let distance = sqr_distance_to_segment(p, *segment).isqrt() as f64;
+        let size = min_size as f64 + growth * distance;
+        if size < u32::MAX as f64 {
+            size as u32
+        } else {
+            u32::MAX
+        }
+    }
+}
+
+#[inline]
+fn centroid([a, b, c]: [IntPoint; 3]) -> IntPoint {
+    IntPoint::new(
+        ((a.x as i64 + b.x as i64 + c.x as i64) / 3) as i32,
+        ((a.y as i64 + b.y as i64 + c.y as i64) / 3) as i32,
+    )
+}
+
+#[inline]
+pub(crate) fn sqr_distance_to_segment(p: IntPoint, [a, b]: [IntPoint; 2]) -> u64 {
+    let ab = b.subtract(a);
+    let ap = p.subtract(a);
+    let t = ab.dot_product(ap);
+    if t <= 0 {
+        return ap.sqr_length() as u64;
+    }
+    let sqr_ab = ab.sqr_length();
+    if t >= sqr_ab {
+        return p.subtract(b).sqr_length() as u64;
+    }
+
+    // |ab x ap|^2 / |ab|^2
+    let cross = ab.cross_product(ap) as i128;
+    ((cross * cross) / sqr_ab as i128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::tessellation::circumcenter::RefineOptions;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::area::Area;
+    use i_overlay::i_shape::int::path::IntPath;
+    use rand::RngExt;
+
+    #[test]
+    fn test_0() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(2000, 0),
+            IntPoint::new(2000, 2000),
+            IntPoint::new(0, 2000),
+        ];
+
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_graded(40, 0.5, &RefineOptions::with_max_points(usize::MAX))
+            .0;
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert!(delaunay.is_delaunay());
+
+        // short edges at the walls, long ones in the middle
+        let mut wall = 0;
+        let mut middle = 0;
+        for t in delaunay.triangles.iter() {
+            let [a, b, c] = t.vertices.map(|v| v.point);
+            let sqr_max = a
+                .sqr_distance(b)
+                .max(b.sqr_distance(c))
+                .max(c.sqr_distance(a));
+            let x = (a.x + b.x + c.x) / 3;
+            let y = (a.y + b.y + c.y) / 3;
+            let distance = x.min(y).min(2000 - x).min(2000 - y);
+            if distance < 20 {
+                assert!(sqr_max <= 60 * 60);
+                wall += 1;
+            } else if distance > 800 {
+                middle = middle.max(sqr_max);
+            }
+        }
+        assert!(wall > 100);
+        assert!(middle > 200 * 200);
+    }
+
+    #[test]
+    fn test_1() {
+        // a hole is a wall too
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(2000, 0),
+                IntPoint::new(2000, 2000),
+                IntPoint::new(0, 2000),
+            ],
+            vec![
+                IntPoint::new(900, 900),
+                IntPoint::new(900, 1100),
+                IntPoint::new(1100, 1100),
+                IntPoint::new(1100, 900),
+            ],
+        ];
+
+        let delaunay = shape
+            .triangulate()
+            .into_delaunay()
+            .refine_graded(40, 0.5, &RefineOptions::with_max_points(usize::MAX))
+            .0;
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), shape.area_two());
+
+        let near_hole = delaunay
+            .points
+            .iter()
+            .filter(|p| (850..=1150).contains(&p.x) && (850..=1150).contains(&p.y))
+            .count();
+        assert!(near_hole > 20);
+    }
+
+    #[test]
+    fn test_2() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10007, 1),
+            IntPoint::new(10003, 997),
+            IntPoint::new(1, 1003),
+        ];
+
+        let (delaunay, report) = contour.triangulate().into_delaunay().refine_graded(
+            40,
+            0.5,
+            &RefineOptions::with_max_points(usize::MAX),
+        );
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert_eq!(delaunay.points.len(), 4 + report.inserted_points);
+
+        let options = RefineOptions::with_max_points(300);
+        let (delaunay, report) = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_graded(2, 0.0, &options);
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert_eq!(report.inserted_points, 300);
+        assert!(!report.is_target_met);
+    }
+
+    #[test]
+    fn test_random_0() {
+        for _ in 0..500 {
+            let path = random(1000, 8);
+            let shapes = path.simplify(FillRule::NonZero, IntOverlayOptions::keep_all_points());
+            let Some(first) = shapes.first() else {
+                continue;
+            };
+
+            let delaunay = first
+                .triangulate()
+                .into_delaunay()
+                .refine_graded(50, 0.3, &RefineOptions::with_max_points(usize::MAX))
+                .0;
+
+            delaunay.validate();
+            assert_eq!(delaunay.area(), first.area_two());
+        }
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
@@ -1,2 +1,5 @@
 pub mod circumcenter;
+pub mod graded;
+pub mod quality;
+pub mod region;
 pub mod split;
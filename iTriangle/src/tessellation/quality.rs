@@ -0,0 +1,768 @@
+use crate::advanced::bitset::IndexBitSet;
+use crate::advanced::delaunay::{is_in_circle, IntDelaunay};
+use crate::advanced::insert::{MeshLocation, Step};
+use crate::geom::point::IndexPoint;
+use crate::geom::triangle::IntTriangle;
+use crate::tessellation::circumcenter::{is_converged, RefineOptions, RefineReport};
+use crate::tessellation::split::segment_split_point;
+use alloc::vec::Vec;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_float::triangle::Triangle;
+
+/// The largest minimum angle, in degrees, that Ruppert refinement is guaranteed to reach.
+///
+/// [`IntDelaunay::refine_to_min_angle`] clamps the requested angle to this value.
+pub const MAX_MIN_ANGLE: f64 = 20.7;
+
+// edges shorter than this are not split any more, the integer grid is too coarse
+// to place a point between their ends without distortion
+const MIN_SPLIT_SQR_LENGTH: i64 = 16;
+
+impl IntDelaunay {
+    /// Consuming variant of [`refine_to_min_angle_mut`](Self::refine_to_min_angle_mut).
+    #[inline]
+    pub fn refine_to_min_angle(
+        mut self,
+        degrees: f64,
+        max_area: u64,
+        max_points: Option<usize>,
+    ) -> (Self, RefineReport) {
+        let report = self.refine_to_min_angle_mut(degrees, max_area, max_points);
+        (self, report)
+    }
+
+    /// Refines the mesh until every triangle has all angles of at least `degrees`
+    /// and an area of at most `max_area` (Ruppert's algorithm).
+    ///
+    /// Boundary edges and constraints are treated as segments. A segment is split in
+    /// the middle while a vertex lies inside its diametral circle. A bad triangle gets
+    /// its circumcenter inserted, unless the circumcenter encroaches a segment, then
+    /// the segment is split instead. Boundaries sliced with
+    /// [`SliceContour`](crate::tessellation::split::SliceContour) are handled the same way.
+    ///
+    /// # Parameters
+    /// - `degrees`: the minimum angle, clamped to [`MAX_MIN_ANGLE`]
+    /// - `max_area`: the maximum triangle area, `u64::MAX` for no limit
+    /// - `max_points`: the most points to insert, `None` for no limit
+    ///
+    /// There is no pass limit, the loop goes on until the target is met, nothing
+    /// can be split any more, or `max_points` points were inserted.
+    ///
+    /// A segment is split at the grid point on it closest to its middle. A segment
+    /// without grid points between its ends, e.g. from `(0, 0)` to `(7, 3)`, is split
+    /// at the rounded middle instead, which lies less than one unit off the segment.
+    /// The two halves stay segments, so the boundary and the area may move by that much.
+    ///
+    /// # Returns
+    /// A [`RefineReport`], the target is met if every triangle fits `degrees` and
+    /// `max_area`. Angles between two input segments do not count, they cannot be
+    /// improved. Triangles too small to split on the integer grid are left as is
+    /// and leave the target unmet.
+    pub fn refine_to_min_angle_mut(
+        &mut self,
+        degrees: f64,
+        max_area: u64,
+        max_points: Option<usize>,
+    ) -> RefineReport {
+        let quality = Quality::new(degrees, max_area);
+        let options = RefineOptions::with_max_points(max_points.unwrap_or(usize::MAX));
+
+        let mut bitset = IndexBitSet::with_size(self.triangles.len());
+        let mut buffer = Vec::with_capacity(16);
+        let mut report = RefineReport::default();
+
+        // a pass visits every triangle including the new ones
+        while report.passes < options.max_passes && report.inserted_points < options.max_points {
+            let inserted = report.inserted_points;
+            let mut index = 0;
+            while index < self.triangles.len() && report.inserted_points < options.max_points {
+                let is_changed =
+                    self.refine_triangle(index, &quality, &mut buffer, &mut bitset, &mut report);
+                if !is_changed {
+                    index += 1;
+                }
+            }
+
+            report.passes += 1;
+            let split_counter = report.inserted_points - inserted;
+            if is_converged(split_counter, self.triangles.len(), &options) {
+                break;
+            }
+        }
+
+        report.is_target_met = self
+            .triangles
+            .iter()
+            .all(|t| !quality.is_large(t) && (!quality.is_skinny(t) || self.is_input_angle(t)));
+
+        report
+    }
+
+    // returns true if the mesh was changed, the triangle at `index` is a new one then
+    fn refine_triangle(
+        &mut self,
+        index: usize,
+        quality: &Quality,
+        buffer: &mut Vec<usize>,
+        bitset: &mut IndexBitSet,
+        report: &mut RefineReport,
+    ) -> bool {
+        let triangle = self.triangles[index].clone();
+
+        // encroached by the opposite vertex
+        for position in 0..3 {
+            if !self.is_segment(&triangle, position) {
+                continue;
+            }
+            let a = triangle.vertices[position].point;
+            let b = triangle.vertices[(position + 1) % 3].point;
+            let c = triangle.vertices[(position + 2) % 3].point;
+            if is_encroached(b, c, a)
+                && self.split_in_middle(index, position, buffer, bitset, report)
+            {
+                return true;
+            }
+        }
+
+        let is_bad = quality.is_large(&triangle)
+            || quality.is_skinny(&triangle)
+                && !is_too_small(&triangle)
+                && !self.is_input_angle(&triangle);
+        if !is_bad {
+            return false;
+        }
+
+        let abc = triangle.vertices.map(|v| v.point);
+        let center = triangle.abc_by_a().circumscribed_center();
+        if abc.contains(&center) {
+            // the center rounds onto a vertex, the longest edge is split instead
+            let position = triangle.longest_edge_position();
+            return self.split_in_middle(index, position, buffer, bitset, report);
+        }
+
+        self.insert_circumcenter(index, center, buffer, bitset, report)
+    }
+
+    #[inline]
+    fn is_segment(&self, triangle: &IntTriangle, position: usize) -> bool {
+        if triangle.neighbors[position] >= self.triangles.len() {
+            return true;
+        }
+        let b = triangle.vertices[(position + 1) % 3].index;
+        let c = triangle.vertices[(position + 2) % 3].index;
+        self.constraints.contains(b, c)
+    }
+
+    // the smallest angle lies between two segments, no point can improve it
+    #[inline]
+    fn is_input_angle(&self, triangle: &IntTriangle) -> bool {
+        let i = triangle.smallest_angle_position();
+        self.is_segment(triangle, (i + 1) % 3) && self.is_segment(triangle, (i + 2) % 3)
+    }
+
+    fn insert_circumcenter(
+        &mut self,
+        start: usize,
+        center: IntPoint,
+        buffer: &mut Vec<usize>,
+        bitset: &mut IndexBitSet,
+        report: &mut RefineReport,
+    ) -> bool {
+        // walk toward the center, a segment on the way is split instead
+        let count = self.triangles.len();
+        let mut index = start;
+        let mut location = None;
+        for _ in 0..count {
+            let triangle = &self.triangles[index];
+            match triangle.locate(index, center, 0) {
+                Step::Found(found) => {
+                    location = Some(found);
+                    break;
+                }
+                Step::Next(position) => {
+                    if self.is_segment(triangle, position) {
+                        return self.split_in_middle(index, position, buffer, bitset, report);
+                    }
+                    index = triangle.neighbors[position];
+                }
+            }
+        }
+
+        let (index, on_edge) = match location {
+            Some(MeshLocation::Inside(index)) => (index, None),
+            Some(MeshLocation::OnEdge(index, position)) => {
+                if self.is_segment(&self.triangles[index], position)
+                    && self.split_in_middle(index, position, buffer, bitset, report)
+                {
+                    return true;
+                }
+                (index, Some(position))
+            }
+            _ => return false,
+        };
+
+        // a segment too short to split lets the center in
+        if let Some((segment_index, position)) = self.encroached_by(index, center) {
+            if self.split_in_middle(segment_index, position, buffer, bitset, report) {
+                return true;
+            }
+        }
+
+        let vp = IndexPoint::new(self.points.len(), center);
+        self.points.push(center);
+        if let Some(position) = on_edge {
+            let abc = self.triangles[index].abc_by_position(position);
+            self.split_edge(index, abc, vp, buffer);
+        } else {
+            buffer.extend_from_slice(&self.split_inner(index, vp));
+        }
+
+        bitset.clear_and_resize(self.triangles.len());
+        report.flips += self.fix_triangles(buffer, bitset);
+        report.inserted_points += 1;
+
+        true
+    }
+
+    // finds a segment on the border of the cavity of `p`, the triangles whose
+    // circumcircle contains `p`, that `p` encroaches
+    fn encroached_by(&self, start: usize, p: IntPoint) -> Option<(usize, usize)> {
+        let mut cavity = Vec::with_capacity(8);
+        let mut queue = Vec::with_capacity(8);
+        queue.push(start);
+        while let Some(index) = queue.pop() {
+            if cavity.contains(&index) {
+                continue;
+            }
+            cavity.push(index);
+
+            let triangle = &self.triangles[index];
+            for position in 0..3 {
+                let b = triangle.vertices[(position + 1) % 3].point;
+                let c = triangle.vertices[(position + 2) % 3].point;
+                if self.is_segment(triangle, position) {
+                    if is_encroached(b, c, p) {
+                        return Some((index, position));
+                    }
+                    continue;
+                }
+
+                let neighbor = triangle.neighbors[position];
+                let [na, nb, nc] = self.triangles[neighbor].vertices.map(|v| v.point);
+                if is_in_circle(na, nb, nc, p) {
+                    queue.push(neighbor);
+                }
+            }
+        }
+
+        None
+    }
+
+    // splits the edge opposite `position` near the middle, see `split_point`,
+    // a segment stays a segment by its two halves
+    fn split_in_middle(
+        &mut self,
+        index: usize,
+        position: usize,
+        buffer: &mut Vec<usize>,
+        bitset: &mut IndexBitSet,
+        report: &mut RefineReport,
+    ) -> bool {
+        let abc = self.triangles[index].abc_by_position(position);
+        let b = abc.v1.vertex.point;
+        let c = abc.v2.vertex.point;
+        if b.sqr_distance(c) < MIN_SPLIT_SQR_LENGTH {
+            return false;
+        }
+
+        let m = split_point(b, c);
+        if !abc.is_split_valid(m) {
+            return false;
+        }
+        let pcb_index = abc.v0.neighbor;
+        if pcb_index < self.triangles.len()
+            && !self.triangles[pcb_index]
+                .abc_by_neighbor(index)
+                .is_split_valid(m)
+        {
+            return false;
+        }
+
+        let vm = IndexPoint::new(self.points.len(), m);
+        self.points.push(m);
+        self.split_edge(index, abc, vm, buffer);
+
+        bitset.clear_and_resize(self.triangles.len());
+        report.flips += self.fix_triangles(buffer, bitset);
+        report.inserted_points += 1;
+
+        true
+    }
+}
+
+struct Quality {
+    // squared cosine of the minimum angle
+    sqr_cos: f64,
+    max_area_two: u64,
+}
+
+impl Quality {
+    #[inline]
+    fn new(degrees: f64, max_area: u64) -> Self {
+        let radians = degrees.clamp(0.0, MAX_MIN_ANGLE) * (core::f64::consts::PI / 180.0);
+        let cos = small_cos(radians);
+        Self {
+            sqr_cos: cos * cos,
+            max_area_two: max_area.saturating_mul(2),
+        }
+    }
+
+    #[inline]
+    fn is_large(&self, triangle: &IntTriangle) -> bool {
+        let [a, b, c] = triangle.vertices.map(|v| v.point);
+        Triangle::area_two_point(a, b, c).unsigned_abs() > self.max_area_two
+    }
+
+    // the angle opposite the shortest edge is smaller than the minimum angle,
+    // by the law of cosines compared squared to stay without sqrt
+    #[inline]
+    fn is_skinny(&self, triangle: &IntTriangle) -> bool {
+        let [a, b, c] = triangle.vertices.map(|v| v.point);
+        let sqr_a = b.sqr_distance(c);
+        let sqr_b = c.sqr_distance(a);
+        let sqr_c = a.sqr_distance(b);
+
+        let (a, b, c) = (sqr_a as f64, sqr_b as f64, sqr_c as f64);
+        let (min, p, q) = if sqr_a <= sqr_b && sqr_a <= sqr_c {
+            (a, b, c)
+        } else if sqr_b <= sqr_c {
+            (b, a, c)
+        } else {
+            (c, a, b)
+        };
+        let numerator = p + q - min;
+        numerator * numerator > 4.0 * p * q * self.sqr_cos
+    }
+}
+
+impl IntTriangle {
+    // the position of the vertex opposite the longest edge
+    #[inline]
+    fn longest_edge_position(&self) -> usize {
+        let [a, b, c] = self.vertices.map(|v| v.point);
+        let sqr_a = b.sqr_distance(c);
+        let sqr_b = c.sqr_distance(a);
+        let sqr_c = a.sqr_distance(b);
+        if sqr_a >= sqr_b && sqr_a >= sqr_c {
+            0
+        } else if sqr_b >= sqr_c {
+            1
+        } else {
+            2
+        }
+    }
+
+    // the position of the vertex opposite the shortest edge
+    #[inline]
+    fn smallest_angle_position(&self) -> usize {
+        let [a, b, c] = self.vertices.map(|v| v.point);
+        let sqr_a = b.sqr_distance(c);
+        let sqr_b = c.sqr_distance(a);
+        let sqr_c = a.sqr_distance(b);
+        if sqr_a <= sqr_b && sqr_a <= sqr_c {
+            0
+        } else if sqr_b <= sqr_c {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+// cos(x) for x in [0, 0.4] by its Taylor series, core has no trigonometry
+#[inline]
+fn small_cos(x: f64) -> f64 {
+    let x2 = x * x;
+    1.0 - x2 / 2.0 * (1.0 - x2 / 12.0 * (1.0 - x2 / 30.0 * (1.0 - x2 / 56.0)))
+}
+
+// the triangle has an edge too short to place a point between its ends
+#[inline]
+fn is_too_small(triangle: &IntTriangle) -> bool {
+    let [a, b, c] = triangle.vertices.map(|v| v.point);
+    let sqr_min = a
+        .sqr_distance(b)
+        .min(b.sqr_distance(c))
+        .min(c.sqr_distance(a));
+    sqr_min < MIN_SPLIT_SQR_LENGTH
+}
+
+// the grid point on ab closest to its middle, or the rounded middle closest to
+// the line through ab if ab has no grid points between its ends
+#[inline]
+fn split_point(a: IntPoint, b: IntPoint) -> IntPoint {
+    if let Some(m) = segment_split_point(a, b) {
+        return m;
+    }
+
+    let sx = a.x as i64 + b.x as i64;
+    let sy = a.y as i64 + b.y as i64;
+    let (dx, dy) = (b.x as i64 - a.x as i64, b.y as i64 - a.y as i64);
+    let mut best = IntPoint::new((sx >> 1) as i32, (sy >> 1) as i32);
+    let mut best_cross = i128::MAX;
+    for x in [sx >> 1, (sx + 1) >> 1] {
+        for y in [sy >> 1, (sy + 1) >> 1] {
+            let cross = (dx as i128 * (y - a.y as i64) as i128
+                - dy as i128 * (x - a.x as i64) as i128)
+                .abs();
+            if cross < best_cross {
+                best_cross = cross;
+                best = IntPoint::new(x as i32, y as i32);
+            }
+        }
+    }
+    best
+}
+
+// `true` if `p` lies strictly inside the diametral circle of the segment ab
+#[inline]
+fn is_encroached(a: IntPoint, b: IntPoint, p: IntPoint) -> bool {
+    a.subtract(p).dot_product(b.subtract(p)) < 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::advanced::delaunay::IntDelaunay;
+    use crate::geom::triangle::IntTriangle;
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::tessellation::circumcenter::RefineReport;
+    use crate::tessellation::quality::Quality;
+    use crate::tessellation::split::SliceContour;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use i_overlay::core::fill_rule::FillRule;
+    use i_overlay::core::overlay::IntOverlayOptions;
+    use i_overlay::core::simplify::Simplify;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::area::Area;
+    use i_overlay::i_shape::int::path::IntPath;
+    use rand::RngExt;
+
+    #[test]
+    fn test_0() {
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 1000),
+            IntPoint::new(0, 1000),
+        ];
+
+        let (delaunay, report) =
+            contour
+                .triangulate()
+                .into_delaunay()
+                .refine_to_min_angle(20.0, u64::MAX, None);
+
+        assert_quality(&delaunay, 20.0, u64::MAX);
+
+        assert!(report.is_target_met);
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert!(delaunay.is_delaunay());
+    }
+
+    #[test]
+    fn test_1() {
+        // a thin sliver needs inner points
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(2000, 0),
+            IntPoint::new(2000, 100),
+            IntPoint::new(0, 100),
+        ];
+
+        let (delaunay, report) =
+            contour
+                .triangulate()
+                .into_delaunay()
+                .refine_to_min_angle(20.0, u64::MAX, None);
+
+        assert!(delaunay.points.len() > 4);
+        assert_quality(&delaunay, 20.0, u64::MAX);
+        assert!(report.is_target_met);
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert!(delaunay.is_delaunay());
+    }
+
+    #[test]
+    fn test_2() {
+        // an L shape with a hole and an area limit
+        let shape = vec![
+            vec![
+                IntPoint::new(0, 0),
+                IntPoint::new(2000, 0),
+                IntPoint::new(2000, 1000),
+                IntPoint::new(1000, 1000),
+                IntPoint::new(1000, 2000),
+                IntPoint::new(0, 2000),
+            ],
+            vec![
+                IntPoint::new(300, 300),
+                IntPoint::new(300, 700),
+                IntPoint::new(700, 700),
+                IntPoint::new(700, 300),
+            ],
+        ];
+
+        let max_area = 20_000;
+        let (delaunay, report) = shape
+            .triangulate()
+            .into_delaunay()
+            .refine_to_min_angle(20.0, max_area, None);
+
+        assert_quality(&delaunay, 20.0, max_area);
+
+        assert!(report.is_target_met);
+        assert_eq!(delaunay.area(), shape.area_two());
+    }
+
+    #[test]
+    fn test_3() {
+        // a sliced boundary keeps its points on the original edges
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 1000),
+            IntPoint::new(0, 1000),
+        ]
+        .slice_contour(100);
+
+        let (delaunay, report) =
+            contour
+                .triangulate()
+                .into_delaunay()
+                .refine_to_min_angle(20.0, u64::MAX, None);
+
+        assert_quality(&delaunay, 20.0, u64::MAX);
+
+        assert!(report.is_target_met);
+        assert_eq!(delaunay.area(), contour.area_two());
+        for p in delaunay.points.iter() {
+            assert!((0..=1000).contains(&p.x) && (0..=1000).contains(&p.y));
+        }
+    }
+
+    #[test]
+    fn test_4() {
+        // a constraint is split like a boundary segment
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(1000, 0),
+            IntPoint::new(1000, 1000),
+            IntPoint::new(0, 1000),
+        ];
+
+        let (delaunay, report) = contour
+            .triangulate_with_constraints(&[[IntPoint::new(100, 500), IntPoint::new(900, 500)]])
+            .0
+            .into_delaunay()
+            .refine_to_min_angle(20.0, u64::MAX, None);
+
+        assert_quality(&delaunay, 20.0, u64::MAX);
+
+        assert!(report.is_target_met);
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert!(delaunay.constraints.len() > 1);
+        for e in delaunay.constraints.iter() {
+            assert!(delaunay.has_edge(e[0], e[1]));
+            assert_eq!(delaunay.points[e[0]].y, 500);
+            assert_eq!(delaunay.points[e[1]].y, 500);
+        }
+    }
+
+    #[test]
+    fn test_5() {
+        // no edge has a grid point between its ends
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10007, 1),
+            IntPoint::new(10003, 997),
+            IntPoint::new(1, 1003),
+        ];
+
+        let max_area = 200_000;
+        let (delaunay, report) = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_to_min_angle(20.0, max_area, None);
+
+        assert_quality(&delaunay, 20.0, max_area);
+
+        assert!(report.is_target_met);
+        assert_area(&delaunay, &[contour]);
+    }
+
+    #[test]
+    fn test_6() {
+        // the point limit stops the refinement and leaves the target unmet
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(10007, 1),
+            IntPoint::new(10003, 997),
+            IntPoint::new(1, 1003),
+        ];
+
+        let (delaunay, report) =
+            contour
+                .triangulate()
+                .into_delaunay()
+                .refine_to_min_angle(20.0, 10_000, Some(100));
+
+        delaunay.validate();
+        assert_eq!(report.inserted_points, 100);
+        assert!(!report.is_target_met);
+    }
+
+    #[test]
+    fn test_7() {
+        // the old default of 8 passes stopped after 2 with the target unmet
+        let contour = vec![
+            IntPoint::new(0, 0),
+            IntPoint::new(4453, 0),
+            IntPoint::new(4453, 18030),
+            IntPoint::new(3783, 27045),
+            IntPoint::new(0, 18030),
+        ];
+
+        let (delaunay, report) = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_to_min_angle(20.0, 5_323_782, None);
+
+        assert!(report.is_target_met);
+        assert_area(&delaunay, &[contour]);
+
+        let min_angle = delaunay
+            .triangles
+            .iter()
+            .filter(|t| !delaunay.is_input_angle(t))
+            .map(min_angle)
+            .fold(f64::MAX, f64::min);
+        assert!(min_angle >= 19.5, "{min_angle}");
+    }
+
+    #[test]
+    fn test_random_0() {
+        for _ in 0..500 {
+            let path = random(10_000, 8);
+            let shapes = path.simplify(FillRule::NonZero, IntOverlayOptions::keep_all_points());
+            let Some(first) = shapes.first() else {
+                continue;
+            };
+
+            let (delaunay, report) =
+                first
+                    .triangulate()
+                    .into_delaunay()
+                    .refine_to_min_angle(20.0, u64::MAX, None);
+
+            assert_report(&delaunay, &report, 20.0, u64::MAX);
+            assert_area(&delaunay, first);
+        }
+    }
+
+    #[test]
+    fn test_random_1() {
+        // a rotated and scaled hexagon without small angles always meets the target
+        let hexagon = [(4, 0), (2, 3), (-2, 3), (-4, 0), (-2, -3), (2, -3)];
+        let mut rng = rand::rng();
+        for _ in 0..200 {
+            let u = rng.random_range(-300..=300);
+            let v = rng.random_range(-300..=300);
+            if u * u + v * v < 100 {
+                continue;
+            }
+            let contour: IntPath = hexagon
+                .iter()
+                .map(|&(x, y)| {
+                    let dx = rng.random_range(-2..=2);
+                    let dy = rng.random_range(-2..=2);
+                    IntPoint::new(u * x - v * y + dx, v * x + u * y + dy)
+                })
+                .collect();
+
+            let max_area = rng.random_range(1_000..=1_000_000);
+            let (delaunay, report) = contour
+                .triangulate()
+                .into_delaunay()
+                .refine_to_min_angle(20.0, max_area, None);
+
+            assert!(report.is_target_met);
+            assert_quality(&delaunay, 20.0, max_area);
+            assert_area(&delaunay, &[contour]);
+        }
+    }
+
+    fn assert_report(delaunay: &IntDelaunay, report: &RefineReport, degrees: f64, max_area: u64) {
+        delaunay.validate();
+        if report.is_target_met {
+            assert_quality(delaunay, degrees, max_area);
+            return;
+        }
+        // an unmet target is reported only with a bad triangle left,
+        // e.g. next to a small input angle
+        let quality = Quality::new(degrees, max_area);
+        assert!(delaunay
+            .triangles
+            .iter()
+            .any(|t| quality.is_large(t) || quality.is_skinny(t) && !delaunay.is_input_angle(t)));
+    }
+
+    // a point off a segment moves the boundary by less than one unit
+    fn assert_area(delaunay: &IntDelaunay, shape: &[IntPath]) {
+        let perimeter: i64 = shape
+            .iter()
+            .flat_map(|c| c.iter().zip(c.iter().cycle().skip(1)))
+            .map(|(a, b)| a.sqr_distance(*b).isqrt() + 1)
+            .sum();
+        assert!((delaunay.area() - shape.area_two()).abs() <= 4 * perimeter);
+    }
+
+    fn assert_quality(delaunay: &IntDelaunay, degrees: f64, max_area: u64) {
+        delaunay.validate();
+
+        // a small tolerance for the circumcenters rounded to the grid
+        let quality = Quality::new(degrees - 0.5, max_area);
+        for t in delaunay.triangles.iter() {
+            assert!(!quality.is_large(t));
+            assert!(!quality.is_skinny(t) || delaunay.is_input_angle(t));
+        }
+    }
+
+    fn min_angle(t: &IntTriangle) -> f64 {
+        let [a, b, c] = t.vertices.map(|v| (v.point.x as f64, v.point.y as f64));
+        [(a, b, c), (b, c, a), (c, a, b)]
+            .iter()
+            .map(|&(p, q, r)| {
+                let (ux, uy) = (q.0 - p.0, q.1 - p.1);
+                let (vx, vy) = (r.0 - p.0, r.1 - p.1);
+                (ux * vy - uy * vx)
+                    .abs()
+                    .atan2(ux * vx + uy * vy)
+                    .to_degrees()
+            })
+            .fold(f64::MAX, f64::min)
+    }
+
+    fn random(radius: i32, n: usize) -> IntPath {
+        let a = radius / 2;
+        let mut points = Vec::with_capacity(n);
+        let mut rng = rand::rng();
+        for _ in 0..n {
+            let x = rng.random_range(-a..=a);
+            let y = rng.random_range(-a..=a);
+            points.push(IntPoint { x, y })
+        }
+
+        points
+    }
+}
@@ -0,0 +1,208 @@
+use crate::advanced::delaunay::IntDelaunay;
+use crate::tessellation::circumcenter::{EdgeSelector, MinArea, SelectBiggerAngle};
+use i_overlay::core::fill_rule::FillRule;
+use i_overlay::core::overlay::IntOverlayOptions;
+use i_overlay::core::simplify::Simplify;
+use i_overlay::i_float::int::point::IntPoint;
+use i_overlay::i_float::triangle::Triangle;
+use i_overlay::i_shape::int::shape::IntShapes;
+
+impl IntDelaunay {
+    /// Consuming variant of [`refine_within_mut`](Self::refine_within_mut).
+    #[inline]
+    pub fn refine_within(mut self, region: &IntShapes, min_area: u64) -> Self {
+        self.refine_within_mut(region, min_area);
+        self
+    }
+
+    /// Refines only the triangles whose centroid lies inside `region`, the rest of
+    /// the mesh stays as is apart from the splits that spill over the region border.
+    /// A triangle that holds a region vertex is refined too, so a region smaller
+    /// than the triangles around it is still reached.
+    ///
+    /// The region is cleaned up with the overlay `NonZero` rule first, so it may
+    /// overlap itself or the mesh boundary. Triangles are split like in
+    /// [`refine_with_circumcenters_mut`](Self::refine_with_circumcenters_mut).
+    #[inline]
+    pub fn refine_within_mut(&mut self, region: &IntShapes, min_area: u64) {
+        let region = region.simplify(FillRule::NonZero, IntOverlayOptions::default());
+        self.refine_within_by(
+            |[a, b, c]| {
+                contains(&region, centroid(a, b, c))
+                    || region
+                        .iter()
+                        .flatten()
+                        .flatten()
+                        .any(|&p| Triangle::is_contain_point(p, a, b, c))
+            },
+            min_area,
+        );
+    }
+
+    // `is_inside` tells if a triangle belongs to the region
+    pub(crate) fn refine_within_by<F: Fn([IntPoint; 3]) -> bool>(
+        &mut self,
+        is_inside: F,
+        min_area: u64,
+    ) {
+        let selector = Within {
+            is_inside,
+            selector: MinArea::new(min_area, SelectBiggerAngle),
+        };
+        self.refine_with_selector_mut(&selector);
+    }
+}
+
+struct Within<F, S> {
+    is_inside: F,
+    selector: S,
+}
+
+impl<F: Fn([IntPoint; 3]) -> bool, S: EdgeSelector> EdgeSelector for Within<F, S> {
+    #[inline]
+    fn select(&self, delaunay: &IntDelaunay, index: usize) -> Option<usize> {
+        let position = self.selector.select(delaunay, index)?;
+        if (self.is_inside)(delaunay.triangles[index].vertices.map(|v| v.point)) {
+            Some(position)
+        } else {
+            None
+        }
+    }
+}
+
+#[inline]
+fn centroid(a: IntPoint, b: IntPoint, c: IntPoint) -> IntPoint {
+    IntPoint::new(
+        ((a.x as i64 + b.x as i64 + c.x as i64) / 3) as i32,
+        ((a.y as i64 + b.y as i64 + c.y as i64) / 3) as i32,
+    )
+}
+
+// even-odd crossing test, holes of the simplified shapes flip it back,
+// shared by the float region that is mapped into the int space of the mesh,
+// `ContourExtension::contains` of i_shape is not used as it multiplies in i32
+// and overflows for coordinates beyond 46340
+fn contains(shapes: &IntShapes, p: IntPoint) -> bool {
+    let mut is_inside = false;
+    for contour in shapes.iter().flatten() {
+        let Some(&last) = contour.last() else {
+            continue;
+        };
+        let mut b = last;
+        for &a in contour.iter() {
+            if (a.y > p.y) != (b.y > p.y) {
+                let dx = b.x as i64 - a.x as i64;
+                let dy = b.y as i64 - a.y as i64;
+                // p.x < a.x + (p.y - a.y) * dx / dy without the division
+                let lhs = (p.x as i64 - a.x as i64) as i128 * dy as i128;
+                let rhs = (p.y as i64 - a.y as i64) as i128 * dx as i128;
+                if (dy > 0 && lhs < rhs) || (dy < 0 && lhs > rhs) {
+                    is_inside = !is_inside;
+                }
+            }
+            b = a;
+        }
+    }
+    is_inside
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::int::triangulatable::IntTriangulatable;
+    use crate::tessellation::region::contains;
+    use alloc::vec;
+    use i_overlay::i_float::int::point::IntPoint;
+    use i_overlay::i_shape::int::area::Area;
+    use i_overlay::i_shape::int::shape::IntContour;
+
+    fn rect(x0: i32, y0: i32, x1: i32, y1: i32) -> IntContour {
+        vec![
+            IntPoint::new(x0, y0),
+            IntPoint::new(x1, y0),
+            IntPoint::new(x1, y1),
+            IntPoint::new(x0, y1),
+        ]
+    }
+
+    #[test]
+    fn test_0() {
+        let contour = rect(0, 0, 1000, 1000);
+        let region = vec![vec![rect(0, 0, 300, 300)]];
+
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_within(&region, 500);
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+        assert!(delaunay.is_delaunay());
+
+        let inside = delaunay
+            .points
+            .iter()
+            .filter(|p| p.x <= 300 && p.y <= 300)
+            .count();
+        let far = delaunay
+            .points
+            .iter()
+            .filter(|p| p.x > 600 || p.y > 600)
+            .count();
+        assert!(inside > 50);
+        assert!(far < 10);
+    }
+
+    #[test]
+    fn test_1() {
+        // a ring region, its hole stays coarse
+        let contour = rect(0, 0, 1000, 1000);
+        let mut hole = rect(300, 300, 700, 700);
+        hole.reverse();
+        let region = vec![vec![rect(100, 100, 900, 900), hole]];
+
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_within(&region, 500);
+
+        delaunay.validate();
+        assert_eq!(delaunay.area(), contour.area_two());
+
+        let ring = delaunay
+            .points
+            .iter()
+            .filter(|p| (150..=250).contains(&p.x) && (150..=850).contains(&p.y))
+            .count();
+        let hole = delaunay
+            .points
+            .iter()
+            .filter(|p| (400..=600).contains(&p.x) && (400..=600).contains(&p.y))
+            .count();
+        assert!(ring > 20);
+        assert!(ring > 4 * hole);
+    }
+
+    #[test]
+    fn test_2() {
+        let shapes = vec![vec![
+            rect(0, 0, 10, 10),
+            vec![
+                IntPoint::new(3, 3),
+                IntPoint::new(3, 7),
+                IntPoint::new(7, 7),
+                IntPoint::new(7, 3),
+            ],
+        ]];
+
+        assert!(contains(&shapes, IntPoint::new(1, 1)));
+        assert!(contains(&shapes, IntPoint::new(8, 5)));
+        assert!(!contains(&shapes, IntPoint::new(5, 5)));
+        assert!(!contains(&shapes, IntPoint::new(-1, 5)));
+        assert!(!contains(&shapes, IntPoint::new(11, 5)));
+
+        // far coordinates do not overflow
+        let big = vec![vec![rect(-i32::MAX, -i32::MAX, i32::MAX, i32::MAX)]];
+        assert!(contains(&big, IntPoint::new(i32::MAX - 1, 0)));
+        assert!(!contains(&big, IntPoint::new(i32::MIN, 0)));
+    }
+}
@@ -57,8 +57,15 @@ impl SliceContour for IntShapes {
     }
 }
 
+// pushes the points that slice ab into pieces of about `radius`, b included
 #[inline]
-fn extract(a: IntPoint, b: IntPoint, radius: u64, sqr_radius: u64, contour: &mut IntContour) {
+pub(crate) fn extract(
+    a: IntPoint,
+    b: IntPoint,
+    radius: u64,
+    sqr_radius: u64,
+    contour: &mut IntContour,
+) {
     let ab = b.subtract(a);
     let sqr_len = ab.sqr_length() as u64;
     if sqr_len <= sqr_radius {
@@ -100,6 +107,68 @@ fn extract(a: IntPoint, b: IntPoint, radius: u64, sqr_radius: u64, contour: &mut
     contour.push(b);
 }
 
+// pushes the grid points of ab closest to the cuts of `extract`, b excluded,
+// every pushed point lies exactly on ab, so fewer cuts are made if ab has
+// fewer grid points between its ends
+#[inline]
+pub(crate) fn extract_on_segment(
+    a: IntPoint,
+    b: IntPoint,
+    radius: u64,
+    sqr_radius: u64,
+    points: &mut Vec<IntPoint>,
+) {
+    let ab = b.subtract(a);
+    let sqr_len = ab.sqr_length() as u64;
+    if sqr_len <= sqr_radius {
+        return;
+    }
+    let n = (sqr_len.isqrt() + (radius >> 1)) / radius;
+    let g = gcd(ab.x.unsigned_abs(), ab.y.unsigned_abs());
+    if n <= 1 || g <= 1 {
+        return;
+    }
+
+    let sx = ab.x / g as i64;
+    let sy = ab.y / g as i64;
+    let mut last = 0;
+    for i in 1..n {
+        let k = (2 * i * g + n) / (2 * n);
+        if k == last || k >= g {
+            continue;
+        }
+        last = k;
+        let x = a.x as i64 + sx * k as i64;
+        let y = a.y as i64 + sy * k as i64;
+        points.push(IntPoint::new(x as i32, y as i32));
+    }
+}
+
+// the grid point on the segment closest to its middle, none if the segment
+// has no grid points between its ends
+#[inline]
+pub(crate) fn segment_split_point(a: IntPoint, b: IntPoint) -> Option<IntPoint> {
+    let dx = b.x as i64 - a.x as i64;
+    let dy = b.y as i64 - a.y as i64;
+    let n = gcd(dx.unsigned_abs(), dy.unsigned_abs()) as i64;
+    if n < 2 {
+        return None;
+    }
+    let k = n >> 1;
+    let x = a.x as i64 + dx / n * k;
+    let y = a.y as i64 + dy / n * k;
+
+    Some(IntPoint::new(x as i32, y as i32))
+}
+
+#[inline]
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
 #[cfg(test)]
 mod tests {
     use crate::tessellation::split::SliceContour;
@@ -1,3 +1,5 @@
+#![allow(clippy::useless_vec)]
+
 #[cfg(test)]
 mod tests {
     use i_overlay::i_shape::base::data::Contour;
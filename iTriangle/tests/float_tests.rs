@@ -2,11 +2,21 @@
 mod tests {
     use i_overlay::core::fill_rule::FillRule;
     use i_overlay::float::simplify::SimplifyShape;
+    use i_overlay::i_float::float::rect::FloatRect;
     use i_overlay::i_shape::base::data::Contour;
     use i_overlay::i_shape::float::area::Area;
+    use i_triangle::advanced::edge::EdgeKind;
+    use i_triangle::float::builder::TriangulationBuilder;
+    use i_triangle::float::circumcenter::FloatEdgeSelector;
+    use i_triangle::float::delaunay::Delaunay;
     use i_triangle::float::triangulatable::Triangulatable;
     use i_triangle::float::triangulation::Triangulation;
     use i_triangle::float::triangulator::Triangulator;
+    use i_triangle::int::diagnostics::ValidationIssue;
+    use i_triangle::int::error::TriangulationError;
+    use i_triangle::int::provenance::VertexOrigin;
+    use i_triangle::int::steiner::SteinerOutcome;
+    use i_triangle::tessellation::circumcenter::RefineOptions;
     use rand::RngExt;
 
     #[test]
@@ -89,6 +99,541 @@ mod tests {
         t1.validate(area, 0.001);
     }
 
+    #[test]
+    fn test_4() {
+        let contour = [[0.0, 0.0], [10.0, -1.0], [20.0, 0.0], [10.0, 1.0]];
+
+        let (raw, missed) = contour.triangulate_with_constraints(&[[[0.0, 0.0], [20.0, 0.0]]]);
+        assert!(missed.is_empty());
+
+        let delaunay = raw.into_delaunay();
+
+        let points = delaunay.points();
+        let indices = delaunay.triangle_indices::<u16>();
+
+        assert_eq!(delaunay.constraints().len(), 1);
+        let edge = delaunay.constraints().iter().next().unwrap();
+        assert_eq!(points[edge[0]], [0.0, 0.0]);
+        assert_eq!(points[edge[1]], [20.0, 0.0]);
+
+        let has_edge = indices
+            .chunks(3)
+            .any(|t| t.contains(&(edge[0] as u16)) && t.contains(&(edge[1] as u16)));
+        assert!(has_edge);
+    }
+
+    #[test]
+    fn test_5() {
+        let points = [
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [2.0, 0.0],
+            [0.0, 1.0],
+            [1.0, 1.0],
+            [2.0, 1.0],
+            [0.5, 0.5],
+        ];
+
+        let delaunay = Delaunay::from_points(&points);
+        let triangulation = delaunay.to_triangulation::<u16>();
+
+        assert_eq!(triangulation.points.len(), 7);
+        triangulation.validate(2.0, 0.001);
+
+        let polygons = delaunay.to_convex_polygons();
+        assert!(!polygons.is_empty());
+
+        let refined = delaunay.refine_with_circumcenters(0.01);
+        refined.to_triangulation::<u16>().validate(2.0, 0.001);
+    }
+
+    #[test]
+    fn test_6() {
+        let delaunay = Delaunay::<[f64; 2]>::from_points(&[[1.0, 1.0], [2.0, 2.0], [3.0, 3.0]]);
+        assert!(delaunay.triangle_indices::<u16>().is_empty());
+
+        let delaunay = Delaunay::<[f64; 2]>::from_points(&[]);
+        assert!(delaunay.triangle_indices::<u16>().is_empty());
+    }
+
+    #[test]
+    fn test_7() {
+        let contour = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+        let points = [[2.0, 2.0], [2.0, 0.0], [4.0, 4.0], [8.0, 2.0], [2.0, 2.0]];
+
+        let (raw, outcomes) = contour.triangulate_with_steiner_outcomes(&points);
+        raw.to_triangulation::<u16>().validate(16.0, 0.001);
+
+        let vertices = raw.points();
+        assert_eq!(vertices.len(), 6);
+
+        assert_eq!(outcomes.len(), points.len());
+        match outcomes[1] {
+            SteinerOutcome::Inserted { vertex_index } => {
+                assert_eq!(vertices[vertex_index], points[1])
+            }
+            _ => panic!("edge point is not inserted"),
+        }
+        assert!(matches!(outcomes[0], SteinerOutcome::Inserted { .. }));
+        assert!(matches!(
+            outcomes[2],
+            SteinerOutcome::OnExistingVertex { .. }
+        ));
+        assert_eq!(outcomes[3], SteinerOutcome::Outside);
+        assert!(matches!(outcomes[4], SteinerOutcome::Merged { .. }));
+    }
+
+    #[test]
+    fn test_8() {
+        // the outer contour goes clockwise, validation reverses it
+        let shape = vec![
+            vec![[0.0, 0.0], [0.0, 4.0], [4.0, 4.0], [4.0, 0.0]],
+            vec![[1.0, 1.0], [3.0, 1.0], [3.0, 3.0], [1.0, 3.0]],
+        ];
+
+        let mut triangulator = Triangulator::<u16>::default();
+        triangulator.provenance(true);
+        assert!(triangulator.is_provenance());
+
+        let triangulation = triangulator.triangulate(&shape);
+        let origins = triangulator.vertex_origins();
+        assert_eq!(origins.len(), triangulation.points.len());
+
+        for (p, origin) in triangulation.points.iter().zip(origins.iter()) {
+            let VertexOrigin::Input {
+                contour, vertex, ..
+            } = *origin
+            else {
+                panic!("unexpected origin");
+            };
+            assert_eq!(shape[contour][vertex], *p);
+        }
+
+        triangulator.provenance(false);
+        triangulator.triangulate(&shape);
+        assert!(triangulator.vertex_origins().is_empty());
+    }
+
+    #[test]
+    fn test_9() {
+        let contour = [[0.0f64, 0.0], [8.0, 0.0], [8.0, 4.0], [0.0, 4.0]];
+        let z = [0.0f64, 8.0, 12.0, 4.0];
+
+        let (raw, mut attributes) = contour.triangulate_with_attributes(&z).unwrap();
+        assert_eq!(attributes.len(), raw.points().len());
+
+        let delaunay = raw
+            .into_delaunay()
+            .refine_with_circumcenters_and_attributes(0.5, &mut attributes)
+            .unwrap();
+        let points = delaunay.points();
+        assert!(points.len() > contour.len());
+        assert_eq!(attributes.len(), points.len());
+
+        // z = x + y is linear and stays exact
+        for (p, z) in points.iter().zip(attributes.iter()) {
+            assert!((p[0] + p[1] - z).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn test_10() {
+        let shape = [
+            vec![[0.0, 0.0], [6.0, 0.0], [6.0, 6.0], [0.0, 6.0]],
+            vec![[2.0, 2.0], [2.0, 4.0], [4.0, 4.0], [4.0, 2.0]],
+        ];
+
+        let delaunay = shape.triangulate().into_delaunay();
+        let kinds = delaunay.edge_kinds();
+        assert_eq!(kinds.len(), delaunay.triangle_neighbors().len());
+
+        let count = |kind: EdgeKind| kinds.iter().flatten().filter(|&&k| k == kind).count();
+        assert_eq!(count(EdgeKind::OuterBoundary), 4);
+        assert_eq!(count(EdgeKind::HoleBoundary { contour: Some(1) }), 4);
+        assert_eq!(count(EdgeKind::Constraint), 0);
+    }
+
+    #[test]
+    fn test_11() {
+        let mut triangulator = Triangulator::<u8>::default();
+
+        let nan = [[0.0, 0.0], [1.0, f64::NAN], [1.0, 1.0]];
+        assert_eq!(
+            triangulator.try_triangulate(&nan.to_vec()).err(),
+            Some(TriangulationError::CoordinateOutOfRange)
+        );
+
+        let line = [[0.0, 0.0], [1.0, 0.0]];
+        assert_eq!(
+            triangulator.try_triangulate(&line.to_vec()).err(),
+            Some(TriangulationError::EmptyInput)
+        );
+
+        let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        let triangulation = triangulator.try_triangulate(&square.to_vec()).unwrap();
+        assert_eq!(triangulation.indices.len(), 6);
+
+        let steiner = [[0.5, 0.5], [0.5, f64::INFINITY]];
+        assert_eq!(
+            square.try_triangulate_with_steiner_points(&steiner).err(),
+            Some(TriangulationError::InvalidSteinerPoint { index: 1 })
+        );
+
+        // 64 copies of a square take 256 points, one more than u8 can address
+        let mut builder = TriangulationBuilder::default();
+        for _ in 0..63 {
+            builder.try_append(triangulation.clone()).unwrap();
+        }
+        assert!(builder.try_append(triangulation.clone()).is_err());
+        assert_eq!(builder.build().points.len(), 4 * 63);
+    }
+
+    #[test]
+    fn test_12() {
+        // a clockwise bow tie
+        let contour = vec![[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+
+        let mut triangulator = Triangulator::<u16>::default();
+        triangulator.triangulate(&contour);
+        assert!(triangulator.validation_issues::<[f64; 2]>().is_empty());
+
+        triangulator.diagnostics(true);
+        assert!(triangulator.is_diagnostics());
+        triangulator.triangulate(&contour);
+
+        let issues = triangulator.validation_issues::<[f64; 2]>();
+        let crossing = issues.iter().find_map(|issue| match issue {
+            ValidationIssue::SelfIntersection { point } => Some(*point),
+            _ => None,
+        });
+        let [x, y] = crossing.unwrap();
+        assert!((x - 0.5).abs() < 0.001 && (y - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_13() {
+        let contour = [[0.0, 0.0], [10.0, 0.0], [10.0, 1.0], [0.0, 1.0]];
+
+        let (delaunay, report) = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_to_min_angle(20.0, 1.0, None);
+        assert!(report.is_target_met);
+
+        let triangulation = delaunay.to_triangulation::<u16>();
+        triangulation.validate(10.0, 0.001);
+
+        let points = &triangulation.points;
+        for t in triangulation.indices.chunks(3) {
+            let [a, b, c] = [
+                points[t[0] as usize],
+                points[t[1] as usize],
+                points[t[2] as usize],
+            ];
+            assert!(area(a, b, c) <= 1.0 + 0.001);
+            assert!(min_angle(a, b, c) >= 19.5);
+        }
+    }
+
+    #[test]
+    fn test_14() {
+        let contour = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+
+        // dense near the left side
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_sizing(
+                |p: [f64; 2]| 0.5 + 0.2 * p[0],
+                &RefineOptions::with_max_points(usize::MAX),
+            )
+            .0;
+
+        let triangulation = delaunay.to_triangulation::<u16>();
+        triangulation.validate(100.0, 0.001);
+
+        let points = &triangulation.points;
+        let left = points.iter().filter(|p| p[0] < 2.0).count();
+        let right = points.iter().filter(|p| p[0] > 8.0).count();
+        assert!(left > 2 * right);
+    }
+
+    #[test]
+    fn test_15() {
+        let contour = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+
+        let mut delaunay = contour.triangulate().into_delaunay();
+        let report = delaunay.refine_to_max_area_mut(0.5, None);
+        assert!(report.is_target_met);
+        assert!(report.inserted_points > 0);
+
+        let triangulation = delaunay.to_triangulation::<u16>();
+        triangulation.validate(100.0, 0.001);
+
+        let points = &triangulation.points;
+        for t in triangulation.indices.chunks(3) {
+            let [a, b, c] = [
+                points[t[0] as usize],
+                points[t[1] as usize],
+                points[t[2] as usize],
+            ];
+            assert!(area(a, b, c) <= 0.5 + 0.001);
+        }
+    }
+
+    #[test]
+    fn test_16() {
+        // split long edges in the upper half only
+        struct UpperHalf;
+
+        impl FloatEdgeSelector<[f64; 2]> for UpperHalf {
+            fn select(&self, points: [[f64; 2]; 3], _: [bool; 3]) -> Option<usize> {
+                if points.iter().any(|p| p[1] < 5.0) {
+                    return None;
+                }
+                (0..3).find(|&i| {
+                    let [a, b] = [points[(i + 1) % 3], points[(i + 2) % 3]];
+                    (a[0] - b[0]).hypot(a[1] - b[1]) > 1.0
+                })
+            }
+        }
+
+        let contour = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_circumcenters(2.0)
+            .refine_with_selector(&UpperHalf);
+
+        let triangulation = delaunay.to_triangulation::<u16>();
+        triangulation.validate(100.0, 0.001);
+
+        let points = &triangulation.points;
+        let upper = points.iter().filter(|p| p[1] > 5.0).count();
+        let lower = points.iter().filter(|p| p[1] < 5.0).count();
+        assert!(upper > 2 * lower);
+    }
+
+    #[test]
+    fn test_17() {
+        // the region sticks out of the mesh
+        let contour = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let region = [[-5.0, -5.0], [3.0, -5.0], [3.0, 3.0], [-5.0, 3.0]];
+
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_within(&region, 0.05);
+
+        let triangulation = delaunay.to_triangulation::<u16>();
+        triangulation.validate(100.0, 0.001);
+
+        let points = &triangulation.points;
+        let inside = points.iter().filter(|p| p[0] <= 3.0 && p[1] <= 3.0).count();
+        let far = points.iter().filter(|p| p[0] > 6.0 || p[1] > 6.0).count();
+        assert!(inside > 20);
+        assert!(far < 10);
+    }
+
+    #[test]
+    fn test_18() {
+        let contour = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+
+        let mut delaunay = contour.triangulate().into_delaunay();
+        let options = RefineOptions {
+            max_passes: 2,
+            ..Default::default()
+        };
+        let report = delaunay.refine_with_options_mut(0.1, &options);
+        assert!(report.passes <= 2);
+        assert!(report.inserted_points > 0);
+        assert!(!report.is_target_met);
+
+        let triangulation = delaunay.to_triangulation::<u16>();
+        triangulation.validate(100.0, 0.001);
+        assert_eq!(triangulation.points.len(), 4 + report.inserted_points);
+    }
+
+    #[test]
+    fn test_19() {
+        let shape = [
+            vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            vec![[4.0, 4.0], [4.0, 6.0], [6.0, 6.0], [6.0, 4.0]],
+        ];
+        let triangulation = shape.triangulate().to_triangulation::<u16>();
+        let prepared = triangulation.prepare();
+        assert_eq!(prepared.triangles_count(), triangulation.indices.len() / 3);
+
+        let mut points = Vec::new();
+        for i in -2..=22 {
+            for j in -2..=22 {
+                points.push([0.5 * i as f64, 0.5 * j as f64]);
+            }
+        }
+        let expected = triangulation.locate_points::<f64>(&points);
+        for (p, location) in points.iter().zip(expected.iter()) {
+            assert_eq!(&prepared.locate_point(p), location);
+        }
+
+        // the hole is empty, the whole mesh is reached from far away
+        assert!(prepared.triangles_in_radius(&[5.0, 5.0], 0.5).is_empty());
+        assert_eq!(
+            prepared.triangles_in_radius(&[20.0, 20.0], 100.0).len(),
+            prepared.triangles_count()
+        );
+        assert!(!prepared.triangles_in_radius(&[5.0, 5.0], 1.0).is_empty());
+
+        let rect = FloatRect::new(-5.0, 1.0, -5.0, 1.0);
+        let corner = prepared.triangles_in_rect(&rect);
+        assert!(!corner.is_empty());
+        assert!(corner.len() < prepared.triangles_count());
+        assert!(prepared
+            .triangles_in_rect(&FloatRect::new(11.0, 12.0, 0.0, 10.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_20() {
+        let contour = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let triangulation = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_with_circumcenters(5.0)
+            .to_triangulation::<u16>();
+
+        // uv and height fields
+        let uv: Vec<_> = triangulation
+            .points
+            .iter()
+            .map(|p| [p[0] / 10.0, p[1] / 10.0])
+            .collect();
+        let height: Vec<_> = triangulation.points.iter().map(|p| p[0] + p[1]).collect();
+
+        let points: [[f64; 2]; 4] = [[2.5, 7.5], [10.0, 5.0], [0.0, 0.0], [11.0, 5.0]];
+        let uv_result = triangulation.interpolate(&uv, &points);
+        let height_result = triangulation.interpolate(&height, &points);
+
+        for (i, p) in points.iter().take(3).enumerate() {
+            let [u, v] = uv_result[i].unwrap();
+            assert!((u - p[0] / 10.0).abs() < 1e-6);
+            assert!((v - p[1] / 10.0).abs() < 1e-6);
+            assert!((height_result[i].unwrap() - p[0] - p[1]).abs() < 1e-6);
+        }
+        assert_eq!(uv_result[3], None);
+        assert_eq!(height_result[2], Some(0.0));
+
+        let weights = triangulation.locate_barycentric(&points);
+        let sum: f64 = weights[0].unwrap().weights.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-9);
+        assert!(weights[1].unwrap().weights.contains(&0.0));
+
+        let prepared = triangulation.prepare();
+        for (p, barycentric) in points.iter().zip(weights.iter()) {
+            let prepared_barycentric = prepared.locate_barycentric(p);
+            assert_eq!(&prepared_barycentric, barycentric);
+        }
+    }
+
+    #[test]
+    fn test_21() {
+        let shape: [Vec<[f64; 2]>; 2] = [
+            vec![[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]],
+            vec![[4.0, 4.0], [4.0, 6.0], [6.0, 6.0], [6.0, 4.0]],
+        ];
+        let delaunay = shape.triangulate().into_delaunay();
+        let prepared = delaunay.prepare();
+
+        let inside = prepared.closest_point(&[2.0, 3.0]).unwrap();
+        assert_eq!(inside.point, [2.0, 3.0]);
+        assert_eq!(inside.sqr_distance, 0.0);
+
+        // far outside the mesh bounds
+        let far = prepared.closest_point(&[-100.0, 2.5]).unwrap();
+        assert!(far.point[0].abs() < 1e-6);
+        assert!((far.point[1] - 2.5).abs() < 1e-6);
+        assert!((far.sqr_distance - 10_000.0).abs() < 1e-3);
+
+        // from the hole to its border
+        let hole = prepared.closest_point(&[5.0, 5.5]).unwrap();
+        assert!((hole.point[1] - 6.0).abs() < 1e-6);
+        assert!((hole.sqr_distance - 0.25).abs() < 1e-6);
+
+        let triangulation = delaunay.to_triangulation::<u16>();
+        for c in [inside, far, hole] {
+            let location = triangulation.locate_points::<f64>(&[c.point]);
+            assert!(location[0].triangle().is_some());
+        }
+        assert!(triangulation
+            .prepare()
+            .closest_point(&[20.0, 20.0])
+            .is_some());
+    }
+
+    #[test]
+    fn test_22() {
+        // a ring region far larger than the mesh, its hole stays coarse
+        let contour = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let region: [Vec<[f64; 2]>; 2] = [
+            vec![[-1e6, -1e6], [1e6, -1e6], [1e6, 1e6], [-1e6, 1e6]],
+            vec![[2.0, 2.0], [2.0, 8.0], [8.0, 8.0], [8.0, 2.0]],
+        ];
+
+        let delaunay = contour
+            .triangulate()
+            .into_delaunay()
+            .refine_within(&region, 0.05);
+
+        let triangulation = delaunay.to_triangulation::<u16>();
+        triangulation.validate(100.0, 0.001);
+
+        let points = &triangulation.points;
+        let rim = points.iter().filter(|p| p[0] < 1.5).count();
+        let hole = points
+            .iter()
+            .filter(|p| (3.0..=7.0).contains(&p[0]) && (3.0..=7.0).contains(&p[1]))
+            .count();
+        assert!(rim > 20);
+        assert!(rim > 4 * hole);
+    }
+
+    #[test]
+    fn test_23() {
+        let contour: [[f64; 2]; 4] = [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]];
+        let prepared = contour.triangulate().to_triangulation::<u16>().prepare();
+
+        // far off the mesh, beyond the integer grid
+        let far = prepared.closest_point(&[1e12, 5.0]).unwrap();
+        assert!((far.point[0] - 10.0).abs() < 1e-6);
+        assert!((far.point[1] - 5.0).abs() < 1e-6);
+
+        let far = prepared.closest_point(&[-1e300, -1e300]).unwrap();
+        assert!(far.point[0].abs() < 1e-6 && far.point[1].abs() < 1e-6);
+
+        assert!(prepared.closest_point(&[f64::INFINITY, 0.0]).is_none());
+        assert!(prepared.closest_point(&[0.0, f64::NEG_INFINITY]).is_none());
+        assert!(prepared.closest_point(&[f64::NAN, 5.0]).is_none());
+    }
+
+    #[test]
+    fn test_24() {
+        // a far query point does not change the quantization of the mesh
+        let contour: [[f64; 2]; 4] = [[0.0, 0.0], [1.0, 0.0], [1.0, 0.01], [0.0, 0.01]];
+        let triangulation = contour.triangulate().to_triangulation::<u16>();
+        let values: Vec<_> = triangulation.points.iter().map(|p| p[0]).collect();
+
+        let near = [0.7, 0.005];
+        let alone = triangulation.locate_barycentric(&[near]);
+        let with_far = triangulation.locate_barycentric(&[near, [1e9, 1e9], [f64::NAN, 0.0]]);
+        assert!(alone[0].is_some());
+        assert_eq!(with_far[0], alone[0]);
+        assert_eq!(with_far[1], None);
+        assert_eq!(with_far[2], None);
+
+        let result = triangulation.interpolate(&values, &[near, [1e9, 1e9]]);
+        assert!((result[0].unwrap() - 0.7).abs() < 1e-9);
+        assert_eq!(result[1], None);
+    }
+
     #[test]
     fn test_random_0() {
         let mut triangulator = Triangulator::<u32>::default();
@@ -208,6 +753,20 @@ mod tests {
         }
     }
 
+    fn area(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+        0.5 * ((b[0] - a[0]) * (c[1] - a[1]) - (c[0] - a[0]) * (b[1] - a[1])).abs()
+    }
+
+    fn min_angle(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+        let angle = |p: [f64; 2], q: [f64; 2], r: [f64; 2]| {
+            let (ux, uy) = (q[0] - p[0], q[1] - p[1]);
+            let (vx, vy) = (r[0] - p[0], r[1] - p[1]);
+            let cos = (ux * vx + uy * vy) / ((ux * ux + uy * uy) * (vx * vx + vy * vy)).sqrt();
+            cos.clamp(-1.0, 1.0).acos().to_degrees()
+        };
+        angle(a, b, c).min(angle(b, c, a)).min(angle(c, a, b))
+    }
+
     fn random(radius: i32, n: usize) -> Contour<[f32; 2]> {
         let a = radius / 2;
         let mut points = Vec::with_capacity(n);